@@ -1,3 +1,6 @@
+use super::{AstName, NameTable};
+use std::fmt::Display;
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum LexType {
     Eof,
@@ -28,6 +31,7 @@ pub enum LexType {
     Sub,
     Mul,
     Div,
+    FloorDiv,
     Mod,
     Pow,
     Concat,
@@ -35,13 +39,14 @@ pub enum LexType {
     SubAssign,
     MulAssign,
     DivAssign,
+    FloorDivAssign,
     ModAssign,
     PowAssign,
     ConcatAssign,
     RawString(String),
     QuotedString(String),
     Number(String),
-    Name(String),
+    Name(AstName),
     Comment(String),
     BlockComment,
     BrokenString,
@@ -50,8 +55,13 @@ pub enum LexType {
     SingletonOr,
     SingletonAnd,
     QuestionMark,
+    InterpStringBegin(String),
+    InterpStringMid(String),
+    InterpStringEnd(String),
+    // an interpolated string without expressions
+    InterpStringSimple(String),
+    BrokenInterpDoubleBrace,
     Error,
-    Begin,
     And,
     Break,
     Do,
@@ -126,140 +136,137 @@ impl LexType {
             LexType::BrokenComment => 45,
             LexType::BrokenUnicode => 46,
             LexType::Error => 47,
-            LexType::Begin => 48,
-            LexType::And => 49,
-            LexType::Break => 50,
-            LexType::Do => 51,
-            LexType::Else => 52,
-            LexType::ElseIf => 53,
-            LexType::End => 54,
-            LexType::False => 55,
-            LexType::For => 56,
-            LexType::Function => 57,
-            LexType::If => 58,
-            LexType::In => 59,
-            LexType::Local => 60,
-            LexType::Nil => 61,
-            LexType::Not => 62,
-            LexType::Or => 63,
-            LexType::Repeat => 64,
-            LexType::Return => 65,
-            LexType::Then => 66,
-            LexType::True => 67,
-            LexType::Until => 68,
-            LexType::While => 69,
-            LexType::SingletonOr => 70,
-            LexType::SingletonAnd => 71,
-            LexType::QuestionMark => 72,
+            LexType::And => 48,
+            LexType::Break => 49,
+            LexType::Do => 50,
+            LexType::Else => 51,
+            LexType::ElseIf => 52,
+            LexType::End => 53,
+            LexType::False => 54,
+            LexType::For => 55,
+            LexType::Function => 56,
+            LexType::If => 57,
+            LexType::In => 58,
+            LexType::Local => 59,
+            LexType::Nil => 60,
+            LexType::Not => 61,
+            LexType::Or => 62,
+            LexType::Repeat => 63,
+            LexType::Return => 64,
+            LexType::Then => 65,
+            LexType::True => 66,
+            LexType::Until => 67,
+            LexType::While => 68,
+            LexType::SingletonOr => 69,
+            LexType::SingletonAnd => 70,
+            LexType::QuestionMark => 71,
+            LexType::InterpStringBegin(_) => 72,
+            LexType::InterpStringMid(_) => 73,
+            LexType::InterpStringEnd(_) => 74,
+            LexType::InterpStringSimple(_) => 75,
+            LexType::BrokenInterpDoubleBrace => 76,
+            LexType::FloorDiv => 77,
+            LexType::FloorDivAssign => 78,
         }
     }
 
     pub const fn count() -> i32 {
-        70
+        79
     }
-}
-
-impl ToString for LexType {
-    fn to_string(&self) -> String {
-        String::from(match self {
-            LexType::Eof => "Eof",
-            LexType::CharEnd => "CharEnd",
-            LexType::Assign => "Assign",
-            LexType::Equal => "Equal",
-            LexType::Less => "Less",
-            LexType::Greater => "Greater",
-            LexType::LessEqual => "LessEqual",
-            LexType::GreaterEqual => "GreaterEqual",
-            LexType::Colon => "Colon",
-            LexType::LeftSquareBracket => "LeftSquareBracket",
-            LexType::RightSquareBracket => "RightSquareBracket",
-            LexType::LeftRoundBracket => "LeftRoundBracket",
-            LexType::RightRoundBracket => "RightSquareBracket",
-            LexType::LeftCurlyBracket => "LeftCurlyBracket",
-            LexType::RightCurlyBracket => "RightCurlyBracket",
-            LexType::Comma => "Comma",
-            LexType::Sharp => "Sharp",
-            LexType::Semicolon => "Semicolon",
-            LexType::NotEqual => "NotEqual",
-            LexType::Dot => "Dot",
-            LexType::Dot2 => "Dot2",
-            LexType::Dot3 => "Dot3",
-            LexType::SkinnyArrow => "SkinnyArrow",
-            LexType::DoubleColon => "DoubleColon",
-            LexType::Add => "Add",
-            LexType::Sub => "Sub",
-            LexType::Mul => "Mul",
-            LexType::Div => "Div",
-            LexType::Mod => "Mod",
-            LexType::Pow => "Pow",
-            LexType::Concat => "Concat",
-            LexType::AddAssign => "AddAssign",
-            LexType::SubAssign => "SubAssign",
-            LexType::MulAssign => "MulAssign",
-            LexType::DivAssign => "DivAssign",
-            LexType::ModAssign => "ModAssign",
-            LexType::PowAssign => "PowAssign",
-            LexType::ConcatAssign => "ConcatAssign",
-            LexType::RawString(val) => {
-                let mut output = String::from("RawString: ");
-                output.push_str(val.as_str());
-
-                return output;
-            }
-            LexType::QuotedString(val) => {
-                let mut output = String::from("QuotedString: ");
-                output.push_str(val.as_str());
-
-                return output;
-            }
-            LexType::Number(val) => {
-                let mut output = String::from("Number: ");
-                output.push_str(val.as_str());
 
-                return output;
-            }
-            LexType::Name(val) => {
-                let mut output = String::from("Name: ");
-                output.push_str(val.as_str());
-
-                return output;
-            }
-            LexType::Comment(val) => {
-                let mut output = String::from("Comment: ");
-                output.push_str(val.as_str());
+    // like the Display text, with names spelled out from the table they were read into
+    pub fn to_source_string(&self, names: &NameTable) -> String {
+        match self {
+            LexType::Name(name) => format!("'{}'", names.get_value(*name)),
+            _ => self.to_string(),
+        }
+    }
+}
 
-                return output;
+// the lexeme as luau spells it in diagnostics, names are spelled as `identifier` since the text
+// is in the name table, see to_source_string
+impl Display for LexType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LexType::Eof => "<eof>",
+            LexType::CharEnd => "<unknown>",
+            LexType::Assign => "'='",
+            LexType::Equal => "'=='",
+            LexType::Less => "'<'",
+            LexType::Greater => "'>'",
+            LexType::LessEqual => "'<='",
+            LexType::GreaterEqual => "'>='",
+            LexType::Colon => "':'",
+            LexType::LeftSquareBracket => "'['",
+            LexType::RightSquareBracket => "']'",
+            LexType::LeftRoundBracket => "'('",
+            LexType::RightRoundBracket => "')'",
+            LexType::LeftCurlyBracket => "'{'",
+            LexType::RightCurlyBracket => "'}'",
+            LexType::Comma => "','",
+            LexType::Sharp => "'#'",
+            LexType::Semicolon => "';'",
+            LexType::NotEqual => "'~='",
+            LexType::Dot => "'.'",
+            LexType::Dot2 => "'..'",
+            LexType::Dot3 => "'...'",
+            LexType::SkinnyArrow => "'->'",
+            LexType::DoubleColon => "'::'",
+            LexType::Add => "'+'",
+            LexType::Sub => "'-'",
+            LexType::Mul => "'*'",
+            LexType::Div => "'/'",
+            LexType::FloorDiv => "'//'",
+            LexType::Mod => "'%'",
+            LexType::Pow => "'^'",
+            LexType::Concat => "'..'",
+            LexType::AddAssign => "'+='",
+            LexType::SubAssign => "'-='",
+            LexType::MulAssign => "'*='",
+            LexType::DivAssign => "'/='",
+            LexType::FloorDivAssign => "'//='",
+            LexType::ModAssign => "'%='",
+            LexType::PowAssign => "'^='",
+            LexType::ConcatAssign => "'..='",
+            LexType::RawString(val) | LexType::QuotedString(val) => {
+                return write!(f, "\"{}\"", val)
             }
-            LexType::BlockComment => "BlockComment",
-            LexType::BrokenString => "BrokenString",
-            LexType::BrokenComment => "BrokenComment",
-            LexType::BrokenUnicode => "BrokenUnicode",
-            LexType::Error => "Error",
-            LexType::Begin => "Begin",
-            LexType::And => "And",
-            LexType::Break => "Break",
-            LexType::Do => "Do",
-            LexType::Else => "Else",
-            LexType::ElseIf => "ElseIf",
-            LexType::End => "End",
-            LexType::False => "False",
-            LexType::For => "For",
-            LexType::Function => "Function",
-            LexType::If => "If",
-            LexType::In => "In",
-            LexType::Local => "Local",
-            LexType::Nil => "Nil",
-            LexType::Not => "Not",
-            LexType::Or => "Or",
-            LexType::Repeat => "Repeat",
-            LexType::Return => "Return",
-            LexType::Then => "Then",
-            LexType::True => "True",
-            LexType::Until => "Until",
-            LexType::While => "While",
-            LexType::SingletonOr => "SingletonOr",
-            LexType::SingletonAnd => "SingletonAnd",
-            LexType::QuestionMark => "QuestionMark",
+            LexType::Number(val) => return write!(f, "'{}'", val),
+            LexType::Name(_) => "identifier",
+            LexType::Comment(_) | LexType::BlockComment => "comment",
+            LexType::BrokenString => "malformed string",
+            LexType::BrokenComment => "unfinished comment",
+            LexType::BrokenUnicode => "invalid UTF-8 sequence",
+            LexType::Error => "<unknown>",
+            LexType::And => "'and'",
+            LexType::Break => "'break'",
+            LexType::Do => "'do'",
+            LexType::Else => "'else'",
+            LexType::ElseIf => "'elseif'",
+            LexType::End => "'end'",
+            LexType::False => "'false'",
+            LexType::For => "'for'",
+            LexType::Function => "'function'",
+            LexType::If => "'if'",
+            LexType::In => "'in'",
+            LexType::Local => "'local'",
+            LexType::Nil => "'nil'",
+            LexType::Not => "'not'",
+            LexType::Or => "'or'",
+            LexType::Repeat => "'repeat'",
+            LexType::Return => "'return'",
+            LexType::Then => "'then'",
+            LexType::True => "'true'",
+            LexType::Until => "'until'",
+            LexType::While => "'while'",
+            LexType::SingletonOr => "'|'",
+            LexType::SingletonAnd => "'&'",
+            LexType::QuestionMark => "'?'",
+            LexType::InterpStringBegin(val) => return write!(f, "`{}{{", val),
+            LexType::InterpStringMid(val) => return write!(f, "}}{}{{", val),
+            LexType::InterpStringEnd(val) => return write!(f, "}}{}`", val),
+            LexType::InterpStringSimple(val) => return write!(f, "`{}`", val),
+            LexType::BrokenInterpDoubleBrace => "'{{', which is invalid (did you mean '\\{'?)",
         })
     }
 }
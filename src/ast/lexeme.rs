@@ -1,15 +1,50 @@
-use super::{LexLocation, LexType};
+use super::{LexLocation, LexType, Trivia};
 use std::fmt::Display;
 
 #[derive(Clone)]
 pub struct Lexeme {
     lex_type: LexType,
     location: LexLocation,
+
+    // source text and surrounding trivia are only kept by a lossless lexer
+    text: String,
+    leading_trivia: Vec<Trivia>,
+    trailing_trivia: Vec<Trivia>,
+
+    // description of a lexeme that failed to lex
+    error: Option<String>,
 }
 
 impl Lexeme {
     pub fn new(location: LexLocation, lex_type: LexType) -> Self {
-        Lexeme { lex_type, location }
+        Lexeme {
+            lex_type,
+            location,
+            text: String::new(),
+            leading_trivia: Vec::new(),
+            trailing_trivia: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = String::from(text);
+        self
+    }
+
+    pub fn with_trivia(
+        mut self,
+        leading_trivia: Vec<Trivia>,
+        trailing_trivia: Vec<Trivia>,
+    ) -> Self {
+        self.leading_trivia = leading_trivia;
+        self.trailing_trivia = trailing_trivia;
+        self
+    }
+
+    pub fn with_error(mut self, error: &str) -> Self {
+        self.error = Some(String::from(error));
+        self
     }
 
     pub const fn get_location(&self) -> LexLocation {
@@ -19,15 +54,27 @@ impl Lexeme {
     pub fn get_type(&self) -> LexType {
         self.lex_type.clone()
     }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_leading_trivia(&self) -> &[Trivia] {
+        &self.leading_trivia
+    }
+
+    // trivia after the lexeme up to and including the end of its line
+    pub fn get_trailing_trivia(&self) -> &[Trivia] {
+        &self.trailing_trivia
+    }
+
+    pub fn get_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
 }
 
 impl Display for Lexeme {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "<{}, location: {}>",
-            self.lex_type.to_string(),
-            self.location
-        )
+        write!(f, "<{}, location: {}>", self.lex_type, self.location)
     }
 }
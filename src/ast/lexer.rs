@@ -1,7 +1,38 @@
-use super::{LexLocation, LexPosition, LexType, Lexeme};
+use super::{LexLocation, LexPosition, LexType, Lexeme, NameTable, Trivia, TriviaKind};
 use std::str::Chars;
 
+// an invalid utf-8 sequence of the original bytes, replaced by U+FFFD when they were decoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidUtf8 {
+    // character offset of the replacement in the decoded text
+    offset: u32,
+    // length of the sequence in the original bytes
+    len: u32,
+}
+
+impl InvalidUtf8 {
+    pub fn new(offset: u32, len: u32) -> Self {
+        InvalidUtf8 { offset, len }
+    }
+
+    pub fn get_offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn get_len(&self) -> u32 {
+        self.len
+    }
+}
+
+// braces are tracked so that the '}' closing an interpolated string expression resumes the string
+#[derive(Clone, Copy, PartialEq)]
+enum BraceType {
+    InterpolatedString,
+    Normal,
+}
+
 pub struct Lexer<'src_lf> {
+    src: &'src_lf str,
     src_p: Chars<'src_lf>,
     current_char: Option<char>,
 
@@ -13,14 +44,77 @@ pub struct Lexer<'src_lf> {
     next_lexeme: Lexeme,
 
     previous_location: LexLocation,
+
+    brace_stack: Vec<BraceType>,
+
+    lossless: bool,
+    // invalid utf-8 sequences that were replaced by U+FFFD, the next one to be consumed and how
+    // many bytes the decoded text is longer than the original bytes before the current character
+    invalid_utf8: Vec<InvalidUtf8>,
+    next_invalid_utf8: usize,
+    decoded_growth: u32,
+    // a broken comment found while reading trailing trivia, it is the next lexeme
+    pending_lexeme: Option<Lexeme>,
+
+    // names read by the lexer are interned here, the parser interns the names it creates as well
+    names: NameTable,
 }
 
 impl<'src_lf> Lexer<'src_lf> {
     pub fn new(src: &'src_lf str) -> Self {
+        Lexer::create(src, false, Vec::new())
+    }
+
+    // every lexeme keeps its source text with the whitespace and comments around it,
+    // comments are trivia instead of lexemes
+    pub fn new_lossless(src: &'src_lf str) -> Self {
+        Lexer::create(src, true, Vec::new())
+    }
+
+    // lexes source produced by decode_lossy, the replaced sequences are reported as lexemes and
+    // byte offsets of positions index the original bytes
+    pub fn with_invalid_utf8(src: &'src_lf str, invalid_utf8: Vec<InvalidUtf8>) -> Self {
+        Lexer::create(src, false, invalid_utf8)
+    }
+
+    // replaces invalid utf-8 sequences by U+FFFD and returns where the replacements are
+    pub fn decode_lossy(src: &[u8]) -> (String, Vec<InvalidUtf8>) {
+        let mut text = String::new();
+        let mut invalid_utf8: Vec<InvalidUtf8> = Vec::new();
+        let mut offset = 0;
+
+        let mut rest = src;
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    text.push_str(valid);
+
+                    return (text, invalid_utf8);
+                }
+                Err(error) => {
+                    let (valid, invalid) = rest.split_at(error.valid_up_to());
+                    let valid = String::from_utf8_lossy(valid);
+
+                    text.push_str(&valid);
+                    offset += valid.chars().count() as u32;
+
+                    let len = error.error_len().unwrap_or(invalid.len());
+                    invalid_utf8.push(InvalidUtf8::new(offset, len as u32));
+                    text.push(char::REPLACEMENT_CHARACTER);
+                    offset += 1;
+
+                    rest = &invalid[len..];
+                }
+            }
+        }
+    }
+
+    fn create(src: &'src_lf str, lossless: bool, invalid_utf8: Vec<InvalidUtf8>) -> Self {
         let mut chars = src.chars();
         let current_char = chars.next();
 
         let mut result = Lexer {
+            src,
             src_p: chars,
             current_char,
             offset: 0,
@@ -29,24 +123,42 @@ impl<'src_lf> Lexer<'src_lf> {
             lexeme: Lexeme::new(LexLocation::zero(), LexType::Eof),
             next_lexeme: Lexeme::new(LexLocation::zero(), LexType::Eof),
             previous_location: LexLocation::zero(),
+            brace_stack: Vec::new(),
+            lossless,
+            invalid_utf8,
+            next_invalid_utf8: 0,
+            decoded_growth: 0,
+            pending_lexeme: None,
+            names: NameTable::new(),
         };
         result.next(true);
 
         result
     }
 
+    pub fn get_names(&self) -> &NameTable {
+        &self.names
+    }
+
+    pub fn get_names_mut(&mut self) -> &mut NameTable {
+        &mut self.names
+    }
+
+    pub fn into_names(self) -> NameTable {
+        self.names
+    }
+
     fn is_space(ch: char) -> bool {
-        match ch {
-            ' ' | '\t' | '\r' | '\n' => true,
-            _ => false,
-        }
+        matches!(ch, ' ' | '\t' | '\r' | '\n' | '\x0b' | '\x0c')
     }
 
+    // like luau only '\n' starts a line, the '\r' of '\r\n' is the last column of its line
+    // and a lone '\r' is whitespace
     fn is_new_line(ch: char) -> bool {
         ch.eq(&'\n')
     }
 
-    fn buf_to_string(buf: &Vec<char>) -> String {
+    fn buf_to_string(buf: &[char]) -> String {
         buf.iter().collect::<String>()
     }
 
@@ -57,29 +169,48 @@ impl<'src_lf> Lexer<'src_lf> {
                 self.line_offset = self.offset + 1;
             }
 
+            if let Some(invalid) = self.invalid_utf8.get(self.next_invalid_utf8) {
+                if invalid.offset == self.offset {
+                    self.decoded_growth += ch.len_utf8() as u32 - invalid.len;
+                    self.next_invalid_utf8 += 1;
+                }
+            }
+
             self.offset += 1;
             self.current_char = self.src_p.next();
         }
     }
 
+    // looks past the current character, peek_char(0) is the current character
+    fn peek_char(&self, count: usize) -> Option<char> {
+        if count == 0 {
+            self.current_char
+        } else {
+            self.src_p.clone().nth(count - 1)
+        }
+    }
+
+    // byte offset of the current character in the source
+    fn byte_offset(&self) -> usize {
+        self.src.len() - self.src_p.as_str().len() - self.current_char.map_or(0, char::len_utf8)
+    }
+
+    // the offset is the one in the original bytes when invalid utf-8 sequences were replaced
     fn position(&self) -> LexPosition {
         LexPosition::new(self.line, self.offset - self.line_offset)
+            .with_offset(self.byte_offset() as u32 - self.decoded_growth)
     }
 
     fn is_comment(lexeme: &Lexeme) -> bool {
-        match lexeme.get_type() {
-            LexType::Comment(_) | LexType::BlockComment => true,
-            _ => false,
-        }
+        matches!(
+            lexeme.get_type(),
+            LexType::Comment(_) | LexType::BlockComment
+        )
     }
 
     fn skip_space(&mut self) {
-        loop {
-            if let Some(ch) = self.current_char {
-                if !Lexer::is_space(ch) {
-                    break;
-                }
-            } else {
+        while let Some(ch) = self.current_char {
+            if !Lexer::is_space(ch) {
                 break;
             }
 
@@ -112,85 +243,228 @@ impl<'src_lf> Lexer<'src_lf> {
         }
     }
 
+    // sep is the level of the opening bracket, what names the construct in diagnostics
     fn read_long_string(
         &mut self,
         position: &LexPosition,
         sep: i32,
         wrap_fn: fn(&Vec<char>) -> LexType,
         broken: LexType,
+        what: &str,
     ) -> Lexeme {
         let mut buf: Vec<char> = Vec::new();
+        // first closing bracket of another level, a likely cause of the missing close
+        let mut mismatch: Option<(i32, LexPosition)> = None;
 
         loop {
-            if let Some(ch) = self.current_char {
-                self.consume();
+            match self.current_char {
+                Some(']') => {
+                    let bracket = self.position();
+                    self.consume();
 
-                if ch.eq(&']') {
-                    if self.skip_long_separator(ch).eq(&sep) {
-                        return Lexeme::new(
-                            LexLocation::new(*position, self.position()),
-                            wrap_fn(&buf),
-                        );
+                    let mut level = 0;
+                    while self.current_char.eq(&Some('=')) {
+                        level += 1;
+                        self.consume();
+                    }
+
+                    if self.current_char.eq(&Some(']')) {
+                        if level == sep {
+                            self.consume();
+
+                            return Lexeme::new(
+                                LexLocation::new(*position, self.position()),
+                                wrap_fn(&buf),
+                            );
+                        }
+
+                        mismatch = mismatch.or(Some((level, bracket)));
                     }
-                } else {
+
+                    // the second bracket may start the real closing bracket, it is read again
+                    buf.push(']');
+                    buf.extend((0..level).map(|_| '='));
+                }
+                Some(ch) => {
                     buf.push(ch);
+                    self.consume();
+                }
+                None => {
+                    let close = Lexer::long_bracket(']', sep);
+                    let message = match mismatch {
+                        Some((level, bracket)) => format!(
+                            "Unfinished long {}, expected '{}' (the '{}' at line {} has a different level)",
+                            what,
+                            close,
+                            Lexer::long_bracket(']', level),
+                            bracket.get_line() + 1
+                        ),
+                        None => format!("Unfinished long {}, expected '{}'", what, close),
+                    };
+
+                    return self.broken(position, broken, &message);
                 }
-            } else {
-                return Lexeme::new(LexLocation::new(*position, self.position()), broken);
             }
         }
     }
 
-    fn read_quoted_string(&mut self, delimiter: char) -> Lexeme {
-        let start = self.position();
+    fn long_bracket(bracket: char, level: i32) -> String {
+        let mut result = String::new();
+        result.push(bracket);
+        result.extend((0..level).map(|_| '='));
+        result.push(bracket);
+
+        result
+    }
+
+    // error lexemes span from their start to the current position
+    fn broken(&self, start: &LexPosition, lex_type: LexType, message: &str) -> Lexeme {
+        Lexeme::new(LexLocation::new(*start, self.position()), lex_type).with_error(message)
+    }
+
+    // keeps the escape sequence starting at the current backslash, it is decoded by the parser
+    // returns false when the source ends right after the backslash
+    fn read_backslash_in_string(&mut self, buf: &mut Vec<char>) -> bool {
+        buf.push('\\');
+        self.consume();
 
+        match self.current_char {
+            Some('\r') => {
+                buf.push('\r');
+                self.consume();
+
+                if self.current_char.eq(&Some('\n')) {
+                    buf.push('\n');
+                    self.consume();
+                }
+            }
+            Some('z') => {
+                buf.push('z');
+                self.consume();
+
+                while let Some(ch) = self.current_char {
+                    if !Lexer::is_space(ch) {
+                        break;
+                    }
+
+                    buf.push(ch);
+                    self.consume();
+                }
+            }
+            Some(ch) => {
+                buf.push(ch);
+                self.consume();
+            }
+            None => return false,
+        }
+
+        true
+    }
+
+    // the token keeps the raw string body, escape sequences are decoded by the parser
+    fn read_quoted_string(&mut self, delimiter: char, start: &LexPosition) -> Lexeme {
         let mut buf: Vec<char> = Vec::new();
+
         loop {
-            if let Some(ch) = self.current_char {
-                self.consume();
+            match self.current_char {
+                Some(ch) if ch.eq(&delimiter) => {
+                    self.consume();
 
-                if ch.eq(&delimiter) {
                     return Lexeme::new(
-                        LexLocation::new(start, self.position()),
+                        LexLocation::new(*start, self.position()),
                         LexType::QuotedString(Lexer::buf_to_string(&buf)),
                     );
-                } else {
-                    match ch {
-                        '\\' => {
-                            if let Some(nch) = self.current_char {
-                                self.consume();
-
-                                match nch {
-                                    '\r' | '\n' => {
-                                        buf.push(nch);
-                                    }
-                                    'r' => buf.push('\n'),
-                                    'n' => buf.push('\n'),
-                                    't' => buf.push('\t'),
-                                    '\\' => buf.push('\\'),
-                                    _ => {
-                                        return Lexeme::new(
-                                            LexLocation::new(start, self.position()),
-                                            LexType::BrokenString,
-                                        )
-                                    }
-                                }
-                            }
-                        }
-                        '\r' | '\n' => {
-                            return Lexeme::new(
-                                LexLocation::new(start, self.position()),
-                                LexType::BrokenString,
-                            )
+                }
+                Some('\\') => {
+                    if !self.read_backslash_in_string(&mut buf) {
+                        return self.broken(
+                            start,
+                            LexType::BrokenString,
+                            "Unfinished escape sequence at the end of the file",
+                        );
+                    }
+                }
+                Some('\r') | Some('\n') | None => {
+                    return self.broken(
+                        start,
+                        LexType::BrokenString,
+                        "Unfinished string, did you forget a closing quote?",
+                    )
+                }
+                Some(ch) => {
+                    buf.push(ch);
+                    self.consume();
+                }
+            }
+        }
+    }
+
+    // reads an interpolated string part up to the next expression or the closing backtick,
+    // start is the location of the backtick or the brace that ended the previous expression
+    fn read_interpolated_string_section(
+        &mut self,
+        start: &LexPosition,
+        format_type: fn(String) -> LexType,
+        end_type: fn(String) -> LexType,
+    ) -> Lexeme {
+        let mut buf: Vec<char> = Vec::new();
+
+        loop {
+            match self.current_char {
+                Some('`') => {
+                    self.consume();
+
+                    return Lexeme::new(
+                        LexLocation::new(*start, self.position()),
+                        end_type(Lexer::buf_to_string(&buf)),
+                    );
+                }
+                Some('\\') => {
+                    // the brace of \u{...} doesn't start an expression
+                    if self.peek_char(1).eq(&Some('u')) && self.peek_char(2).eq(&Some('{')) {
+                        for _ in 0..3 {
+                            buf.push(self.current_char.unwrap());
+                            self.consume();
                         }
-                        _ => buf.push(ch),
+                    } else if !self.read_backslash_in_string(&mut buf) {
+                        return self.broken(
+                            start,
+                            LexType::BrokenString,
+                            "Unfinished escape sequence at the end of the file",
+                        );
                     }
                 }
-            } else {
-                return Lexeme::new(
-                    LexLocation::new(start, self.position()),
-                    LexType::BrokenString,
-                );
+                Some('{') => {
+                    self.brace_stack.push(BraceType::InterpolatedString);
+
+                    if self.peek_char(1).eq(&Some('{')) {
+                        let location = LexLocation::new(*start, self.position());
+                        self.consume();
+                        self.consume();
+
+                        return Lexeme::new(location, LexType::BrokenInterpDoubleBrace).with_error(
+                            "Double braces are not permitted within interpolated strings",
+                        );
+                    }
+
+                    self.consume();
+
+                    return Lexeme::new(
+                        LexLocation::new(*start, self.position()),
+                        format_type(Lexer::buf_to_string(&buf)),
+                    );
+                }
+                Some('\r') | Some('\n') | None => {
+                    return self.broken(
+                        start,
+                        LexType::BrokenString,
+                        "Unfinished interpolated string, did you forget a closing '`'?",
+                    )
+                }
+                Some(ch) => {
+                    buf.push(ch);
+                    self.consume();
+                }
             }
         }
     }
@@ -198,18 +472,13 @@ impl<'src_lf> Lexer<'src_lf> {
     fn read_number(&mut self, ch: char, position: &LexPosition) -> Lexeme {
         let mut buf: Vec<char> = Vec::new();
         buf.push(ch);
-        loop {
-            if let Some(ch) = self.current_char {
-                buf.push(ch);
+        while let Some(ch) = self.current_char {
+            if !(ch.is_ascii_digit() || ch.eq(&'.') || ch.eq(&'_')) {
+                break;
             }
-            self.consume();
 
-            if let Some(ch) = self.current_char {
-                if ch.is_digit(10) || ch.eq(&'.') || ch.eq(&'_') {
-                    continue;
-                }
-            }
-            break;
+            buf.push(ch);
+            self.consume();
         }
 
         if let Some(ch) = self.current_char {
@@ -226,9 +495,10 @@ impl<'src_lf> Lexer<'src_lf> {
             }
         }
 
+        // the literal is validated when it is converted, see to_number
         while self.current_char.is_some()
-            && (self.current_char.unwrap().is_alphabetic()
-                || self.current_char.unwrap().is_digit(10)
+            && (self.current_char.unwrap().is_ascii_alphabetic()
+                || self.current_char.unwrap().is_ascii_digit()
                 || self.current_char.eq(&Some('_')))
         {
             buf.push(self.current_char.unwrap());
@@ -241,23 +511,30 @@ impl<'src_lf> Lexer<'src_lf> {
         )
     }
 
-    fn read_comment_body(&mut self) -> Lexeme {
-        let start = self.position();
+    // start is the location of the leading '--'
+    fn read_comment_body(&mut self, start: &LexPosition) -> Lexeme {
+        let start = *start;
 
+        let mut buf: Vec<char> = Vec::new();
         if self.current_char.eq(&Some('[')) {
+            self.consume();
             let sep = self.skip_long_separator('[');
 
             if sep.ge(&0) {
                 return self.read_long_string(
                     &start,
                     sep,
-                    |x: &Vec<char>| LexType::Comment(Lexer::buf_to_string(x)),
+                    |_: &Vec<char>| LexType::BlockComment,
                     LexType::BrokenComment,
+                    "comment",
                 );
             }
+
+            // not a long bracket, the consumed '[=*' is part of a line comment
+            buf.push('[');
+            buf.extend((0..(-sep - 1)).map(|_| '='));
         }
 
-        let mut buf: Vec<char> = Vec::new();
         while self.current_char.is_some()
             && self.current_char.ne(&Some('\r'))
             && !Lexer::is_new_line(self.current_char.unwrap())
@@ -275,52 +552,18 @@ impl<'src_lf> Lexer<'src_lf> {
     fn read_name(&mut self, ch: char, position: &LexPosition) -> Lexeme {
         let mut buf: Vec<char> = Vec::new();
         buf.push(ch);
-        loop {
-            if let Some(ch) = self.current_char {
-                buf.push(ch);
+        while let Some(ch) = self.current_char {
+            if !(ch.is_alphabetic() || ch.is_ascii_digit() || ch.eq(&'_')) {
+                break;
             }
 
+            buf.push(ch);
             self.consume();
-
-            if self.current_char.is_some()
-                && (self.current_char.unwrap().is_alphabetic()
-                    || self.current_char.unwrap().is_digit(10)
-                    || self.current_char.eq(&Some('_')))
-            {
-                continue;
-            }
-            break;
         }
 
-        let name = Lexer::buf_to_string(&buf);
-        Lexeme::new(
-            LexLocation::new(*position, self.position()),
-            match name.as_str() {
-                "begin" => LexType::Begin,
-                "and" => LexType::And,
-                "break" => LexType::Break,
-                "do" => LexType::Do,
-                "else" => LexType::Else,
-                "elseif" => LexType::ElseIf,
-                "end" => LexType::End,
-                "false" => LexType::False,
-                "for" => LexType::For,
-                "function" => LexType::Function,
-                "if" => LexType::If,
-                "in" => LexType::In,
-                "local" => LexType::Local,
-                "nil" => LexType::Nil,
-                "not" => LexType::Not,
-                "or" => LexType::Or,
-                "repeat" => LexType::Repeat,
-                "return" => LexType::Return,
-                "then" => LexType::Then,
-                "true" => LexType::True,
-                "until" => LexType::Until,
-                "while" => LexType::While,
-                _ => LexType::Name(name),
-            },
-        )
+        let (_, lex_type) = self.names.get_or_add(&Lexer::buf_to_string(&buf));
+
+        Lexeme::new(LexLocation::new(*position, self.position()), lex_type)
     }
 
     fn read_next(&mut self) -> Lexeme {
@@ -334,108 +577,130 @@ impl<'src_lf> Lexer<'src_lf> {
                     Some('>') => {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::SkinnyArrow)
+                        Lexeme::new(
+                            LexLocation::new(start, self.position()),
+                            LexType::SkinnyArrow,
+                        )
                     }
                     Some('=') => {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::SubAssign)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::SubAssign)
                     }
                     Some('-') => {
                         self.consume();
 
-                        self.read_comment_body()
+                        self.read_comment_body(&start)
                     }
-                    _ => Lexeme::new(LexLocation::line_offset(start, 1), LexType::Sub),
+                    _ => Lexeme::new(LexLocation::new(start, self.position()), LexType::Sub),
                 },
                 '+' => {
                     if self.current_char.eq(&Some('=')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::AddAssign)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::AddAssign)
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Add)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Add)
                     }
                 }
                 '*' => {
                     if self.current_char.eq(&Some('=')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::MulAssign)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::MulAssign)
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Mul)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Mul)
                     }
                 }
-                '/' => {
-                    if self.current_char.eq(&Some('=')) {
+                '/' => match self.current_char {
+                    Some('/') => {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::DivAssign)
-                    } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Div)
+                        if self.current_char.eq(&Some('=')) {
+                            self.consume();
+
+                            Lexeme::new(
+                                LexLocation::new(start, self.position()),
+                                LexType::FloorDivAssign,
+                            )
+                        } else {
+                            Lexeme::new(LexLocation::new(start, self.position()), LexType::FloorDiv)
+                        }
                     }
-                }
+                    Some('=') => {
+                        self.consume();
+
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::DivAssign)
+                    }
+                    _ => Lexeme::new(LexLocation::new(start, self.position()), LexType::Div),
+                },
                 '%' => {
                     if self.current_char.eq(&Some('=')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::ModAssign)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::ModAssign)
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Mod)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Mod)
                     }
                 }
                 '^' => {
                     if self.current_char.eq(&Some('=')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::PowAssign)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::PowAssign)
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Pow)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Pow)
                     }
                 }
                 '=' => {
                     if self.current_char.eq(&Some('=')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::Equal)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Equal)
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Assign)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Assign)
                     }
                 }
                 '<' => {
                     if self.current_char.eq(&Some('=')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::LessEqual)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::LessEqual)
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Less)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Less)
                     }
                 }
                 '>' => {
                     if self.current_char.eq(&Some('=')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::GreaterEqual)
+                        Lexeme::new(
+                            LexLocation::new(start, self.position()),
+                            LexType::GreaterEqual,
+                        )
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Greater)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Greater)
                     }
                 }
                 '~' => {
                     if self.current_char.eq(&Some('=')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::NotEqual)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::NotEqual)
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Not)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Not)
                     }
                 }
                 ':' => {
                     if self.current_char.eq(&Some(':')) {
                         self.consume();
 
-                        Lexeme::new(LexLocation::line_offset(start, 2), LexType::DoubleColon)
+                        Lexeme::new(
+                            LexLocation::new(start, self.position()),
+                            LexType::DoubleColon,
+                        )
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::Colon)
+                        Lexeme::new(LexLocation::new(start, self.position()), LexType::Colon)
                     }
                 }
                 '[' => {
@@ -446,17 +711,23 @@ impl<'src_lf> Lexer<'src_lf> {
                             sep,
                             |x: &Vec<char>| LexType::RawString(Lexer::buf_to_string(x)),
                             LexType::BrokenString,
+                            "string",
                         )
                     } else if sep.eq(&-1) {
                         Lexeme::new(
-                            LexLocation::line_offset(start, 1),
+                            LexLocation::new(start, self.position()),
                             LexType::LeftSquareBracket,
                         )
                     } else {
-                        Lexeme::new(LexLocation::line_offset(start, 1), LexType::BrokenString)
+                        let message = format!(
+                            "Invalid long string delimiter, expected '{}'",
+                            Lexer::long_bracket('[', -sep - 1)
+                        );
+
+                        self.broken(&start, LexType::BrokenString, &message)
                     }
                 }
-                '\'' | '\"' => self.read_quoted_string(ch),
+                '\'' | '\"' => self.read_quoted_string(ch, &start),
                 '.' => {
                     if self.current_char.eq(&Some('.')) {
                         self.consume();
@@ -464,59 +735,102 @@ impl<'src_lf> Lexer<'src_lf> {
                         if self.current_char.eq(&Some('.')) {
                             self.consume();
 
-                            Lexeme::new(LexLocation::line_offset(start, 3), LexType::Dot3)
+                            Lexeme::new(LexLocation::new(start, self.position()), LexType::Dot3)
                         } else if self.current_char.eq(&Some('=')) {
                             self.consume();
 
-                            Lexeme::new(LexLocation::line_offset(start, 3), LexType::ConcatAssign)
+                            Lexeme::new(
+                                LexLocation::new(start, self.position()),
+                                LexType::ConcatAssign,
+                            )
                         } else {
-                            Lexeme::new(LexLocation::line_offset(start, 2), LexType::Dot2)
+                            Lexeme::new(LexLocation::new(start, self.position()), LexType::Dot2)
                         }
                     } else {
                         if let Some(nch) = self.current_char {
-                            if nch.is_digit(10) {
+                            if nch.is_ascii_digit() {
                                 self.read_number(ch, &start)
                             } else {
-                                Lexeme::new(LexLocation::line_offset(start, 1), LexType::Dot)
+                                Lexeme::new(LexLocation::new(start, self.position()), LexType::Dot)
                             }
                         } else {
-                            Lexeme::new(LexLocation::line_offset(start, 1), LexType::Dot)
+                            Lexeme::new(LexLocation::new(start, self.position()), LexType::Dot)
                         }
                     }
                 }
                 '(' => Lexeme::new(
-                    LexLocation::line_offset(start, 1),
+                    LexLocation::new(start, self.position()),
                     LexType::LeftRoundBracket,
                 ),
                 ')' => Lexeme::new(
-                    LexLocation::line_offset(start, 1),
+                    LexLocation::new(start, self.position()),
                     LexType::RightRoundBracket,
                 ),
-                '{' => Lexeme::new(
-                    LexLocation::line_offset(start, 1),
-                    LexType::LeftCurlyBracket,
-                ),
-                '}' => Lexeme::new(
-                    LexLocation::line_offset(start, 1),
-                    LexType::RightCurlyBracket,
+                '{' => {
+                    self.brace_stack.push(BraceType::Normal);
+
+                    Lexeme::new(
+                        LexLocation::new(start, self.position()),
+                        LexType::LeftCurlyBracket,
+                    )
+                }
+                '}' => {
+                    if self.brace_stack.pop() == Some(BraceType::InterpolatedString) {
+                        self.read_interpolated_string_section(
+                            &start,
+                            LexType::InterpStringMid,
+                            LexType::InterpStringEnd,
+                        )
+                    } else {
+                        Lexeme::new(
+                            LexLocation::new(start, self.position()),
+                            LexType::RightCurlyBracket,
+                        )
+                    }
+                }
+                '`' => self.read_interpolated_string_section(
+                    &start,
+                    LexType::InterpStringBegin,
+                    LexType::InterpStringSimple,
                 ),
                 ']' => Lexeme::new(
-                    LexLocation::line_offset(start, 1),
+                    LexLocation::new(start, self.position()),
                     LexType::RightSquareBracket,
                 ),
-                ';' => Lexeme::new(LexLocation::line_offset(start, 1), LexType::Semicolon),
-                ',' => Lexeme::new(LexLocation::line_offset(start, 1), LexType::Comma),
-                '#' => Lexeme::new(LexLocation::line_offset(start, 1), LexType::Sharp),
-                '|' => Lexeme::new(LexLocation::line_offset(start, 1), LexType::SingletonOr),
-                '&' => Lexeme::new(LexLocation::line_offset(start, 1), LexType::SingletonAnd),
-                '?' => Lexeme::new(LexLocation::line_offset(start, 1), LexType::QuestionMark),
+                ';' => Lexeme::new(LexLocation::new(start, self.position()), LexType::Semicolon),
+                ',' => Lexeme::new(LexLocation::new(start, self.position()), LexType::Comma),
+                '#' => Lexeme::new(LexLocation::new(start, self.position()), LexType::Sharp),
+                '|' => Lexeme::new(
+                    LexLocation::new(start, self.position()),
+                    LexType::SingletonOr,
+                ),
+                '&' => Lexeme::new(
+                    LexLocation::new(start, self.position()),
+                    LexType::SingletonAnd,
+                ),
+                '?' => Lexeme::new(
+                    LexLocation::new(start, self.position()),
+                    LexType::QuestionMark,
+                ),
                 _ => {
-                    if ch.is_digit(10) {
+                    if ch.is_ascii_digit() {
                         self.read_number(ch, &start)
                     } else if ch.is_alphabetic() || ch.eq(&'_') {
                         self.read_name(ch, &start)
+                    } else if self
+                        .invalid_utf8
+                        .binary_search_by_key(&(self.offset - 1), InvalidUtf8::get_offset)
+                        .is_ok()
+                    {
+                        self.broken(&start, LexType::BrokenUnicode, "Invalid UTF-8 sequence")
+                    } else if !ch.is_ascii() {
+                        let message = format!("Unexpected Unicode character U+{:04X}", ch as u32);
+
+                        self.broken(&start, LexType::BrokenUnicode, &message)
                     } else {
-                        Lexeme::new(LexLocation::line_zero(start), LexType::Eof)
+                        let message = format!("Unexpected character '{}'", ch.escape_default());
+
+                        self.broken(&start, LexType::Error, &message)
                     }
                 }
             }
@@ -525,13 +839,165 @@ impl<'src_lf> Lexer<'src_lf> {
         }
     }
 
+    fn push_trivia(
+        &mut self,
+        trivia: &mut Vec<Trivia>,
+        kind: TriviaKind,
+        start: LexPosition,
+        begin: usize,
+    ) {
+        let location = LexLocation::new(start, self.position());
+        trivia.push(Trivia::new(
+            kind,
+            location,
+            &self.src[begin..self.byte_offset()],
+        ));
+    }
+
+    fn is_trivia_newline(&self) -> bool {
+        match self.current_char {
+            Some('\n') => true,
+            Some('\r') => self.peek_char(1).eq(&Some('\n')),
+            _ => false,
+        }
+    }
+
+    fn read_newline_trivia(&mut self, trivia: &mut Vec<Trivia>) {
+        let start = self.position();
+        let begin = self.byte_offset();
+
+        if self.current_char.eq(&Some('\r')) {
+            self.consume();
+        }
+        self.consume();
+
+        self.push_trivia(trivia, TriviaKind::Newline, start, begin);
+    }
+
+    fn read_whitespace_trivia(&mut self, trivia: &mut Vec<Trivia>) {
+        let start = self.position();
+        let begin = self.byte_offset();
+
+        while let Some(ch) = self.current_char {
+            if !Lexer::is_space(ch) || self.is_trivia_newline() {
+                break;
+            }
+
+            self.consume();
+        }
+
+        self.push_trivia(trivia, TriviaKind::Whitespace, start, begin);
+    }
+
+    // a broken comment is not trivia, it is returned so that it reaches the parser as a lexeme
+    fn read_comment_trivia(&mut self, trivia: &mut Vec<Trivia>) -> Option<Lexeme> {
+        let start = self.position();
+        let begin = self.byte_offset();
+
+        self.consume();
+        self.consume();
+
+        let comment = self.read_comment_body(&start);
+        let text = &self.src[begin..self.byte_offset()];
+
+        match comment.get_type() {
+            LexType::BrokenComment => Some(
+                self.broken(
+                    &start,
+                    LexType::BrokenComment,
+                    comment.get_error().unwrap_or_default(),
+                )
+                .with_text(text),
+            ),
+            LexType::BlockComment => {
+                self.push_trivia(trivia, TriviaKind::BlockComment, start, begin);
+
+                None
+            }
+            _ => {
+                self.push_trivia(trivia, TriviaKind::Comment, start, begin);
+
+                None
+            }
+        }
+    }
+
+    fn is_comment_start(&self) -> bool {
+        self.current_char.eq(&Some('-')) && self.peek_char(1).eq(&Some('-'))
+    }
+
+    fn read_leading_trivia(&mut self, trivia: &mut Vec<Trivia>) -> Option<Lexeme> {
+        loop {
+            if self.is_trivia_newline() {
+                self.read_newline_trivia(trivia);
+            } else if self.current_char.is_some_and(Lexer::is_space) {
+                self.read_whitespace_trivia(trivia);
+            } else if self.is_comment_start() {
+                if let Some(broken) = self.read_comment_trivia(trivia) {
+                    return Some(broken);
+                }
+            } else {
+                return None;
+            }
+        }
+    }
+
+    // trailing trivia ends with the first newline, everything after it leads the next lexeme
+    fn read_trailing_trivia(&mut self, trivia: &mut Vec<Trivia>) -> Option<Lexeme> {
+        loop {
+            if self.is_trivia_newline() {
+                self.read_newline_trivia(trivia);
+
+                return None;
+            } else if self.current_char.is_some_and(Lexer::is_space) {
+                self.read_whitespace_trivia(trivia);
+            } else if self.is_comment_start() {
+                if let Some(broken) = self.read_comment_trivia(trivia) {
+                    return Some(broken);
+                }
+            } else {
+                return None;
+            }
+        }
+    }
+
+    fn read_next_lossless(&mut self) -> Lexeme {
+        let mut leading_trivia: Vec<Trivia> = Vec::new();
+
+        let lexeme = if let Some(lexeme) = self.pending_lexeme.take() {
+            lexeme
+        } else if let Some(lexeme) = self.read_leading_trivia(&mut leading_trivia) {
+            lexeme
+        } else {
+            let begin = self.byte_offset();
+            let lexeme = self.read_next();
+
+            lexeme.with_text(&self.src[begin..self.byte_offset()])
+        };
+
+        let mut trailing_trivia: Vec<Trivia> = Vec::new();
+        if lexeme.get_type().ne(&LexType::BrokenComment) {
+            self.pending_lexeme = self.read_trailing_trivia(&mut trailing_trivia);
+        }
+
+        lexeme.with_trivia(leading_trivia, trailing_trivia)
+    }
+
     pub fn next(&mut self, skip_comments: bool) -> Lexeme {
         loop {
-            self.skip_space();
-            self.previous_location = self.lexeme.get_location();
+            if !self.lossless {
+                self.skip_space();
+            }
+            if !Lexer::is_comment(&self.lexeme) {
+                self.previous_location = self.lexeme.get_location();
+            }
 
             self.lexeme = self.next_lexeme.clone();
-            self.next_lexeme = self.read_next();
+            self.next_lexeme = if self.lossless {
+                self.read_next_lossless()
+            } else {
+                self.read_next()
+            };
 
             if skip_comments && Lexer::is_comment(&self.lexeme) {
                 continue;
@@ -569,6 +1035,7 @@ impl<'src_lf> Lexer<'src_lf> {
 
 #[cfg(test)]
 mod tests {
+    use super::super::{ColumnEncoding, LineIndex};
     use super::*;
 
     fn assert_single(src: &str, type_: LexType) {
@@ -581,7 +1048,6 @@ mod tests {
     #[test]
     fn test_lexeme_single() {
         let assert_list = vec![
-            ("begin", LexType::Begin),
             ("and", LexType::And),
             ("break", LexType::Break),
             ("do", LexType::Do),
@@ -603,13 +1069,12 @@ mod tests {
             ("true", LexType::True),
             ("until", LexType::Until),
             ("while", LexType::While),
-            ("_var1", LexType::Name(String::from("_var1"))),
-            (" _var1", LexType::Name(String::from("_var1"))),
-            ("_var1 ", LexType::Name(String::from("_var1"))),
-            ("_var1 _var2", LexType::Name(String::from("_var1"))),
             ("123", LexType::Number(String::from("123"))),
             ("123.456", LexType::Number(String::from("123.456"))),
             (".456", LexType::Number(String::from(".456"))),
+            ("1..2", LexType::Number(String::from("1..2"))),
+            ("1e-5", LexType::Number(String::from("1e-5"))),
+            ("0x1p", LexType::Number(String::from("0x1p"))),
             ("-", LexType::Sub),
             ("-a", LexType::Sub),
             ("-1", LexType::Sub),
@@ -619,6 +1084,9 @@ mod tests {
                 "--comment body",
                 LexType::Comment(String::from("comment body")),
             ),
+            ("--[==[comment]]\n]==]", LexType::BlockComment),
+            ("--[[!strict]]", LexType::BlockComment),
+            ("--[=comment", LexType::Comment(String::from("[=comment"))),
             ("+", LexType::Add),
             ("+a", LexType::Add),
             ("+a", LexType::Add),
@@ -628,6 +1096,8 @@ mod tests {
             ("*=", LexType::MulAssign),
             ("/", LexType::Div),
             ("/=", LexType::DivAssign),
+            ("//", LexType::FloorDiv),
+            ("//=", LexType::FloorDivAssign),
             ("%", LexType::Mod),
             ("%=", LexType::ModAssign),
             ("^", LexType::Pow),
@@ -653,8 +1123,14 @@ mod tests {
             ("\"foobar\"", LexType::QuotedString(String::from("foobar"))),
             (
                 "\"foo\\\nbar\"",
-                LexType::QuotedString(String::from("foo\nbar")),
+                LexType::QuotedString(String::from("foo\\\nbar")),
+            ),
+            (
+                "'\\x41\\'\\z\n  '",
+                LexType::QuotedString(String::from("\\x41\\'\\z\n  ")),
             ),
+            ("'foo\nbar'", LexType::BrokenString),
+            ("'foo\\", LexType::BrokenString),
             (".a", LexType::Dot),
             (".", LexType::Dot),
             ("..", LexType::Dot2),
@@ -673,6 +1149,19 @@ mod tests {
             let (keyword, lex_type) = item;
             assert_single(keyword, lex_type);
         }
+
+        let name_list = vec![
+            ("_var1", "_var1"),
+            (" _var1", "_var1"),
+            ("_var1 ", "_var1"),
+            ("_var1 _var2", "_var1"),
+            ("begin", "begin"),
+        ];
+
+        for (src, name) in name_list {
+            let (result, names) = lex_names(src);
+            assert_eq!(result[0], name_type(&names, name));
+        }
     }
 
     #[test]
@@ -686,37 +1175,544 @@ mod tests {
         hello_world('foo bar')
 
         ";
+        let (actual_vec, names) = lex_names(src);
+
+        let name = |name: &str| name_type(&names, name);
         let expect_vec = vec![
             LexType::Function,
-            LexType::Name(String::from("hello_world")),
+            name("hello_world"),
             LexType::LeftRoundBracket,
-            LexType::Name(String::from("statement")),
+            name("statement"),
             LexType::RightRoundBracket,
-            LexType::Name(String::from("print")),
+            name("print"),
             LexType::LeftRoundBracket,
-            LexType::Name(String::from("statement")),
+            name("statement"),
             LexType::RightRoundBracket,
             LexType::End,
             LexType::Comment(String::from(" print \"foo bar\"")),
-            LexType::Name(String::from("hello_world")),
+            name("hello_world"),
             LexType::LeftRoundBracket,
             LexType::QuotedString(String::from("foo bar")),
             LexType::RightRoundBracket,
         ];
 
+        assert_eq!(expect_vec, actual_vec);
+    }
+
+    fn lex_names(src: &str) -> (Vec<LexType>, NameTable) {
         let mut lexer = Lexer::new(src);
 
-        let mut actual_vec: Vec<LexType> = Vec::new();
+        let mut result: Vec<LexType> = Vec::new();
         loop {
             let lexeme = lexer.next(false);
 
             if lexeme.get_type().eq(&LexType::Eof) {
                 break;
-            } else {
-                actual_vec.push(lexeme.get_type());
             }
+            result.push(lexeme.get_type());
         }
 
-        assert_eq!(expect_vec, actual_vec);
+        (result, lexer.into_names())
+    }
+
+    fn lex_all(src: &str) -> Vec<LexType> {
+        lex_names(src).0
+    }
+
+    // the lexeme type of a name read into the table
+    fn name_type(names: &NameTable, name: &str) -> LexType {
+        names.get(name).unwrap().1
+    }
+
+    #[test]
+    fn test_interpolated_string() {
+        assert_eq!(
+            lex_all("`hello`"),
+            vec![LexType::InterpStringSimple(String::from("hello"))]
+        );
+
+        let (result, names) = lex_names("`a{b}c{ {d} }e`");
+        assert_eq!(
+            result,
+            vec![
+                LexType::InterpStringBegin(String::from("a")),
+                name_type(&names, "b"),
+                LexType::InterpStringMid(String::from("c")),
+                LexType::LeftCurlyBracket,
+                name_type(&names, "d"),
+                LexType::RightCurlyBracket,
+                LexType::InterpStringEnd(String::from("e")),
+            ]
+        );
+
+        // nested strings and escaped braces
+        assert_eq!(
+            lex_all("`{`x{1}`}\\{\\u{41}`"),
+            vec![
+                LexType::InterpStringBegin(String::new()),
+                LexType::InterpStringBegin(String::from("x")),
+                LexType::Number(String::from("1")),
+                LexType::InterpStringEnd(String::new()),
+                LexType::InterpStringEnd(String::from("\\{\\u{41}")),
+            ]
+        );
+
+        let (result, names) = lex_names("`a{{b}}`");
+        assert_eq!(
+            result,
+            vec![
+                LexType::BrokenInterpDoubleBrace,
+                name_type(&names, "b"),
+                LexType::InterpStringEnd(String::from("}")),
+            ]
+        );
+
+        assert_eq!(lex_all("`abc\n"), vec![LexType::BrokenString]);
+    }
+
+    fn lex_lossless(src: &str) -> Vec<Lexeme> {
+        let mut lexer = Lexer::new_lossless(src);
+
+        let mut result: Vec<Lexeme> = Vec::new();
+        loop {
+            let lexeme = lexer.next(false);
+            let eof = lexeme.get_type().eq(&LexType::Eof);
+
+            result.push(lexeme);
+            if eof {
+                break;
+            }
+        }
+
+        result
+    }
+
+    fn trivia_texts(trivia: &[Trivia]) -> Vec<(TriviaKind, &str)> {
+        trivia
+            .iter()
+            .map(|trivia| (trivia.get_kind(), trivia.get_text()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let src = "--!strict\r\n\tlocal a = 'x\\z\n  y' --[==[ block\n]==] + 1\n\n\
+                   local b = `{a}\u{e9}` // 2 -- tail\r\n--[ not a block\n  return --[[]]a\n  ";
+
+        let text: String = lex_lossless(src)
+            .iter()
+            .map(|lexeme| {
+                let mut text = String::new();
+                for trivia in lexeme.get_leading_trivia() {
+                    text.push_str(trivia.get_text());
+                }
+                text.push_str(lexeme.get_text());
+                for trivia in lexeme.get_trailing_trivia() {
+                    text.push_str(trivia.get_text());
+                }
+
+                text
+            })
+            .collect();
+
+        assert_eq!(text, src);
+    }
+
+    #[test]
+    fn test_lossless_trivia() {
+        let lexemes = lex_lossless("local a = 1 -- c\n\n  --[[ b ]] print(a)\n");
+
+        let one = &lexemes[3];
+        assert_eq!(one.get_text(), "1");
+        assert!(one.get_leading_trivia().is_empty());
+        assert_eq!(
+            trivia_texts(one.get_trailing_trivia()),
+            vec![
+                (TriviaKind::Whitespace, " "),
+                (TriviaKind::Comment, "-- c"),
+                (TriviaKind::Newline, "\n"),
+            ]
+        );
+
+        let print = &lexemes[4];
+        assert_eq!(print.get_text(), "print");
+        assert_eq!(
+            trivia_texts(print.get_leading_trivia()),
+            vec![
+                (TriviaKind::Newline, "\n"),
+                (TriviaKind::Whitespace, "  "),
+                (TriviaKind::BlockComment, "--[[ b ]]"),
+                (TriviaKind::Whitespace, " "),
+            ]
+        );
+        assert_eq!(
+            print.get_leading_trivia()[2].get_location(),
+            LexLocation::new(LexPosition::new(2, 2), LexPosition::new(2, 11))
+        );
+
+        let eof = lexemes.last().unwrap();
+        assert!(eof.get_leading_trivia().is_empty());
+        assert_eq!(
+            trivia_texts(lexemes[lexemes.len() - 2].get_trailing_trivia()),
+            vec![(TriviaKind::Newline, "\n")]
+        );
+
+        // unterminated block comments still reach the parser
+        let lexemes = lex_lossless("a --[[ b");
+        assert_eq!(
+            trivia_texts(lexemes[0].get_trailing_trivia()),
+            vec![(TriviaKind::Whitespace, " ")]
+        );
+        assert_eq!(lexemes[1].get_type(), LexType::BrokenComment);
+        assert_eq!(lexemes[1].get_text(), "--[[ b");
+        assert_eq!(lexemes[2].get_type(), LexType::Eof);
+    }
+
+    fn lex_errors(src: &str) -> Vec<(LexType, Option<String>)> {
+        let mut lexer = Lexer::new(src);
+
+        let mut result: Vec<(LexType, Option<String>)> = Vec::new();
+        loop {
+            let lexeme = lexer.next(false);
+            if lexeme.get_type().eq(&LexType::Eof) {
+                break;
+            }
+
+            result.push((lexeme.get_type(), lexeme.get_error().map(String::from)));
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_error_lexemes() {
+        let names = lex_names("a $ \\ b").1;
+        assert_eq!(
+            lex_errors("a $ \\ b"),
+            vec![
+                (name_type(&names, "a"), None),
+                (
+                    LexType::Error,
+                    Some(String::from("Unexpected character '$'"))
+                ),
+                (
+                    LexType::Error,
+                    Some(String::from("Unexpected character '\\\\'"))
+                ),
+                (name_type(&names, "b"), None),
+            ]
+        );
+        assert_eq!(
+            lex_errors("\u{a0}\u{1}"),
+            vec![
+                (
+                    LexType::BrokenUnicode,
+                    Some(String::from("Unexpected Unicode character U+00A0"))
+                ),
+                (
+                    LexType::Error,
+                    Some(String::from("Unexpected character '\\u{1}'"))
+                ),
+            ]
+        );
+
+        let single = |src: &str| lex_errors(src).remove(0);
+        assert_eq!(
+            single("'abc\\"),
+            (
+                LexType::BrokenString,
+                Some(String::from(
+                    "Unfinished escape sequence at the end of the file"
+                ))
+            )
+        );
+        assert_eq!(
+            single("'abc\n'"),
+            (
+                LexType::BrokenString,
+                Some(String::from(
+                    "Unfinished string, did you forget a closing quote?"
+                ))
+            )
+        );
+        assert_eq!(
+            single("[=x"),
+            (
+                LexType::BrokenString,
+                Some(String::from(
+                    "Invalid long string delimiter, expected '[=['"
+                ))
+            )
+        );
+        assert_eq!(
+            single("[==[ x\n]=] y"),
+            (
+                LexType::BrokenString,
+                Some(String::from(
+                    "Unfinished long string, expected ']==]' (the ']=]' at line 2 has a different level)"
+                ))
+            )
+        );
+        assert_eq!(
+            single("--[[ x"),
+            (
+                LexType::BrokenComment,
+                Some(String::from("Unfinished long comment, expected ']]'"))
+            )
+        );
+
+        // closing brackets of another level are part of the string
+        assert_eq!(
+            single("[=[a]]=]"),
+            (LexType::RawString(String::from("a]")), None)
+        );
+        assert_eq!(
+            lex_errors("[[a]]]"),
+            vec![
+                (LexType::RawString(String::from("a")), None),
+                (LexType::RightSquareBracket, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let (text, invalid_utf8) = Lexer::decode_lossy(b"a\xffb\xe2\x82c\xe2\x82\xac");
+        assert_eq!(text, "a\u{fffd}b\u{fffd}c\u{20ac}");
+        assert_eq!(
+            invalid_utf8,
+            vec![InvalidUtf8::new(1, 1), InvalidUtf8::new(3, 2)]
+        );
+
+        let mut lexer = Lexer::with_invalid_utf8(&text, invalid_utf8);
+        let lexeme = lexer.next(false);
+        assert_eq!(lexeme.get_type(), name_type(lexer.get_names(), "a"));
+
+        let lexeme = lexer.next(false);
+        assert_eq!(lexeme.get_type(), LexType::BrokenUnicode);
+        assert_eq!(lexeme.get_error(), Some("Invalid UTF-8 sequence"));
+        assert_eq!(
+            lexeme.get_location(),
+            LexLocation::new(LexPosition::new(0, 1), LexPosition::new(0, 2))
+        );
+
+        // offsets index the original bytes, the line index of the bytes agrees with the lexer
+        let src = b"local s = '\xe2\x82' .. \xff\nx\xfe\xfe = '\xc3\xbc'";
+        let (text, invalid_utf8) = Lexer::decode_lossy(src);
+        let index = LineIndex::from_bytes(src);
+        let mut lexer = Lexer::with_invalid_utf8(&text, invalid_utf8);
+
+        let mut texts: Vec<&[u8]> = Vec::new();
+        loop {
+            let lexeme = lexer.next(false);
+            let location = lexeme.get_location();
+            for position in [location.get_begin(), location.get_end()] {
+                let converted = index.position(position.get_offset()).unwrap();
+                assert_eq!(converted, position);
+            }
+
+            if lexeme.get_type().eq(&LexType::Eof) {
+                break;
+            }
+            texts.push(&src[location.get_byte_range()]);
+        }
+        assert_eq!(
+            texts,
+            vec![
+                &b"local"[..],
+                b"s",
+                b"=",
+                b"'\xe2\x82'",
+                b"..",
+                b"\xff",
+                b"x",
+                b"\xfe",
+                b"\xfe",
+                b"=",
+                "'ü'".as_bytes(),
+            ]
+        );
+        assert_eq!(index.line_column(24, ColumnEncoding::Char), Some((1, 4)));
+        assert_eq!(index.line_column(29, ColumnEncoding::Char), Some((1, 8)));
+        assert_eq!(index.line_column(29, ColumnEncoding::Utf8), Some((1, 9)));
+        assert_eq!(index.offset(1, 4, ColumnEncoding::Utf16), Some(24));
+    }
+
+    #[test]
+    fn test_byte_offsets() {
+        let src = "local s = [[é\n😀]] .. 'ü'";
+        let mut lexer = Lexer::new(src);
+
+        let mut texts: Vec<&str> = Vec::new();
+        loop {
+            let lexeme = lexer.next(false);
+            if lexeme.get_type().eq(&LexType::Eof) {
+                break;
+            }
+
+            texts.push(&src[lexeme.get_location().get_byte_range()]);
+        }
+        assert_eq!(texts, vec!["local", "s", "=", "[[é\n😀]]", "..", "'ü'"]);
+
+        // tokens spanning lines end on their last line
+        let mut lexer = Lexer::new(src);
+        lexer.next(false);
+        lexer.next(false);
+        lexer.next(false);
+        let location = lexer.next(false).get_location();
+        assert_eq!(location.get_begin(), LexPosition::new(0, 10));
+        assert_eq!(location.get_end(), LexPosition::new(1, 3));
+        assert_eq!(location.get_end().get_offset(), 21);
+
+        let lexeme = lexer.next(false);
+        assert_eq!(
+            lexeme.get_location(),
+            LexLocation::new(LexPosition::new(1, 4), LexPosition::new(1, 6))
+        );
+    }
+
+    #[test]
+    fn test_line_index() {
+        let src = "aé😀b\n\nx = 1";
+        let index = LineIndex::new(src);
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line_start(1), Some(9));
+        assert_eq!(index.line_end(0), Some(8));
+        assert_eq!(index.line_end(2), Some(src.len() as u32));
+        assert_eq!(index.line_start(3), None);
+
+        // the b after the emoji
+        assert_eq!(index.line_column(7, ColumnEncoding::Utf8), Some((0, 7)));
+        assert_eq!(index.line_column(7, ColumnEncoding::Utf16), Some((0, 4)));
+        assert_eq!(index.line_column(7, ColumnEncoding::Char), Some((0, 3)));
+        assert_eq!(index.line_column(10, ColumnEncoding::Utf16), Some((2, 0)));
+        assert_eq!(index.line_column(2, ColumnEncoding::Char), None);
+        assert_eq!(index.line_column(100, ColumnEncoding::Char), None);
+
+        assert_eq!(index.offset(0, 4, ColumnEncoding::Utf16), Some(7));
+        assert_eq!(index.offset(0, 3, ColumnEncoding::Char), Some(7));
+        assert_eq!(index.offset(0, 4, ColumnEncoding::Char), Some(8));
+        assert_eq!(index.offset(0, 5, ColumnEncoding::Char), None);
+        assert_eq!(index.offset(0, 3, ColumnEncoding::Utf16), None);
+        assert_eq!(index.offset(0, 4, ColumnEncoding::Utf8), None);
+
+        assert_eq!(
+            index.convert_column(0, 4, ColumnEncoding::Utf16, ColumnEncoding::Char),
+            Some(3)
+        );
+        assert_eq!(
+            index.convert_column(0, 2, ColumnEncoding::Char, ColumnEncoding::Utf16),
+            Some(2)
+        );
+
+        // positions of the line index match the ones of the lexer
+        let mut lexer = Lexer::new(src);
+        loop {
+            let lexeme = lexer.next(false);
+            let location = lexeme.get_location();
+            for position in [location.get_begin(), location.get_end()] {
+                let converted = index.position(position.get_offset()).unwrap();
+                assert_eq!(converted, position);
+                assert_eq!(converted.get_offset(), position.get_offset());
+            }
+
+            if lexeme.get_type().eq(&LexType::Eof) {
+                break;
+            }
+        }
+    }
+
+    // expected locations are the ones the luau lexer reports for the same source
+    #[test]
+    fn test_line_terminators() {
+        let src =
+            "local a = 1\r\nlocal\x0cb\x0b= 2\rc = [[\r\nx\r\ny]]\r\nd = 'p\\\r\nq' -- c\r\n\x0c";
+        let mut lexer = Lexer::new(src);
+
+        let mut result: Vec<(LexType, LexLocation)> = Vec::new();
+        loop {
+            let lexeme = lexer.next(false);
+            result.push((lexeme.get_type(), lexeme.get_location()));
+
+            if lexeme.get_type().eq(&LexType::Eof) {
+                break;
+            }
+        }
+
+        let name = |name: &str| name_type(lexer.get_names(), name);
+        let number = |number: &str| LexType::Number(String::from(number));
+        let at = |begin: (u32, u32), end: (u32, u32)| {
+            LexLocation::new(
+                LexPosition::new(begin.0, begin.1),
+                LexPosition::new(end.0, end.1),
+            )
+        };
+        assert_eq!(
+            result,
+            vec![
+                (LexType::Local, at((0, 0), (0, 5))),
+                (name("a"), at((0, 6), (0, 7))),
+                (LexType::Assign, at((0, 8), (0, 9))),
+                (number("1"), at((0, 10), (0, 11))),
+                (LexType::Local, at((1, 0), (1, 5))),
+                (name("b"), at((1, 6), (1, 7))),
+                (LexType::Assign, at((1, 8), (1, 9))),
+                (number("2"), at((1, 10), (1, 11))),
+                (name("c"), at((1, 12), (1, 13))),
+                (LexType::Assign, at((1, 14), (1, 15))),
+                (
+                    LexType::RawString(String::from("\r\nx\r\ny")),
+                    at((1, 16), (3, 3))
+                ),
+                (name("d"), at((4, 0), (4, 1))),
+                (LexType::Assign, at((4, 2), (4, 3))),
+                (
+                    LexType::QuotedString(String::from("p\\\r\nq")),
+                    at((4, 4), (5, 2))
+                ),
+                (LexType::Comment(String::from(" c")), at((5, 3), (5, 7))),
+                (LexType::Eof, at((6, 1), (6, 1))),
+            ]
+        );
+
+        // the line index agrees with the lexer
+        let index = LineIndex::new(src);
+        assert_eq!(index.line_count(), 7);
+        assert_eq!(
+            index.line_column(src.find("\rc").unwrap() as u32 + 1, ColumnEncoding::Char),
+            Some((1, 12))
+        );
+    }
+
+    #[test]
+    fn test_name_table() {
+        let mut names = NameTable::new();
+
+        // reserved words are known before any source is read
+        let (local, lex_type) = names.get("local").unwrap();
+        assert_eq!(lex_type, LexType::Local);
+        assert_eq!(names.get_value(local), "local");
+        assert!(names.get("print").is_none());
+
+        let (print, lex_type) = names.get_or_add("print");
+        assert_eq!(lex_type, LexType::Name(print));
+        assert_eq!(names.get_or_add("print").0, print);
+
+        // these have the same 32-bit fnv-1a hash
+        let (costarring, _) = names.get_or_add("costarring");
+        let (liquid, _) = names.get_or_add("liquid");
+        assert_ne!(costarring, liquid);
+        assert_eq!(names.get_value(costarring), "costarring");
+        assert_eq!(names.get_value(liquid), "liquid");
+
+        // names read by the lexer are interned in its table
+        let mut lexer = Lexer::new("foo while foo");
+        let first = lexer.next(false).get_type();
+        assert_eq!(lexer.next(false).get_type(), LexType::While);
+        assert_eq!(lexer.next(false).get_type(), first);
+        let (foo, _) = lexer.get_names().get("foo").unwrap();
+        assert_eq!(first, LexType::Name(foo));
+        assert_eq!(lexer.get_names().get_value(foo), "foo");
     }
 }
@@ -0,0 +1,196 @@
+use super::LexPosition;
+
+// the unit columns are counted in, lsp clients count utf-16 code units
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnEncoding {
+    Utf8,
+    Utf16,
+    Char,
+}
+
+// a character taking more than one byte, columns after it differ between encodings
+#[derive(Clone, Copy, Debug)]
+struct WideChar {
+    // byte offset from the start of the line
+    offset: u32,
+    len_utf8: u32,
+    len_utf16: u32,
+}
+
+impl WideChar {
+    fn width(&self, encoding: ColumnEncoding) -> u32 {
+        match encoding {
+            ColumnEncoding::Utf8 => self.len_utf8,
+            ColumnEncoding::Utf16 => self.len_utf16,
+            ColumnEncoding::Char => 1,
+        }
+    }
+}
+
+// converts between byte offsets and line/column pairs of a source, lines and columns are zero
+// based and like in the lexer only '\n' breaks lines
+#[derive(Clone, Debug)]
+pub struct LineIndex {
+    line_starts: Vec<u32>,
+    wide_chars: Vec<Vec<WideChar>>,
+    len: u32,
+}
+
+impl LineIndex {
+    pub fn new(src: &str) -> Self {
+        let chars = src
+            .char_indices()
+            .map(|(offset, ch)| (offset as u32, ch, ch.len_utf8() as u32));
+
+        LineIndex::from_chars(chars, src.len() as u32)
+    }
+
+    // for the original bytes of a source that was decoded with Lexer::decode_lossy, like in the
+    // lexer an invalid utf-8 sequence is a single character
+    pub fn from_bytes(src: &[u8]) -> Self {
+        let mut chars: Vec<(u32, char, u32)> = Vec::new();
+        let mut offset = 0;
+
+        for chunk in src.utf8_chunks() {
+            for (index, ch) in chunk.valid().char_indices() {
+                chars.push(((offset + index) as u32, ch, ch.len_utf8() as u32));
+            }
+            offset += chunk.valid().len();
+
+            if !chunk.invalid().is_empty() {
+                let len = chunk.invalid().len();
+                chars.push((offset as u32, char::REPLACEMENT_CHARACTER, len as u32));
+                offset += len;
+            }
+        }
+
+        LineIndex::from_chars(chars.into_iter(), src.len() as u32)
+    }
+
+    // the characters with their byte offset and their length in bytes
+    fn from_chars(chars: impl Iterator<Item = (u32, char, u32)>, len: u32) -> Self {
+        let mut line_starts: Vec<u32> = vec![0];
+        let mut wide_chars: Vec<Vec<WideChar>> = vec![Vec::new()];
+
+        for (offset, ch, len_utf8) in chars {
+            if ch.eq(&'\n') {
+                line_starts.push(offset + 1);
+                wide_chars.push(Vec::new());
+            } else if !ch.is_ascii() {
+                let line_start = line_starts[line_starts.len() - 1];
+
+                if let Some(line) = wide_chars.last_mut() {
+                    line.push(WideChar {
+                        offset: offset - line_start,
+                        len_utf8,
+                        len_utf16: ch.len_utf16() as u32,
+                    });
+                }
+            }
+        }
+
+        LineIndex {
+            line_starts,
+            wide_chars,
+            len,
+        }
+    }
+
+    pub fn line_count(&self) -> u32 {
+        self.line_starts.len() as u32
+    }
+
+    pub fn line_start(&self, line: u32) -> Option<u32> {
+        self.line_starts.get(line as usize).copied()
+    }
+
+    // byte offset of the end of the line, before its line break
+    pub fn line_end(&self, line: u32) -> Option<u32> {
+        self.line_start(line)?;
+
+        Some(match self.line_start(line + 1) {
+            Some(next) => next - 1,
+            None => self.len,
+        })
+    }
+
+    // None when the offset is past the end of the source or inside a character
+    pub fn line_column(&self, offset: u32, encoding: ColumnEncoding) -> Option<(u32, u32)> {
+        if offset > self.len {
+            return None;
+        }
+
+        let line = self.line_starts.partition_point(|start| start.le(&offset)) - 1;
+        let column = self.column_of(line, offset - self.line_starts[line], encoding)?;
+
+        Some((line as u32, column))
+    }
+
+    // None when the line doesn't exist or the column is past its end or inside a character
+    pub fn offset(&self, line: u32, column: u32, encoding: ColumnEncoding) -> Option<u32> {
+        let start = self.line_start(line)?;
+        let utf8_column = self.utf8_column_of(line as usize, column, encoding)?;
+
+        if start + utf8_column > self.line_end(line)? {
+            return None;
+        }
+
+        Some(start + utf8_column)
+    }
+
+    // the position the lexer gives to the character at the offset
+    pub fn position(&self, offset: u32) -> Option<LexPosition> {
+        let (line, column) = self.line_column(offset, ColumnEncoding::Char)?;
+
+        Some(LexPosition::new(line, column).with_offset(offset))
+    }
+
+    pub fn convert_column(
+        &self,
+        line: u32,
+        column: u32,
+        from: ColumnEncoding,
+        to: ColumnEncoding,
+    ) -> Option<u32> {
+        let offset = self.offset(line, column, from)?;
+
+        self.column_of(line as usize, offset - self.line_starts[line as usize], to)
+    }
+
+    fn column_of(&self, line: usize, utf8_column: u32, encoding: ColumnEncoding) -> Option<u32> {
+        let mut column = utf8_column;
+
+        for wide in &self.wide_chars[line] {
+            if wide.offset >= utf8_column {
+                break;
+            }
+
+            if utf8_column < wide.offset + wide.len_utf8 {
+                return None;
+            }
+
+            column -= wide.len_utf8 - wide.width(encoding);
+        }
+
+        Some(column)
+    }
+
+    fn utf8_column_of(&self, line: usize, column: u32, encoding: ColumnEncoding) -> Option<u32> {
+        let mut utf8_column = column;
+
+        for wide in &self.wide_chars[line] {
+            if wide.offset >= utf8_column {
+                break;
+            }
+
+            // the column points inside the character, e.g. between the halves of a surrogate pair
+            if utf8_column < wide.offset + wide.width(encoding) {
+                return None;
+            }
+
+            utf8_column += wide.len_utf8 - wide.width(encoding);
+        }
+
+        Some(utf8_column)
+    }
+}
@@ -1,19 +1,50 @@
 use std::fmt::Display;
+use std::ops::Range;
 
-#[derive(Clone, Copy, PartialEq)]
+// the column counts characters, the offset counts bytes from the start of the source
+#[derive(Clone, Copy, Debug)]
 pub struct LexPosition {
     line: u32,
     column: u32,
+    offset: u32,
 }
 
 impl LexPosition {
     pub fn new(line: u32, column: u32) -> Self {
-        LexPosition { line, column }
+        LexPosition {
+            line,
+            column,
+            offset: 0,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
     }
 
     pub fn zero() -> Self {
         LexPosition::new(0, 0)
     }
+
+    pub fn get_line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn get_column(&self) -> u32 {
+        self.column
+    }
+
+    pub fn get_offset(&self) -> u32 {
+        self.offset
+    }
+}
+
+// the offset follows from the line and column of a given source, it isn't compared
+impl PartialEq for LexPosition {
+    fn eq(&self, other: &Self) -> bool {
+        self.line == other.line && self.column == other.column
+    }
 }
 
 use std::cmp::Ordering;
@@ -61,7 +92,7 @@ impl Display for LexPosition {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LexLocation {
     begin: LexPosition,
     end: LexPosition,
@@ -72,12 +103,8 @@ impl LexLocation {
         LexLocation { begin, end }
     }
 
-    pub fn line_offset(begin: LexPosition, length: u32) -> Self {
-        LexLocation::new(begin, LexPosition::new(begin.line, begin.column + length))
-    }
-
     pub fn line_zero(begin: LexPosition) -> Self {
-        LexLocation::line_offset(begin, 0u32)
+        LexLocation::new(begin, begin)
     }
 
     pub fn zero() -> Self {
@@ -103,6 +130,11 @@ impl LexLocation {
     pub fn get_end(&self) -> LexPosition {
         self.end
     }
+
+    // the source text of the location is &src[location.get_byte_range()]
+    pub fn get_byte_range(&self) -> Range<usize> {
+        self.begin.offset as usize..self.end.offset as usize
+    }
 }
 
 impl Display for LexLocation {
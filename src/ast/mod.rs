@@ -1,15 +1,27 @@
 mod lex_type;
 mod lexeme;
 mod lexer;
+mod line_index;
 mod location;
 mod name_table;
 mod node;
+mod number;
 mod parse;
+mod position_index;
+mod printer;
+#[cfg(test)]
+mod test_util;
+mod trivia;
 
 pub use lex_type::LexType;
 pub use lexeme::Lexeme;
-pub use lexer::Lexer;
+pub use lexer::{InvalidUtf8, Lexer};
+pub use line_index::{ColumnEncoding, LineIndex};
 pub use location::{LexLocation, LexPosition};
 pub use name_table::*;
 pub use node::*;
+pub use number::{to_number, NumberParseResult};
 pub use parse::*;
+pub use position_index::PositionIndex;
+pub use printer::{print_chunk, print_expr, print_stat, print_type, print_type_pack};
+pub use trivia::{Trivia, TriviaKind};
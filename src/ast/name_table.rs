@@ -1,26 +1,42 @@
 use super::{AstName, LexType};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+// reserved words are added to every table, their entries carry the keyword lexeme type
+const RESERVED: [(&str, LexType); 21] = [
+    ("and", LexType::And),
+    ("break", LexType::Break),
+    ("do", LexType::Do),
+    ("else", LexType::Else),
+    ("elseif", LexType::ElseIf),
+    ("end", LexType::End),
+    ("false", LexType::False),
+    ("for", LexType::For),
+    ("function", LexType::Function),
+    ("if", LexType::If),
+    ("in", LexType::In),
+    ("local", LexType::Local),
+    ("nil", LexType::Nil),
+    ("not", LexType::Not),
+    ("or", LexType::Or),
+    ("repeat", LexType::Repeat),
+    ("return", LexType::Return),
+    ("then", LexType::Then),
+    ("true", LexType::True),
+    ("until", LexType::Until),
+    ("while", LexType::While),
+];
 
 #[derive(Clone, PartialEq)]
 pub struct NameTableEntry {
-    value: AstName,
+    value: Arc<str>,
+    // the keyword lexeme type for reserved words, LexType::Name of this entry otherwise
     type_: LexType,
 }
 
 impl NameTableEntry {
-    pub fn new(name: &str, type_: LexType) -> Self {
-        NameTableEntry {
-            value: AstName::new(String::from(name)),
-            type_,
-        }
-    }
-
-    pub fn get_hash(&self) -> u32 {
-        self.value.hash()
-    }
-
-    pub fn get_name(&self) -> AstName {
-        self.value.clone()
+    pub fn get_value(&self) -> &str {
+        &self.value
     }
 
     pub fn get_type(&self) -> LexType {
@@ -28,44 +44,92 @@ impl NameTableEntry {
     }
 }
 
+// interns names, the same text always gives the same AstName
+#[derive(Clone)]
 pub struct NameTable {
-    data: HashMap<u32, NameTableEntry>,
+    entries: Vec<NameTableEntry>,
+    lookup: HashMap<Arc<str>, AstName>,
+}
+
+impl Default for NameTable {
+    fn default() -> Self {
+        NameTable::new()
+    }
 }
 
 impl NameTable {
     pub fn new() -> Self {
-        NameTable {
-            data: HashMap::new(),
+        let mut result = NameTable {
+            entries: Vec::new(),
+            lookup: HashMap::new(),
+        };
+
+        for (name, keyword) in RESERVED.iter() {
+            result.add(name, keyword.clone());
         }
+
+        result
     }
 
+    // adds a name with the lexeme type it is read as, an existing entry is replaced
     pub fn add(&mut self, name: &str, type_: LexType) -> AstName {
-        let entry = &NameTableEntry::new(name, type_);
-        self.data.insert(entry.get_hash(), entry.clone());
+        match type_ {
+            LexType::Name(_) => self.insert(name, None),
+            _ => self.insert(name, Some(type_)),
+        }
+    }
+
+    fn insert(&mut self, name: &str, keyword: Option<LexType>) -> AstName {
+        if let Some(existed_name) = self.lookup.get(name) {
+            let existed_name = *existed_name;
+            self.entries[existed_name.get_index() as usize].type_ =
+                keyword.unwrap_or(LexType::Name(existed_name));
 
-        entry.get_name()
+            return existed_name;
+        }
+
+        let value: Arc<str> = Arc::from(name);
+        let name = AstName::new(self.entries.len() as u32);
+
+        self.entries.push(NameTableEntry {
+            value: Arc::clone(&value),
+            type_: keyword.unwrap_or(LexType::Name(name)),
+        });
+        self.lookup.insert(value, name);
+
+        name
     }
 
     pub fn get_or_add(&mut self, name: &str) -> (AstName, LexType) {
-        let entry = &NameTableEntry::new(name, LexType::Eof);
-
-        if let Some(existed_entry) = self.data.get(&entry.get_hash()) {
-            (existed_entry.get_name(), existed_entry.get_type())
+        if let Some(result) = self.get(name) {
+            result
         } else {
-            let lex_type = &LexType::Name(String::from(name));
-            let name = self.add(name, lex_type.clone());
+            let name = self.insert(name, None);
 
-            (name, lex_type.clone())
+            (name, LexType::Name(name))
         }
     }
 
-    pub fn get(&self, name: &str) -> (AstName, LexType) {
-        let entry = &NameTableEntry::new(name, LexType::Eof);
+    pub fn get(&self, name: &str) -> Option<(AstName, LexType)> {
+        self.lookup
+            .get(name)
+            .map(|name| (*name, self.get_entry(*name).get_type()))
+    }
 
-        if let Some(existed_entry) = self.data.get(&entry.get_hash()) {
-            (existed_entry.get_name(), existed_entry.get_type())
-        } else {
-            (AstName::new(String::new()), LexType::Name(String::new()))
-        }
+    // the name must come from this table
+    pub fn get_entry(&self, name: AstName) -> &NameTableEntry {
+        &self.entries[name.get_index() as usize]
+    }
+
+    pub fn get_value(&self, name: AstName) -> &str {
+        self.get_entry(name).get_value()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
     }
 }
@@ -0,0 +1,215 @@
+use super::{super::LexLocation, AstExpr, AstLocal, AstStat, AstType, AstTypePack};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+// handle of a node in the arena that allocated it, copying it doesn't copy the node,
+// the node type is part of the id so an expression id can't be used where a type is expected
+pub struct NodeId<T> {
+    index: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> NodeId<T> {
+    fn new(index: usize) -> Self {
+        NodeId {
+            index: index as u32,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn get_index(&self) -> u32 {
+        self.index
+    }
+}
+
+// the impls are written out because deriving them would require T to implement them as well
+impl<T> Clone for NodeId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeId<T> {}
+
+impl<T> PartialEq for NodeId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for NodeId<T> {}
+
+impl<T> PartialOrd for NodeId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for NodeId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<T> Hash for NodeId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> Debug for NodeId<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NodeId({})", self.index)
+    }
+}
+
+pub type AstExprId = NodeId<AstExpr>;
+pub type AstTypeId = NodeId<AstType>;
+pub type AstTypePackId = NodeId<AstTypePack>;
+pub type AstStatId = NodeId<AstStat>;
+// every reference to a local shares its id
+pub type LocalId = NodeId<AstLocal>;
+
+// any node of a tree, used where nodes of different kinds are handled together
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AstNodeId {
+    Expr(AstExprId),
+    Stat(AstStatId),
+    Type(AstTypeId),
+    TypePack(AstTypePackId),
+    Local(LocalId),
+}
+
+// owns all nodes and locals of a tree, nodes refer to their children and locals by id
+#[derive(Clone, Default)]
+pub struct AstArena {
+    exprs: Vec<AstExpr>,
+    types: Vec<AstType>,
+    type_packs: Vec<AstTypePack>,
+    stats: Vec<AstStat>,
+    locals: Vec<AstLocal>,
+}
+
+// anything that can be allocated in an arena, each kind has its own storage
+pub trait ArenaNode: Sized {
+    fn get_storage(arena: &AstArena) -> &[Self];
+    fn get_storage_mut(arena: &mut AstArena) -> &mut Vec<Self>;
+}
+
+impl ArenaNode for AstExpr {
+    fn get_storage(arena: &AstArena) -> &[Self] {
+        &arena.exprs
+    }
+
+    fn get_storage_mut(arena: &mut AstArena) -> &mut Vec<Self> {
+        &mut arena.exprs
+    }
+}
+
+impl ArenaNode for AstType {
+    fn get_storage(arena: &AstArena) -> &[Self] {
+        &arena.types
+    }
+
+    fn get_storage_mut(arena: &mut AstArena) -> &mut Vec<Self> {
+        &mut arena.types
+    }
+}
+
+impl ArenaNode for AstTypePack {
+    fn get_storage(arena: &AstArena) -> &[Self] {
+        &arena.type_packs
+    }
+
+    fn get_storage_mut(arena: &mut AstArena) -> &mut Vec<Self> {
+        &mut arena.type_packs
+    }
+}
+
+impl ArenaNode for AstStat {
+    fn get_storage(arena: &AstArena) -> &[Self] {
+        &arena.stats
+    }
+
+    fn get_storage_mut(arena: &mut AstArena) -> &mut Vec<Self> {
+        &mut arena.stats
+    }
+}
+
+impl ArenaNode for AstLocal {
+    fn get_storage(arena: &AstArena) -> &[Self] {
+        &arena.locals
+    }
+
+    fn get_storage_mut(arena: &mut AstArena) -> &mut Vec<Self> {
+        &mut arena.locals
+    }
+}
+
+impl AstArena {
+    pub fn new() -> Self {
+        AstArena::default()
+    }
+
+    pub fn alloc<T: ArenaNode>(&mut self, node: T) -> NodeId<T> {
+        let storage = T::get_storage_mut(self);
+        storage.push(node);
+
+        NodeId::new(storage.len() - 1)
+    }
+
+    // ids must come from this arena
+    pub fn get<T: ArenaNode>(&self, id: NodeId<T>) -> &T {
+        &T::get_storage(self)[id.index as usize]
+    }
+
+    pub fn get_mut<T: ArenaNode>(&mut self, id: NodeId<T>) -> &mut T {
+        &mut T::get_storage_mut(self)[id.index as usize]
+    }
+
+    // for locals this is the location of their name
+    pub fn get_location(&self, node: AstNodeId) -> LexLocation {
+        match node {
+            AstNodeId::Expr(id) => self[id].get_location(),
+            AstNodeId::Stat(id) => self[id].get_location(),
+            AstNodeId::Type(id) => self[id].get_location(),
+            AstNodeId::TypePack(id) => self[id].get_location(),
+            AstNodeId::Local(id) => self[id].get_location(),
+        }
+    }
+
+    // all ids of one kind in allocation order, children are allocated before their parents
+    pub fn ids<T: ArenaNode>(&self) -> impl Iterator<Item = NodeId<T>> {
+        (0..T::get_storage(self).len()).map(NodeId::new)
+    }
+
+    // number of nodes, locals aren't counted
+    pub fn len(&self) -> usize {
+        self.exprs.len() + self.types.len() + self.type_packs.len() + self.stats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_locals_len(&self) -> usize {
+        self.locals.len()
+    }
+}
+
+impl<T: ArenaNode> Index<NodeId<T>> for AstArena {
+    type Output = T;
+
+    fn index(&self, id: NodeId<T>) -> &T {
+        self.get(id)
+    }
+}
+
+impl<T: ArenaNode> IndexMut<NodeId<T>> for AstArena {
+    fn index_mut(&mut self, id: NodeId<T>) -> &mut T {
+        self.get_mut(id)
+    }
+}
@@ -5,3 +5,17 @@ pub struct AstArgumentName {
     name: AstName,
     location: LexLocation,
 }
+
+impl AstArgumentName {
+    pub fn new(name: AstName, location: LexLocation) -> Self {
+        AstArgumentName { name, location }
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+}
@@ -1,11 +1,12 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, AstExprId, Expr, LexLocation};
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BinaryOperator {
     Add,
     Sub,
     Mul,
     Div,
+    FloorDiv,
     Mod,
     Pow,
     Concat,
@@ -24,12 +25,18 @@ impl BinaryOperator {
         match self {
             &BinaryOperator::Or => 1,
             &BinaryOperator::And => 2,
-            &BinaryOperator::NotEqual | &BinaryOperator::Equal
-            | &BinaryOperator::Less | &BinaryOperator::LessEqual
-            | &BinaryOperator::Greater | &BinaryOperator::GreaterEqual => 3,
+            &BinaryOperator::NotEqual
+            | &BinaryOperator::Equal
+            | &BinaryOperator::Less
+            | &BinaryOperator::LessEqual
+            | &BinaryOperator::Greater
+            | &BinaryOperator::GreaterEqual => 3,
             &BinaryOperator::Concat => 5,
             &BinaryOperator::Add | &BinaryOperator::Sub => 6,
-            &BinaryOperator::Mul | &BinaryOperator::Div | &BinaryOperator::Mod => 7,
+            &BinaryOperator::Mul
+            | &BinaryOperator::Div
+            | &BinaryOperator::FloorDiv
+            | &BinaryOperator::Mod => 7,
             &BinaryOperator::Pow => 10,
         }
     }
@@ -38,12 +45,18 @@ impl BinaryOperator {
         match self {
             &BinaryOperator::Or => 1,
             &BinaryOperator::And => 2,
-            &BinaryOperator::NotEqual | &BinaryOperator::Equal
-            | &BinaryOperator::Less | &BinaryOperator::LessEqual
-            | &BinaryOperator::Greater | &BinaryOperator::GreaterEqual => 3,
+            &BinaryOperator::NotEqual
+            | &BinaryOperator::Equal
+            | &BinaryOperator::Less
+            | &BinaryOperator::LessEqual
+            | &BinaryOperator::Greater
+            | &BinaryOperator::GreaterEqual => 3,
             &BinaryOperator::Concat => 4,
             &BinaryOperator::Add | &BinaryOperator::Sub => 6,
-            &BinaryOperator::Mul | &BinaryOperator::Div | &BinaryOperator::Mod => 7,
+            &BinaryOperator::Mul
+            | &BinaryOperator::Div
+            | &BinaryOperator::FloorDiv
+            | &BinaryOperator::Mod => 7,
             &BinaryOperator::Pow => 9,
         }
     }
@@ -52,24 +65,37 @@ impl BinaryOperator {
 #[derive(Clone)]
 pub struct ExprBinary {
     operator: BinaryOperator,
-    left: Box<AstExpr>,
-    right: Box<AstExpr>,
+    left: AstExprId,
+    right: AstExprId,
 }
 
 impl ExprBinary {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         location: LexLocation,
         operator: BinaryOperator,
-        left: Box<AstExpr>,
-        right: Box<AstExpr>,
-    ) -> Box<AstExpr> {
+        left: AstExprId,
+        right: AstExprId,
+    ) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprBinary(Box::new(ExprBinary {
+            Expr::Binary(Box::new(ExprBinary {
                 operator,
                 left,
                 right,
             })),
         )
     }
+
+    pub fn get_operator(&self) -> BinaryOperator {
+        self.operator
+    }
+
+    pub fn get_left(&self) -> AstExprId {
+        self.left
+    }
+
+    pub fn get_right(&self) -> AstExprId {
+        self.right
+    }
 }
@@ -1,24 +1,25 @@
-use super::super::{super::LexLocation, AstExpr, AstNodePayload};
+use super::super::{super::LexLocation, AstExpr, AstExprId, Expr};
 
 #[derive(Clone)]
 pub struct ExprCall {
-    func: Box<AstExpr>,
-    args: Vec<Box<AstExpr>>,
+    func: AstExprId,
+    args: Vec<AstExprId>,
     self_: bool,
     arg_location: LexLocation,
 }
 
 impl ExprCall {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         location: LexLocation,
-        func: Box<AstExpr>,
-        args: Vec<Box<AstExpr>>,
+        func: AstExprId,
+        args: Vec<AstExprId>,
         self_: bool,
         arg_location: LexLocation,
-    ) -> Box<AstExpr> {
+    ) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprCall(Box::new(ExprCall {
+            Expr::Call(Box::new(ExprCall {
                 func,
                 args,
                 self_,
@@ -26,4 +27,20 @@ impl ExprCall {
             })),
         )
     }
+
+    pub fn get_func(&self) -> AstExprId {
+        self.func
+    }
+
+    pub fn get_args(&self) -> &[AstExprId] {
+        &self.args
+    }
+
+    pub fn has_self(&self) -> bool {
+        self.self_
+    }
+
+    pub fn get_arg_location(&self) -> LexLocation {
+        self.arg_location
+    }
 }
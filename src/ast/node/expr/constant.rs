@@ -1,17 +1,17 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, Expr, LexLocation};
 
-pub fn new_constant_nil(location: LexLocation) -> Box<AstExpr> {
-    AstExpr::new(location, AstNodePayload::ExprConstantNil)
+pub fn new_constant_nil(location: LexLocation) -> AstExpr {
+    AstExpr::new(location, Expr::ConstantNil)
 }
 
-pub fn new_constant_bool(location: LexLocation, value: bool) -> Box<AstExpr> {
-    AstExpr::new(location, AstNodePayload::ExprConstantBool(value))
+pub fn new_constant_bool(location: LexLocation, value: bool) -> AstExpr {
+    AstExpr::new(location, Expr::ConstantBool(value))
 }
 
-pub fn new_constant_number(location: LexLocation, value: f64) -> Box<AstExpr> {
-    AstExpr::new(location, AstNodePayload::ExprConstantNumber(value))
+pub fn new_constant_number(location: LexLocation, value: f64) -> AstExpr {
+    AstExpr::new(location, Expr::ConstantNumber(value))
 }
 
-pub fn new_constant_string(location: LexLocation, value: String) -> Box<AstExpr> {
-    AstExpr::new(location, AstNodePayload::ExprConstantString(value))
+pub fn new_constant_string(location: LexLocation, value: Vec<u8>) -> AstExpr {
+    AstExpr::new(location, Expr::ConstantString(value))
 }
@@ -1,23 +1,32 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, AstExprId, Expr, LexLocation};
 
 #[derive(Clone)]
 pub struct ExprError {
-    expressions: Vec<Box<AstExpr>>,
+    expressions: Vec<AstExprId>,
     message_index: usize,
 }
 
 impl ExprError {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         location: LexLocation,
-        expressions: Vec<Box<AstExpr>>,
+        expressions: Vec<AstExprId>,
         message_index: usize,
-    ) -> Box<AstExpr> {
+    ) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprError(Box::new(ExprError {
+            Expr::Error(Box::new(ExprError {
                 expressions,
                 message_index,
             })),
         )
     }
+
+    pub fn get_expressions(&self) -> &[AstExprId] {
+        &self.expressions
+    }
+
+    pub fn get_message_index(&self) -> usize {
+        self.message_index
+    }
 }
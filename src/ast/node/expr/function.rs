@@ -1,19 +1,111 @@
-use super::super::{super::LexLocation, AstLocal, AstName, AstStat, AstTypeList, AstTypePack};
+use super::super::{
+    super::LexLocation, AstExpr, AstName, AstStatId, AstTypeList, AstTypePackId, Expr, LocalId,
+};
 
 #[derive(Clone)]
 pub struct ExprFunction {
     generics: Vec<AstName>,
     generic_packs: Vec<AstName>,
-    self_: Box<AstLocal>,
-    args: Vec<Box<AstLocal>>,
-    has_return_annotation: bool,
-    return_annotation: AstTypeList,
+    self_: Option<LocalId>,
+    args: Vec<LocalId>,
+    return_annotation: Option<AstTypeList>,
     vararg: bool,
     vararg_location: LexLocation,
-    vararg_annotation: Box<AstTypePack>,
-    body: Box<AstStat>,
-    function_depth: u32,
+    vararg_annotation: Option<AstTypePackId>,
+    body: AstStatId,
+    function_depth: usize,
     debug_name: AstName,
     has_end: bool,
     arg_location: Option<LexLocation>,
 }
+
+impl ExprFunction {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        generics: Vec<AstName>,
+        generic_packs: Vec<AstName>,
+        self_: Option<LocalId>,
+        args: Vec<LocalId>,
+        return_annotation: Option<AstTypeList>,
+        vararg: bool,
+        vararg_location: LexLocation,
+        vararg_annotation: Option<AstTypePackId>,
+        body: AstStatId,
+        function_depth: usize,
+        debug_name: AstName,
+        has_end: bool,
+        arg_location: Option<LexLocation>,
+    ) -> Box<ExprFunction> {
+        Box::new(ExprFunction {
+            generics,
+            generic_packs,
+            self_,
+            args,
+            return_annotation,
+            vararg,
+            vararg_location,
+            vararg_annotation,
+            body,
+            function_depth,
+            debug_name,
+            has_end,
+            arg_location,
+        })
+    }
+
+    pub fn get_generics(&self) -> &[AstName] {
+        &self.generics
+    }
+
+    pub fn get_generic_packs(&self) -> &[AstName] {
+        &self.generic_packs
+    }
+
+    pub fn get_self(&self) -> Option<LocalId> {
+        self.self_
+    }
+
+    pub fn get_args(&self) -> &[LocalId] {
+        &self.args
+    }
+
+    pub fn get_return_annotation(&self) -> Option<&AstTypeList> {
+        self.return_annotation.as_ref()
+    }
+
+    pub fn is_vararg(&self) -> bool {
+        self.vararg
+    }
+
+    pub fn get_vararg_location(&self) -> LexLocation {
+        self.vararg_location
+    }
+
+    pub fn get_vararg_annotation(&self) -> Option<AstTypePackId> {
+        self.vararg_annotation
+    }
+
+    pub fn get_body(&self) -> AstStatId {
+        self.body
+    }
+
+    pub fn get_function_depth(&self) -> usize {
+        self.function_depth
+    }
+
+    pub fn get_debug_name(&self) -> AstName {
+        self.debug_name
+    }
+
+    pub fn has_end(&self) -> bool {
+        self.has_end
+    }
+
+    pub fn get_arg_location(&self) -> Option<LexLocation> {
+        self.arg_location
+    }
+}
+
+pub fn new_expr_function(location: LexLocation, function: Box<ExprFunction>) -> AstExpr {
+    AstExpr::new(location, Expr::Function(function))
+}
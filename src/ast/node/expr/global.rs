@@ -1,5 +1,5 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, AstName, Expr, LexLocation};
 
-pub fn new_expr_global(location: LexLocation, name: AstName) -> Box<AstExpr> {
-    AstExpr::new(location, AstNodePayload::ExprGlobal(name))
+pub fn new_expr_global(location: LexLocation, name: AstName) -> AstExpr {
+    AstExpr::new(location, Expr::Global(name))
 }
@@ -1,5 +1,5 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, AstExprId, Expr, LexLocation};
 
-pub fn new_expr_group(location: LexLocation, value: Box<AstExpr>) -> Box<AstExpr> {
-    AstExpr::new(location, AstNodePayload::ExprGroup(value))
+pub fn new_expr_group(location: LexLocation, value: AstExprId) -> AstExpr {
+    AstExpr::new(location, Expr::Group(value))
 }
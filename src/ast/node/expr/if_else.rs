@@ -1,26 +1,39 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, AstExprId, Expr, LexLocation};
 
 #[derive(Clone)]
 pub struct ExprIfElse {
-    condition: Box<AstExpr>,
-    true_expr: Option<Box<AstExpr>>,
-    false_expr: Option<Box<AstExpr>>,
+    condition: AstExprId,
+    true_expr: Option<AstExprId>,
+    false_expr: Option<AstExprId>,
 }
 
 impl ExprIfElse {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         location: LexLocation,
-        condition: Box<AstExpr>,
-        true_expr: Option<Box<AstExpr>>,
-        false_expr: Option<Box<AstExpr>>,
-    ) -> Box<AstExpr> {
+        condition: AstExprId,
+        true_expr: Option<AstExprId>,
+        false_expr: Option<AstExprId>,
+    ) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprIfElse(Box::new(ExprIfElse {
+            Expr::IfElse(Box::new(ExprIfElse {
                 condition,
                 true_expr,
                 false_expr,
             })),
         )
     }
+
+    pub fn get_condition(&self) -> AstExprId {
+        self.condition
+    }
+
+    pub fn get_true_expr(&self) -> Option<AstExprId> {
+        self.true_expr
+    }
+
+    pub fn get_false_expr(&self) -> Option<AstExprId> {
+        self.false_expr
+    }
 }
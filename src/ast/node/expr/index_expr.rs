@@ -1,16 +1,25 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, AstExprId, Expr, LexLocation};
 
 #[derive(Clone)]
 pub struct ExprIndexExpr {
-    expr: Box<AstExpr>,
-    index: Box<AstExpr>,
+    expr: AstExprId,
+    index: AstExprId,
 }
 
 impl ExprIndexExpr {
-    pub fn new(location: LexLocation, expr: Box<AstExpr>, index: Box<AstExpr>) -> Box<AstExpr> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, expr: AstExprId, index: AstExprId) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprIndexExpr(Box::new(ExprIndexExpr { expr, index })),
+            Expr::IndexExpr(Box::new(ExprIndexExpr { expr, index })),
         )
     }
+
+    pub fn get_expr(&self) -> AstExprId {
+        self.expr
+    }
+
+    pub fn get_index(&self) -> AstExprId {
+        self.index
+    }
 }
@@ -1,11 +1,11 @@
 use super::super::{
-    super::{AstNodePayload, LexLocation, LexPosition},
-    AstExpr, AstName,
+    super::{Expr, LexLocation, LexPosition},
+    AstExpr, AstExprId, AstName,
 };
 
 #[derive(Clone)]
 pub struct ExprIndexName {
-    expr: Box<AstExpr>,
+    expr: AstExprId,
     index: AstName,
     index_location: LexLocation,
     op_position: LexPosition,
@@ -13,17 +13,18 @@ pub struct ExprIndexName {
 }
 
 impl ExprIndexName {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         location: LexLocation,
-        expr: Box<AstExpr>,
+        expr: AstExprId,
         index: AstName,
         index_location: LexLocation,
         op_position: LexPosition,
         op: char,
-    ) -> Box<AstExpr> {
+    ) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprIndexName(Box::new(ExprIndexName {
+            Expr::IndexName(Box::new(ExprIndexName {
                 expr,
                 index,
                 index_location,
@@ -32,4 +33,24 @@ impl ExprIndexName {
             })),
         )
     }
+
+    pub fn get_expr(&self) -> AstExprId {
+        self.expr
+    }
+
+    pub fn get_index(&self) -> AstName {
+        self.index
+    }
+
+    pub fn get_index_location(&self) -> LexLocation {
+        self.index_location
+    }
+
+    pub fn get_op_position(&self) -> LexPosition {
+        self.op_position
+    }
+
+    pub fn get_op(&self) -> char {
+        self.op
+    }
 }
@@ -0,0 +1,34 @@
+use super::super::{AstExpr, AstExprId, Expr, LexLocation};
+
+// `a{b}c{d}e` holds the strings a, c, e and the expressions b, d,
+// there is always one more string than there are expressions
+#[derive(Clone)]
+pub struct ExprInterpString {
+    strings: Vec<Vec<u8>>,
+    expressions: Vec<AstExprId>,
+}
+
+impl ExprInterpString {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        strings: Vec<Vec<u8>>,
+        expressions: Vec<AstExprId>,
+    ) -> AstExpr {
+        AstExpr::new(
+            location,
+            Expr::InterpString(Box::new(ExprInterpString {
+                strings,
+                expressions,
+            })),
+        )
+    }
+
+    pub fn get_strings(&self) -> &[Vec<u8>] {
+        &self.strings
+    }
+
+    pub fn get_expressions(&self) -> &[AstExprId] {
+        &self.expressions
+    }
+}
@@ -1,16 +1,25 @@
-use super::super::{AstExpr, AstLocal, AstNodePayload, LexLocation};
+use super::super::{AstExpr, Expr, LexLocation, LocalId};
 
 #[derive(Clone)]
 pub struct ExprLocal {
-    local: AstLocal,
+    local: LocalId,
     upvalue: bool,
 }
 
 impl ExprLocal {
-    pub fn new(location: LexLocation, local: AstLocal, upvalue: bool) -> Box<AstExpr> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, local: LocalId, upvalue: bool) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprLocal(Box::new(ExprLocal { local, upvalue })),
+            Expr::Local(Box::new(ExprLocal { local, upvalue })),
         )
     }
+
+    pub fn get_local(&self) -> LocalId {
+        self.local
+    }
+
+    pub fn is_upvalue(&self) -> bool {
+        self.upvalue
+    }
 }
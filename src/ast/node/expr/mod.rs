@@ -8,24 +8,77 @@ mod group;
 mod if_else;
 mod index_expr;
 mod index_name;
+mod interp_string;
 mod local;
 mod table;
 mod type_assertion;
 mod unary;
 mod varargs;
 
-pub use varargs::*;
 pub use binary::*;
 pub use call::ExprCall;
 pub use constant::*;
 pub use error::ExprError;
-pub use function::ExprFunction;
+pub use function::*;
 pub use global::*;
 pub use group::*;
 pub use if_else::ExprIfElse;
 pub use index_expr::ExprIndexExpr;
 pub use index_name::ExprIndexName;
+pub use interp_string::ExprInterpString;
 pub use local::ExprLocal;
 pub use table::*;
 pub use type_assertion::ExprTypeAssertion;
 pub use unary::*;
+pub use varargs::*;
+
+use super::{AstExprId, AstName, AstNodePayloadType};
+
+#[derive(Clone)]
+pub enum Expr {
+    Group(AstExprId),
+    ConstantNil,
+    ConstantBool(bool),
+    ConstantNumber(f64),
+    ConstantString(Vec<u8>),
+    Local(Box<ExprLocal>),
+    Global(AstName),
+    Varargs,
+    Call(Box<ExprCall>),
+    IndexName(Box<ExprIndexName>),
+    IndexExpr(Box<ExprIndexExpr>),
+    Binary(Box<ExprBinary>),
+    Function(Box<ExprFunction>),
+    IfElse(Box<ExprIfElse>),
+    InterpString(Box<ExprInterpString>),
+    Table(Vec<TableItem>),
+    TypeAssertion(Box<ExprTypeAssertion>),
+    Unary(Box<ExprUnary>),
+    Error(Box<ExprError>),
+}
+
+impl Expr {
+    pub fn get_type(&self) -> AstNodePayloadType {
+        match self {
+            Self::Group(_) => AstNodePayloadType::ExprGroup,
+            Self::ConstantNil => AstNodePayloadType::ExprConstantNil,
+            Self::ConstantBool(_) => AstNodePayloadType::ExprConstantBool,
+            Self::ConstantNumber(_) => AstNodePayloadType::ExprConstantNumber,
+            Self::ConstantString(_) => AstNodePayloadType::ExprConstantString,
+            Self::Local(_) => AstNodePayloadType::ExprLocal,
+            Self::Global(_) => AstNodePayloadType::ExprGlobal,
+            Self::Varargs => AstNodePayloadType::ExprVarargs,
+            Self::Call(_) => AstNodePayloadType::ExprCall,
+            Self::IndexName(_) => AstNodePayloadType::ExprIndexName,
+            Self::IndexExpr(_) => AstNodePayloadType::ExprIndexExpr,
+            Self::Binary(_) => AstNodePayloadType::ExprBinary,
+            Self::Function(_) => AstNodePayloadType::ExprFunction,
+            Self::IfElse(_) => AstNodePayloadType::ExprIfElse,
+            Self::InterpString(_) => AstNodePayloadType::ExprInterpString,
+            Self::Table(_) => AstNodePayloadType::ExprTable,
+            Self::TypeAssertion(_) => AstNodePayloadType::ExprTypeAssertion,
+            Self::Unary(_) => AstNodePayloadType::ExprUnary,
+            Self::Error(_) => AstNodePayloadType::ExprError,
+        }
+    }
+}
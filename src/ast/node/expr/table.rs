@@ -1,6 +1,6 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, AstExprId, Expr, LexLocation};
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TableKind {
     List,
     Record,
@@ -11,16 +11,28 @@ pub enum TableKind {
 pub struct TableItem {
     kind: TableKind,
 
-    key: Box<AstExpr>,
-    value: Box<AstExpr>,
+    key: Option<AstExprId>,
+    value: AstExprId,
 }
 
 impl TableItem {
-    pub fn new(kind: TableKind, key: Box<AstExpr>, value: Box<AstExpr>) -> Self {
+    pub fn new(kind: TableKind, key: Option<AstExprId>, value: AstExprId) -> Self {
         TableItem { kind, key, value }
     }
+
+    pub fn get_kind(&self) -> TableKind {
+        self.kind
+    }
+
+    pub fn get_key(&self) -> Option<AstExprId> {
+        self.key
+    }
+
+    pub fn get_value(&self) -> AstExprId {
+        self.value
+    }
 }
 
-pub fn new_expr_table(location: LexLocation, items: Vec<TableItem>) -> Box<AstExpr> {
-    AstExpr::new(location, AstNodePayload::ExprTable(items))
+pub fn new_expr_table(location: LexLocation, items: Vec<TableItem>) -> AstExpr {
+    AstExpr::new(location, Expr::Table(items))
 }
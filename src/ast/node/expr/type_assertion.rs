@@ -1,20 +1,25 @@
-use super::super::{AstExpr, AstNodePayload, AstType, LexLocation};
+use super::super::{AstExpr, AstExprId, AstTypeId, Expr, LexLocation};
 
 #[derive(Clone)]
 pub struct ExprTypeAssertion {
-    expr: Box<AstExpr>,
-    annotation: Box<AstType>,
+    expr: AstExprId,
+    annotation: AstTypeId,
 }
 
 impl ExprTypeAssertion {
-    pub fn new(
-        location: LexLocation,
-        expr: Box<AstExpr>,
-        annotation: Box<AstType>,
-    ) -> Box<AstExpr> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, expr: AstExprId, annotation: AstTypeId) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprTypeAssertion(Box::new(ExprTypeAssertion { expr, annotation })),
+            Expr::TypeAssertion(Box::new(ExprTypeAssertion { expr, annotation })),
         )
     }
+
+    pub fn get_expr(&self) -> AstExprId {
+        self.expr
+    }
+
+    pub fn get_annotation(&self) -> AstTypeId {
+        self.annotation
+    }
 }
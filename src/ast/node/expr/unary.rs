@@ -1,23 +1,38 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, AstExprId, Expr, LexLocation};
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum UnaryOperator {
     Not,
     Minus,
     Len,
 }
 
+impl UnaryOperator {
+    pub fn priority(&self) -> usize {
+        8
+    }
+}
+
 #[derive(Clone)]
 pub struct ExprUnary {
     operator: UnaryOperator,
-    expr: Box<AstExpr>,
+    expr: AstExprId,
 }
 
 impl ExprUnary {
-    pub fn new(location: LexLocation, operator: UnaryOperator, expr: Box<AstExpr>) -> Box<AstExpr> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, operator: UnaryOperator, expr: AstExprId) -> AstExpr {
         AstExpr::new(
             location,
-            AstNodePayload::ExprUnary(Box::new(ExprUnary { operator, expr })),
+            Expr::Unary(Box::new(ExprUnary { operator, expr })),
         )
     }
+
+    pub fn get_operator(&self) -> UnaryOperator {
+        self.operator
+    }
+
+    pub fn get_expr(&self) -> AstExprId {
+        self.expr
+    }
 }
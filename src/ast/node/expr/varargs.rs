@@ -1,5 +1,5 @@
-use super::super::{AstExpr, AstNodePayload, LexLocation};
+use super::super::{AstExpr, Expr, LexLocation};
 
-pub fn new_expr_varargs(location: LexLocation) -> Box<AstExpr> {
-    AstExpr::new(location, AstNodePayload::ExprVarargs)
+pub fn new_expr_varargs(location: LexLocation) -> AstExpr {
+    AstExpr::new(location, Expr::Varargs)
 }
@@ -0,0 +1,59 @@
+use super::{super::LexLocation, AstName, AstTypeId, AstTypePackId};
+
+#[derive(Clone)]
+pub struct AstGenericType {
+    name: AstName,
+    location: LexLocation,
+    default_value: Option<AstTypeId>,
+}
+
+impl AstGenericType {
+    pub fn new(name: AstName, location: LexLocation, default_value: Option<AstTypeId>) -> Self {
+        AstGenericType {
+            name,
+            location,
+            default_value,
+        }
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+
+    pub fn get_default_value(&self) -> Option<AstTypeId> {
+        self.default_value
+    }
+}
+
+#[derive(Clone)]
+pub struct AstGenericTypePack {
+    name: AstName,
+    location: LexLocation,
+    default_value: Option<AstTypePackId>,
+}
+
+impl AstGenericTypePack {
+    pub fn new(name: AstName, location: LexLocation, default_value: Option<AstTypePackId>) -> Self {
+        AstGenericTypePack {
+            name,
+            location,
+            default_value,
+        }
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+
+    pub fn get_default_value(&self) -> Option<AstTypePackId> {
+        self.default_value
+    }
+}
@@ -1,26 +1,29 @@
-use super::{super::LexLocation, AstName, AstNode};
+use super::{super::LexLocation, AstName, AstTypeId, LocalId};
 
 #[derive(Clone)]
 pub struct AstLocal {
     name: AstName,
     location: LexLocation,
+    shadow: Option<LocalId>,
     function_depth: usize,
     loop_depth: u32,
 
-    annotation: Box<AstNode>,
+    annotation: Option<AstTypeId>,
 }
 
 impl AstLocal {
     pub fn new(
         name: AstName,
         location: LexLocation,
+        shadow: Option<LocalId>,
         function_depth: usize,
         loop_depth: u32,
-        annotation: Box<AstNode>,
+        annotation: Option<AstTypeId>,
     ) -> Self {
         AstLocal {
             name,
             location,
+            shadow,
             function_depth,
             loop_depth,
             annotation,
@@ -28,10 +31,26 @@ impl AstLocal {
     }
 
     pub fn get_name(&self) -> AstName {
-        self.name.clone()
+        self.name
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+
+    pub fn get_shadow(&self) -> Option<LocalId> {
+        self.shadow
     }
 
     pub fn get_function_depth(&self) -> usize {
         self.function_depth
     }
+
+    pub fn get_loop_depth(&self) -> u32 {
+        self.loop_depth
+    }
+
+    pub fn get_annotation(&self) -> Option<AstTypeId> {
+        self.annotation
+    }
 }
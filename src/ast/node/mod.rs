@@ -1,21 +1,26 @@
+mod arena;
 mod argument_name;
 mod expr;
+mod generic;
 mod local;
 mod name;
 mod stat;
 mod type_;
+mod visit;
 
+pub use arena::*;
 pub use argument_name::AstArgumentName;
 pub use expr::*;
+pub use generic::{AstGenericType, AstGenericTypePack};
 pub use local::AstLocal;
 pub use name::AstName;
 pub use stat::*;
 pub use type_::*;
+pub use visit::*;
 
-#[derive(Debug, PartialEq, Eq)]
+// the kind of a node, shared by all node enums so nodes can be told apart without matching
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AstNodePayloadType {
-    None,
-
     ExprGroup,
     ExprConstantNil,
     ExprConstantBool,
@@ -30,6 +35,7 @@ pub enum AstNodePayloadType {
     ExprBinary,
     ExprFunction,
     ExprIfElse,
+    ExprInterpString,
     ExprTable,
     ExprTypeAssertion,
     ExprUnary,
@@ -71,270 +77,105 @@ pub enum AstNodePayloadType {
     TypePackVariadic,
 }
 
-#[derive(Clone)]
-pub enum AstNodePayload {
-    None,
-
-    ExprGroup(Box<AstExpr>),
-    ExprConstantNil,
-    ExprConstantBool(bool),
-    ExprConstantNumber(f64),
-    ExprConstantString(String),
-    ExprLocal(Box<ExprLocal>),
-    ExprGlobal(AstName),
-    ExprVarargs,
-    ExprCall(Box<ExprCall>),
-    ExprIndexName(Box<ExprIndexName>),
-    ExprIndexExpr(Box<ExprIndexExpr>),
-    ExprBinary(Box<ExprBinary>),
-    ExprFunction(Box<ExprFunction>),
-    ExprIfElse(Box<ExprIfElse>),
-    ExprTable(Vec<TableItem>),
-    ExprTypeAssertion(Box<ExprTypeAssertion>),
-    ExprUnary(Box<ExprUnary>),
-    ExprError(Box<ExprError>),
-
-    StatAssign(Box<StatAssign>),
-    StatBlock(Vec<Box<AstStat>>),
-    StatCompoundAssign(Box<StatCompoundAssign>),
-    StatDeclareClass(Box<StatDeclareClass>),
-    StatDeclareFunction(Box<StatFunction>),
-    StatDeclareGlobal(Box<StatDeclareGlobal>),
-    StatExpr(Box<StatExpr>),
-    StatForIn(Box<StatForIn>),
-    StatFunction(Box<StatFunction>),
-    StatLocal(Box<StatLocal>),
-    StatLocalFunction(Box<StatLocalFunction>),
-    StatBreak,
-    StatContinue,
-    StatFor(Box<StatFor>),
-    StatIf(Box<StatIf>),
-    StatRepeat(Box<StatRepeat>),
-    StatReturn(Box<StatReturn>),
-    StatWhile(Box<StatWhile>),
-    StatTypeAlias(Box<StatTypeAlias>),
-    StatError(Box<StatError>),
-
-    TypeError(Box<TypeError>),
-    TypeFunction(Box<TypeFunction>),
-    TypeIntersection(Vec<Box<AstType>>),
-    TypeReference(Box<TypeReference>),
-    TypeSingletonBool(bool),
-    TypeSingletonString(String),
-    TypeTable(Box<TypeTable>),
-    TypeTypeof(Box<AstExpr>),
-    TypeUnion(Vec<Box<AstType>>),
-
-    TypePackExplicit(Box<AstTypeList>),
-    TypePackGeneric(AstName),
-    TypePackVariadic(Box<AstType>),
-}
-
 use super::LexLocation;
 
+// a node of the tree, the payload is one of Expr, Stat, Type or TypePack
 #[derive(Clone)]
-pub struct AstNodeState {
-    class_index: i32,
+pub struct AstNode<T> {
     location: LexLocation,
+    payload: T,
+
+    has_semicolon: bool,
 }
 
-impl AstNodeState {
-    pub fn new(class_index: i32, location: LexLocation) -> Self {
-        AstNodeState {
-            class_index,
+impl<T> AstNode<T> {
+    // nodes are stored in an AstArena, children are referenced by their NodeId, the `new` of a
+    // payload struct builds the whole node around it
+    pub fn new(location: LexLocation, payload: T) -> Self {
+        AstNode {
             location,
+            payload,
+            has_semicolon: false,
         }
     }
 
-    pub fn get_location(&self) -> LexLocation {
-        self.location
+    pub fn get_payload(&self) -> &T {
+        &self.payload
     }
-}
-
-#[derive(Clone)]
-pub struct AstNode {
-    state: AstNodeState,
-    payload: AstNodePayload,
-
-    has_semicolon: bool,
-}
 
-impl AstNode {
-    pub fn get_payload(&self) -> AstNodePayload {
-        self.payload.clone()
+    pub fn get_payload_mut(&mut self) -> &mut T {
+        &mut self.payload
     }
 
     pub fn get_location(&self) -> LexLocation {
-        self.state.get_location()
+        self.location
+    }
+
+    pub fn set_location(&mut self, location: LexLocation) {
+        self.location = location;
     }
 }
 
-pub type AstExpr = AstNode;
-pub type AstType = AstNode;
-pub type AstTypePack = AstNode;
-pub type AstStat = AstNode;
+pub type AstExpr = AstNode<Expr>;
+pub type AstType = AstNode<Type>;
+pub type AstTypePack = AstNode<TypePack>;
+pub type AstStat = AstNode<Stat>;
 
-impl AstNode {
-    pub fn new_nil() -> Box<Self> {
-        Box::new(AstNode {
-            state: AstNodeState::new(0, LexLocation::zero()),
-            payload: AstNodePayload::None,
-            has_semicolon: false,
-        })
-    }
-    pub fn new(location: LexLocation, payload: AstNodePayload) -> Box<Self> {
-        Box::new(AstNode {
-            state: AstNodeState::new(0, location),
-            payload,
-            has_semicolon: false,
-        })
+impl AstExpr {
+    pub fn get_payload_type(&self) -> AstNodePayloadType {
+        self.payload.get_type()
     }
 }
 
 impl AstType {
-    pub fn is_type_pack(&self) -> bool {
-        match self.payload {
-            AstNodePayload::TypePackVariadic(_)
-            | AstNodePayload::TypePackGeneric(_)
-            | AstNodePayload::TypePackExplicit(_) => true,
-            _ => false,
-        }
+    pub fn get_payload_type(&self) -> AstNodePayloadType {
+        self.payload.get_type()
+    }
+}
+
+impl AstTypePack {
+    pub fn get_payload_type(&self) -> AstNodePayloadType {
+        self.payload.get_type()
     }
 }
 
 impl AstStat {
+    pub fn get_payload_type(&self) -> AstNodePayloadType {
+        self.payload.get_type()
+    }
+
     pub fn set_has_semicolon(&mut self, has_semicolon: bool) {
         self.has_semicolon = has_semicolon;
     }
+
+    pub fn has_semicolon(&self) -> bool {
+        self.has_semicolon
+    }
 }
 
 #[derive(Clone)]
 pub struct AstTypeList {
-    types: Vec<AstType>,
-    tail_type: Box<AstTypePack>,
+    types: Vec<AstTypeId>,
+    tail_type: Option<AstTypePackId>,
 }
 
-impl AstNodePayload {
-    pub fn is_expr(&self) -> bool {
-        match self {
-            &Self::ExprError(_)
-            | &Self::ExprGroup(_)
-            | &Self::ExprConstantNil
-            | &Self::ExprConstantBool(_)
-            | &Self::ExprConstantNumber(_)
-            | &Self::ExprLocal(_)
-            | &Self::ExprGlobal(_)
-            | &Self::ExprVarargs
-            | &Self::ExprCall(_)
-            | &Self::ExprIndexName(_)
-            | &Self::ExprIndexExpr(_)
-            | &Self::ExprBinary(_)
-            | &Self::ExprFunction(_)
-            | &Self::ExprIfElse(_)
-            | &Self::ExprTable(_)
-            | &Self::ExprTypeAssertion(_)
-            | &Self::ExprUnary(_) => true,
-            _ => false,
-        }
-    }
-
-    pub fn is_stat(&self) -> bool {
-        match self {
-            &Self::StatAssign(_)
-            | &Self::StatBlock(_)
-            | &Self::StatCompoundAssign(_)
-            | &Self::StatDeclareClass(_)
-            | &Self::StatDeclareFunction(_)
-            | &Self::StatDeclareGlobal(_)
-            | &Self::StatExpr(_)
-            | &Self::StatForIn(_)
-            | &Self::StatFunction(_)
-            | &Self::StatLocal(_)
-            | &Self::StatLocalFunction(_)
-            | &Self::StatBreak
-            | &Self::StatContinue
-            | &Self::StatFor(_)
-            | &Self::StatIf(_)
-            | &Self::StatRepeat(_)
-            | &Self::StatReturn(_)
-            | &Self::StatWhile(_)
-            | &Self::StatTypeAlias(_)
-            | &Self::StatError(_) => true,
-            _ => false,
-        }
+impl AstTypeList {
+    pub fn new(types: Vec<AstTypeId>, tail_type: Option<AstTypePackId>) -> Self {
+        AstTypeList { types, tail_type }
     }
 
-    pub fn is_type(&self) -> bool {
-        match self {
-            &Self::TypeError(_)
-            | &Self::TypeFunction(_)
-            | &Self::TypeIntersection(_)
-            | &Self::TypeReference(_)
-            | &Self::TypeSingletonBool(_)
-            | &Self::TypeSingletonString(_)
-            | &Self::TypeTable(_)
-            | &Self::TypeTypeof(_)
-            | &Self::TypeUnion(_) => true,
-            _ => false,
-        }
+    pub fn get_types(&self) -> &[AstTypeId] {
+        &self.types
     }
 
-    pub fn get_type(&self) -> AstNodePayloadType {
-        match self {
-            &Self::ExprError(_) => AstNodePayloadType::ExprError,
-            &Self::ExprGroup(_) => AstNodePayloadType::ExprGroup,
-            &Self::ExprConstantNil => AstNodePayloadType::ExprConstantNil,
-            &Self::ExprConstantBool(_) => AstNodePayloadType::ExprConstantBool,
-            &Self::ExprConstantNumber(_) => AstNodePayloadType::ExprConstantNumber,
-            &Self::ExprLocal(_) => AstNodePayloadType::ExprLocal,
-            &Self::ExprGlobal(_) => AstNodePayloadType::ExprGlobal,
-            &Self::ExprVarargs => AstNodePayloadType::ExprVarargs,
-            &Self::ExprCall(_) => AstNodePayloadType::ExprCall,
-            &Self::ExprIndexName(_) => AstNodePayloadType::ExprIndexName,
-            &Self::ExprIndexExpr(_) => AstNodePayloadType::ExprIndexExpr,
-            &Self::ExprBinary(_) => AstNodePayloadType::ExprBinary,
-            &Self::ExprFunction(_) => AstNodePayloadType::ExprFunction,
-            &Self::ExprIfElse(_) => AstNodePayloadType::ExprIfElse,
-            &Self::ExprTable(_) => AstNodePayloadType::ExprTable,
-            &Self::ExprTypeAssertion(_) => AstNodePayloadType::ExprTypeAssertion,
-            &Self::ExprUnary(_) => AstNodePayloadType::ExprUnary,
-
-            &Self::StatAssign(_) => AstNodePayloadType::StatAssign,
-            &Self::StatBlock(_) => AstNodePayloadType::StatBlock,
-            &Self::StatCompoundAssign(_) => AstNodePayloadType::StatCompoundAssign,
-            &Self::StatDeclareClass(_) => AstNodePayloadType::StatDeclareClass,
-            &Self::StatDeclareFunction(_) => AstNodePayloadType::StatDeclareFunction,
-            &Self::StatDeclareGlobal(_) => AstNodePayloadType::StatDeclareGlobal,
-            &Self::StatExpr(_) => AstNodePayloadType::StatExpr,
-            &Self::StatForIn(_) => AstNodePayloadType::StatForIn,
-            &Self::StatFunction(_) => AstNodePayloadType::StatFunction,
-            &Self::StatLocal(_) => AstNodePayloadType::StatLocal,
-            &Self::StatLocalFunction(_) => AstNodePayloadType::StatLocalFunction,
-            &Self::StatBreak => AstNodePayloadType::StatBreak,
-            &Self::StatContinue => AstNodePayloadType::StatContinue,
-            &Self::StatFor(_) => AstNodePayloadType::StatFor,
-            &Self::StatIf(_) => AstNodePayloadType::StatIf,
-            &Self::StatRepeat(_) => AstNodePayloadType::StatRepeat,
-            &Self::StatReturn(_) => AstNodePayloadType::StatReturn,
-            &Self::StatWhile(_) => AstNodePayloadType::StatWhile,
-            &Self::StatTypeAlias(_) => AstNodePayloadType::StatTypeAlias,
-            &Self::StatError(_) => AstNodePayloadType::StatError,
-
-            &Self::TypeError(_) => AstNodePayloadType::TypeError,
-            &Self::TypeFunction(_) => AstNodePayloadType::TypeFunction,
-            &Self::TypeIntersection(_) => AstNodePayloadType::TypeIntersection,
-            &Self::TypeReference(_) => AstNodePayloadType::TypeReference,
-            &Self::TypeSingletonBool(_) => AstNodePayloadType::TypeSingletonBool,
-            &Self::TypeSingletonString(_) => AstNodePayloadType::TypeSingletonString,
-            &Self::TypeTable(_) => AstNodePayloadType::TypeTable,
-            &Self::TypeTypeof(_) => AstNodePayloadType::TypeTypeof,
-            &Self::TypeUnion(_) => AstNodePayloadType::TypeUnion,
-
-            _ => AstNodePayloadType::None,
-        }
+    pub fn get_tail_type(&self) -> Option<AstTypePackId> {
+        self.tail_type
     }
 }
 
-pub trait AstVisit {
-    fn visit(&self) -> bool;
+// a type parameter of a type reference can be a type or a type pack
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AstTypeOrPack {
+    Type(AstTypeId),
+    TypePack(AstTypePackId),
 }
@@ -1,7 +1,25 @@
-use super::super::AstExpr;
+use super::super::{super::LexLocation, AstExprId, AstStat, Stat};
 
 #[derive(Clone)]
 pub struct StatAssign {
-    vars: Vec<Box<AstExpr>>,
-    values: Vec<Box<AstExpr>>,
+    vars: Vec<AstExprId>,
+    values: Vec<AstExprId>,
+}
+
+impl StatAssign {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, vars: Vec<AstExprId>, values: Vec<AstExprId>) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::Assign(Box::new(StatAssign { vars, values })),
+        )
+    }
+
+    pub fn get_vars(&self) -> &[AstExprId] {
+        &self.vars
+    }
+
+    pub fn get_values(&self) -> &[AstExprId] {
+        &self.values
+    }
 }
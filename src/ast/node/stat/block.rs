@@ -0,0 +1,5 @@
+use super::super::{super::LexLocation, AstStat, AstStatId, Stat};
+
+pub fn new_stat_block(location: LexLocation, body: Vec<AstStatId>) -> AstStat {
+    AstStat::new(location, Stat::Block(body))
+}
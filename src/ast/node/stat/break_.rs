@@ -0,0 +1,5 @@
+use super::super::{super::LexLocation, AstStat, Stat};
+
+pub fn new_stat_break(location: LexLocation) -> AstStat {
+    AstStat::new(location, Stat::Break)
+}
@@ -1,8 +1,39 @@
-use super::super::{AstExpr, BinaryOperator};
+use super::super::{super::LexLocation, AstExprId, AstStat, BinaryOperator, Stat};
 
 #[derive(Clone)]
 pub struct StatCompoundAssign {
     operator: BinaryOperator,
-    var: Box<AstExpr>,
-    value: Box<AstExpr>,
+    var: AstExprId,
+    value: AstExprId,
+}
+
+impl StatCompoundAssign {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        operator: BinaryOperator,
+        var: AstExprId,
+        value: AstExprId,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::CompoundAssign(Box::new(StatCompoundAssign {
+                operator,
+                var,
+                value,
+            })),
+        )
+    }
+
+    pub fn get_operator(&self) -> BinaryOperator {
+        self.operator
+    }
+
+    pub fn get_var(&self) -> AstExprId {
+        self.var
+    }
+
+    pub fn get_value(&self) -> AstExprId {
+        self.value
+    }
 }
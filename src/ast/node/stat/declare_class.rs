@@ -1,15 +1,75 @@
-use super::super::{AstName, AstType};
+use super::super::{super::LexLocation, AstName, AstStat, AstTypeId, Stat, TableIndexer};
 
 #[derive(Clone)]
 pub struct DeclaredClassProp {
     name: AstName,
-    type_: Box<AstType>,
+    type_: AstTypeId,
     is_method: bool,
 }
 
+impl DeclaredClassProp {
+    pub fn new(name: AstName, type_: AstTypeId, is_method: bool) -> DeclaredClassProp {
+        DeclaredClassProp {
+            name,
+            type_,
+            is_method,
+        }
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_type(&self) -> AstTypeId {
+        self.type_
+    }
+
+    pub fn is_method(&self) -> bool {
+        self.is_method
+    }
+}
+
 #[derive(Clone)]
 pub struct StatDeclareClass {
     name: AstName,
     super_name: Option<AstName>,
     props: Vec<DeclaredClassProp>,
+    indexer: Option<TableIndexer>,
+}
+
+impl StatDeclareClass {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        name: AstName,
+        super_name: Option<AstName>,
+        props: Vec<DeclaredClassProp>,
+        indexer: Option<TableIndexer>,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::DeclareClass(Box::new(StatDeclareClass {
+                name,
+                super_name,
+                props,
+                indexer,
+            })),
+        )
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_super_name(&self) -> Option<AstName> {
+        self.super_name
+    }
+
+    pub fn get_props(&self) -> &[DeclaredClassProp] {
+        &self.props
+    }
+
+    pub fn get_indexer(&self) -> Option<&TableIndexer> {
+        self.indexer.as_ref()
+    }
 }
@@ -1,4 +1,4 @@
-use super::super::{AstArgumentName, AstName, AstTypeList};
+use super::super::{super::LexLocation, AstArgumentName, AstName, AstStat, AstTypeList, Stat};
 
 #[derive(Clone)]
 pub struct StatDeclareFunction {
@@ -9,3 +9,52 @@ pub struct StatDeclareFunction {
     param_names: Vec<Option<AstArgumentName>>,
     return_types: AstTypeList,
 }
+
+impl StatDeclareFunction {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        name: AstName,
+        generics: Vec<AstName>,
+        generic_packs: Vec<AstName>,
+        params: AstTypeList,
+        param_names: Vec<Option<AstArgumentName>>,
+        return_types: AstTypeList,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::DeclareFunction(Box::new(StatDeclareFunction {
+                name,
+                generics,
+                generic_packs,
+                params,
+                param_names,
+                return_types,
+            })),
+        )
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_generics(&self) -> &[AstName] {
+        &self.generics
+    }
+
+    pub fn get_generic_packs(&self) -> &[AstName] {
+        &self.generic_packs
+    }
+
+    pub fn get_params(&self) -> &AstTypeList {
+        &self.params
+    }
+
+    pub fn get_param_names(&self) -> &[Option<AstArgumentName>] {
+        &self.param_names
+    }
+
+    pub fn get_return_types(&self) -> &AstTypeList {
+        &self.return_types
+    }
+}
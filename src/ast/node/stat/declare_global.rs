@@ -1,7 +1,25 @@
-use super::super::{AstName, AstType};
+use super::super::{super::LexLocation, AstName, AstStat, AstTypeId, Stat};
 
 #[derive(Clone)]
 pub struct StatDeclareGlobal {
     name: AstName,
-    type_: Box<AstType>,
+    type_: AstTypeId,
+}
+
+impl StatDeclareGlobal {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, name: AstName, type_: AstTypeId) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::DeclareGlobal(Box::new(StatDeclareGlobal { name, type_ })),
+        )
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_type(&self) -> AstTypeId {
+        self.type_
+    }
 }
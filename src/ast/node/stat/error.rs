@@ -1,8 +1,39 @@
-use super::super::{AstExpr, AstStat};
+use super::super::{super::LexLocation, AstExprId, AstStat, AstStatId, Stat};
 
 #[derive(Clone)]
 pub struct StatError {
-    expressions: Vec<Box<AstExpr>>,
-    statements: Vec<Box<AstStat>>,
-    message_index: u32,
+    expressions: Vec<AstExprId>,
+    statements: Vec<AstStatId>,
+    message_index: usize,
+}
+
+impl StatError {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        expressions: Vec<AstExprId>,
+        statements: Vec<AstStatId>,
+        message_index: usize,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::Error(Box::new(StatError {
+                expressions,
+                statements,
+                message_index,
+            })),
+        )
+    }
+
+    pub fn get_expressions(&self) -> &[AstExprId] {
+        &self.expressions
+    }
+
+    pub fn get_statements(&self) -> &[AstStatId] {
+        &self.statements
+    }
+
+    pub fn get_message_index(&self) -> usize {
+        self.message_index
+    }
 }
@@ -1,6 +1,17 @@
-use super::super::AstExpr;
+use super::super::{super::LexLocation, AstExprId, AstStat, Stat};
 
 #[derive(Clone)]
 pub struct StatExpr {
-    expr: Box<AstExpr>,
+    expr: AstExprId,
+}
+
+impl StatExpr {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, expr: AstExprId) -> AstStat {
+        AstStat::new(location, Stat::Expr(Box::new(StatExpr { expr })))
+    }
+
+    pub fn get_expr(&self) -> AstExprId {
+        self.expr
+    }
 }
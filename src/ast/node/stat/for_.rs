@@ -1,15 +1,77 @@
-use super::super::{super::LexLocation, AstExpr, AstLocal, AstStat};
+use super::super::{super::LexLocation, AstExprId, AstStat, AstStatId, LocalId, Stat};
 
 #[derive(Clone)]
 pub struct StatFor {
-    var: Box<AstLocal>,
-    from: Box<AstExpr>,
-    to: Box<AstExpr>,
-    step: Box<AstExpr>,
-    body: Box<AstStat>,
+    var: LocalId,
+    from: AstExprId,
+    to: AstExprId,
+    step: Option<AstExprId>,
+    body: AstStatId,
 
     has_do: bool,
     do_location: LexLocation,
 
     has_end: bool,
 }
+
+impl StatFor {
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        var: LocalId,
+        from: AstExprId,
+        to: AstExprId,
+        step: Option<AstExprId>,
+        body: AstStatId,
+        has_do: bool,
+        do_location: LexLocation,
+        has_end: bool,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::For(Box::new(StatFor {
+                var,
+                from,
+                to,
+                step,
+                body,
+                has_do,
+                do_location,
+                has_end,
+            })),
+        )
+    }
+
+    pub fn get_var(&self) -> LocalId {
+        self.var
+    }
+
+    pub fn get_from(&self) -> AstExprId {
+        self.from
+    }
+
+    pub fn get_to(&self) -> AstExprId {
+        self.to
+    }
+
+    pub fn get_step(&self) -> Option<AstExprId> {
+        self.step
+    }
+
+    pub fn get_body(&self) -> AstStatId {
+        self.body
+    }
+
+    pub fn has_do(&self) -> bool {
+        self.has_do
+    }
+
+    pub fn get_do_location(&self) -> LexLocation {
+        self.do_location
+    }
+
+    pub fn has_end(&self) -> bool {
+        self.has_end
+    }
+}
@@ -1,10 +1,10 @@
-use super::super::{super::LexLocation, AstExpr, AstLocal, AstStat};
+use super::super::{super::LexLocation, AstExprId, AstStat, AstStatId, LocalId, Stat};
 
 #[derive(Clone)]
 pub struct StatForIn {
-    vars: Vec<Box<AstLocal>>,
-    values: Vec<Box<AstExpr>>,
-    body: Box<AstStat>,
+    vars: Vec<LocalId>,
+    values: Vec<AstExprId>,
+    body: AstStatId,
 
     has_in: bool,
     in_location: LexLocation,
@@ -14,3 +14,65 @@ pub struct StatForIn {
 
     has_end: bool,
 }
+
+impl StatForIn {
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        vars: Vec<LocalId>,
+        values: Vec<AstExprId>,
+        body: AstStatId,
+        has_in: bool,
+        in_location: LexLocation,
+        has_do: bool,
+        do_location: LexLocation,
+        has_end: bool,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::ForIn(Box::new(StatForIn {
+                vars,
+                values,
+                body,
+                has_in,
+                in_location,
+                has_do,
+                do_location,
+                has_end,
+            })),
+        )
+    }
+
+    pub fn get_vars(&self) -> &[LocalId] {
+        &self.vars
+    }
+
+    pub fn get_values(&self) -> &[AstExprId] {
+        &self.values
+    }
+
+    pub fn get_body(&self) -> AstStatId {
+        self.body
+    }
+
+    pub fn has_in(&self) -> bool {
+        self.has_in
+    }
+
+    pub fn get_in_location(&self) -> LexLocation {
+        self.in_location
+    }
+
+    pub fn has_do(&self) -> bool {
+        self.has_do
+    }
+
+    pub fn get_do_location(&self) -> LexLocation {
+        self.do_location
+    }
+
+    pub fn has_end(&self) -> bool {
+        self.has_end
+    }
+}
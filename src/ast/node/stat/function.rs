@@ -1,7 +1,25 @@
-use super::super::{AstExpr, ExprFunction};
+use super::super::{super::LexLocation, AstExprId, AstStat, ExprFunction, Stat};
 
 #[derive(Clone)]
 pub struct StatFunction {
-    name: Box<AstExpr>,
+    name: AstExprId,
     function: Box<ExprFunction>,
 }
+
+impl StatFunction {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, name: AstExprId, function: Box<ExprFunction>) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::Function(Box::new(StatFunction { name, function })),
+        )
+    }
+
+    pub fn get_name(&self) -> AstExprId {
+        self.name
+    }
+
+    pub fn get_function(&self) -> &ExprFunction {
+        &self.function
+    }
+}
@@ -1,14 +1,71 @@
-use super::super::{super::LexLocation, AstExpr, AstStat};
+use super::super::{super::LexLocation, AstExprId, AstStat, AstStatId, Stat};
 
 #[derive(Clone)]
 pub struct StatIf {
-    condition: Box<AstExpr>,
-    then_body: Box<AstStat>,
-    else_body: Box<AstStat>,
+    condition: AstExprId,
+    then_body: AstStatId,
+    else_body: Option<AstStatId>,
 
     has_then: bool,
-    then_locaction: LexLocation,
+    then_location: LexLocation,
 
-    has_else: bool,
-    else_location: LexLocation,
+    else_location: Option<LexLocation>,
+
+    has_end: bool,
+}
+
+impl StatIf {
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        condition: AstExprId,
+        then_body: AstStatId,
+        else_body: Option<AstStatId>,
+        has_then: bool,
+        then_location: LexLocation,
+        else_location: Option<LexLocation>,
+        has_end: bool,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::If(Box::new(StatIf {
+                condition,
+                then_body,
+                else_body,
+                has_then,
+                then_location,
+                else_location,
+                has_end,
+            })),
+        )
+    }
+
+    pub fn get_condition(&self) -> AstExprId {
+        self.condition
+    }
+
+    pub fn get_then_body(&self) -> AstStatId {
+        self.then_body
+    }
+
+    pub fn get_else_body(&self) -> Option<AstStatId> {
+        self.else_body
+    }
+
+    pub fn has_then(&self) -> bool {
+        self.has_then
+    }
+
+    pub fn get_then_location(&self) -> LexLocation {
+        self.then_location
+    }
+
+    pub fn get_else_location(&self) -> Option<LexLocation> {
+        self.else_location
+    }
+
+    pub fn has_end(&self) -> bool {
+        self.has_end
+    }
 }
@@ -1,10 +1,44 @@
-use super::super::{super::LexLocation, AstExpr, AstLocal};
+use super::super::{super::LexLocation, AstExprId, AstStat, LocalId, Stat};
 
 #[derive(Clone)]
 pub struct StatLocal {
-    vars: Vec<Box<AstLocal>>,
-    values: Vec<Box<AstExpr>>,
+    vars: Vec<LocalId>,
+    values: Vec<AstExprId>,
 
-    has_equals_sign: bool,
-    equals_sign_location: LexLocation,
+    equals_sign_location: Option<LexLocation>,
+}
+
+impl StatLocal {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        vars: Vec<LocalId>,
+        values: Vec<AstExprId>,
+        equals_sign_location: Option<LexLocation>,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::Local(Box::new(StatLocal {
+                vars,
+                values,
+                equals_sign_location,
+            })),
+        )
+    }
+
+    pub fn get_vars(&self) -> &[LocalId] {
+        &self.vars
+    }
+
+    pub fn get_values(&self) -> &[AstExprId] {
+        &self.values
+    }
+
+    pub fn has_equals_sign(&self) -> bool {
+        self.equals_sign_location.is_some()
+    }
+
+    pub fn get_equals_sign_location(&self) -> Option<LexLocation> {
+        self.equals_sign_location
+    }
 }
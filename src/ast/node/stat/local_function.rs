@@ -1,7 +1,25 @@
-use super::super::{AstLocal, ExprFunction};
+use super::super::{super::LexLocation, AstStat, ExprFunction, LocalId, Stat};
 
 #[derive(Clone)]
 pub struct StatLocalFunction {
-    name: Box<AstLocal>,
+    name: LocalId,
     function: Box<ExprFunction>,
 }
+
+impl StatLocalFunction {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, name: LocalId, function: Box<ExprFunction>) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::LocalFunction(Box::new(StatLocalFunction { name, function })),
+        )
+    }
+
+    pub fn get_name(&self) -> LocalId {
+        self.name
+    }
+
+    pub fn get_function(&self) -> &ExprFunction {
+        &self.function
+    }
+}
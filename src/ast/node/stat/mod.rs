@@ -1,5 +1,8 @@
 mod assign;
+mod block;
+mod break_;
 mod compound_assign;
+mod continue_;
 mod declare_class;
 mod declare_function;
 mod declare_global;
@@ -17,7 +20,10 @@ mod type_alias;
 mod while_;
 
 pub use assign::StatAssign;
+pub use block::*;
+pub use break_::*;
 pub use compound_assign::StatCompoundAssign;
+pub use continue_::*;
 pub use declare_class::{DeclaredClassProp, StatDeclareClass};
 pub use declare_function::StatDeclareFunction;
 pub use declare_global::StatDeclareGlobal;
@@ -33,3 +39,56 @@ pub use repeat_::StatRepeat;
 pub use return_::StatReturn;
 pub use type_alias::StatTypeAlias;
 pub use while_::StatWhile;
+
+use super::{AstNodePayloadType, AstStatId};
+
+#[derive(Clone)]
+pub enum Stat {
+    Assign(Box<StatAssign>),
+    Block(Vec<AstStatId>),
+    CompoundAssign(Box<StatCompoundAssign>),
+    DeclareClass(Box<StatDeclareClass>),
+    DeclareFunction(Box<StatDeclareFunction>),
+    DeclareGlobal(Box<StatDeclareGlobal>),
+    Expr(Box<StatExpr>),
+    ForIn(Box<StatForIn>),
+    Function(Box<StatFunction>),
+    Local(Box<StatLocal>),
+    LocalFunction(Box<StatLocalFunction>),
+    Break,
+    Continue,
+    For(Box<StatFor>),
+    If(Box<StatIf>),
+    Repeat(Box<StatRepeat>),
+    Return(Box<StatReturn>),
+    While(Box<StatWhile>),
+    TypeAlias(Box<StatTypeAlias>),
+    Error(Box<StatError>),
+}
+
+impl Stat {
+    pub fn get_type(&self) -> AstNodePayloadType {
+        match self {
+            Self::Assign(_) => AstNodePayloadType::StatAssign,
+            Self::Block(_) => AstNodePayloadType::StatBlock,
+            Self::CompoundAssign(_) => AstNodePayloadType::StatCompoundAssign,
+            Self::DeclareClass(_) => AstNodePayloadType::StatDeclareClass,
+            Self::DeclareFunction(_) => AstNodePayloadType::StatDeclareFunction,
+            Self::DeclareGlobal(_) => AstNodePayloadType::StatDeclareGlobal,
+            Self::Expr(_) => AstNodePayloadType::StatExpr,
+            Self::ForIn(_) => AstNodePayloadType::StatForIn,
+            Self::Function(_) => AstNodePayloadType::StatFunction,
+            Self::Local(_) => AstNodePayloadType::StatLocal,
+            Self::LocalFunction(_) => AstNodePayloadType::StatLocalFunction,
+            Self::Break => AstNodePayloadType::StatBreak,
+            Self::Continue => AstNodePayloadType::StatContinue,
+            Self::For(_) => AstNodePayloadType::StatFor,
+            Self::If(_) => AstNodePayloadType::StatIf,
+            Self::Repeat(_) => AstNodePayloadType::StatRepeat,
+            Self::Return(_) => AstNodePayloadType::StatReturn,
+            Self::While(_) => AstNodePayloadType::StatWhile,
+            Self::TypeAlias(_) => AstNodePayloadType::StatTypeAlias,
+            Self::Error(_) => AstNodePayloadType::StatError,
+        }
+    }
+}
@@ -1,9 +1,40 @@
-use super::super::{AstExpr, AstStat};
+use super::super::{super::LexLocation, AstExprId, AstStat, AstStatId, Stat};
 
 #[derive(Clone)]
 pub struct StatRepeat {
-    condition: Box<AstExpr>,
-    body: Box<AstStat>,
+    condition: AstExprId,
+    body: AstStatId,
 
     has_until: bool,
 }
+
+impl StatRepeat {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        condition: AstExprId,
+        body: AstStatId,
+        has_until: bool,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::Repeat(Box::new(StatRepeat {
+                condition,
+                body,
+                has_until,
+            })),
+        )
+    }
+
+    pub fn get_condition(&self) -> AstExprId {
+        self.condition
+    }
+
+    pub fn get_body(&self) -> AstStatId {
+        self.body
+    }
+
+    pub fn has_until(&self) -> bool {
+        self.has_until
+    }
+}
@@ -1,6 +1,17 @@
-use super::super::AstExpr;
+use super::super::{super::LexLocation, AstExprId, AstStat, Stat};
 
 #[derive(Clone)]
 pub struct StatReturn {
-    list: Vec<Box<AstExpr>>,
+    list: Vec<AstExprId>,
+}
+
+impl StatReturn {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(location: LexLocation, list: Vec<AstExprId>) -> AstStat {
+        AstStat::new(location, Stat::Return(Box::new(StatReturn { list })))
+    }
+
+    pub fn get_list(&self) -> &[AstExprId] {
+        &self.list
+    }
 }
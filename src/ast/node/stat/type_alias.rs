@@ -1,10 +1,55 @@
-use super::super::{AstName, AstType};
+use super::super::{
+    super::LexLocation, AstGenericType, AstGenericTypePack, AstName, AstStat, AstTypeId, Stat,
+};
 
 #[derive(Clone)]
 pub struct StatTypeAlias {
     name: AstName,
-    generics: Vec<AstName>,
-    generic_packs: Vec<AstName>,
-    type_: Box<AstType>,
+    generics: Vec<AstGenericType>,
+    generic_packs: Vec<AstGenericTypePack>,
+    type_: AstTypeId,
     exported: bool,
 }
+
+impl StatTypeAlias {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        name: AstName,
+        generics: Vec<AstGenericType>,
+        generic_packs: Vec<AstGenericTypePack>,
+        type_: AstTypeId,
+        exported: bool,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::TypeAlias(Box::new(StatTypeAlias {
+                name,
+                generics,
+                generic_packs,
+                type_,
+                exported,
+            })),
+        )
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_generics(&self) -> &[AstGenericType] {
+        &self.generics
+    }
+
+    pub fn get_generic_packs(&self) -> &[AstGenericTypePack] {
+        &self.generic_packs
+    }
+
+    pub fn get_type(&self) -> AstTypeId {
+        self.type_
+    }
+
+    pub fn is_exported(&self) -> bool {
+        self.exported
+    }
+}
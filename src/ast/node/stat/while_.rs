@@ -1,12 +1,55 @@
-use super::super::{super::LexLocation, AstExpr, AstStat};
+use super::super::{super::LexLocation, AstExprId, AstStat, AstStatId, Stat};
 
 #[derive(Clone)]
 pub struct StatWhile {
-    condition: Box<AstExpr>,
-    body: Box<AstStat>,
+    condition: AstExprId,
+    body: AstStatId,
 
     has_do: bool,
     do_location: LexLocation,
 
     has_end: bool,
 }
+
+impl StatWhile {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        condition: AstExprId,
+        body: AstStatId,
+        has_do: bool,
+        do_location: LexLocation,
+        has_end: bool,
+    ) -> AstStat {
+        AstStat::new(
+            location,
+            Stat::While(Box::new(StatWhile {
+                condition,
+                body,
+                has_do,
+                do_location,
+                has_end,
+            })),
+        )
+    }
+
+    pub fn get_condition(&self) -> AstExprId {
+        self.condition
+    }
+
+    pub fn get_body(&self) -> AstStatId {
+        self.body
+    }
+
+    pub fn has_do(&self) -> bool {
+        self.has_do
+    }
+
+    pub fn get_do_location(&self) -> LexLocation {
+        self.do_location
+    }
+
+    pub fn has_end(&self) -> bool {
+        self.has_end
+    }
+}
@@ -1,26 +1,39 @@
-use super::super::{AstNodePayload, AstType, LexLocation};
+use super::super::{AstType, AstTypeId, LexLocation, Type};
 
 #[derive(Clone)]
 pub struct TypeError {
-    types: Vec<Box<AstType>>,
+    types: Vec<AstTypeId>,
     is_missing: bool,
     message_index: usize,
 }
 
 impl TypeError {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         location: LexLocation,
-        types: Vec<Box<AstType>>,
+        types: Vec<AstTypeId>,
         is_missing: bool,
         message_index: usize,
-    ) -> Box<AstType> {
+    ) -> AstType {
         AstType::new(
             location,
-            AstNodePayload::TypeError(Box::new(TypeError {
+            Type::Error(Box::new(TypeError {
                 types,
                 is_missing,
                 message_index,
             })),
         )
     }
+
+    pub fn get_types(&self) -> &[AstTypeId] {
+        &self.types
+    }
+
+    pub fn is_missing(&self) -> bool {
+        self.is_missing
+    }
+
+    pub fn get_message_index(&self) -> usize {
+        self.message_index
+    }
 }
@@ -1,4 +1,4 @@
-use super::super::{AstArgumentName, AstName, AstTypeList};
+use super::super::{super::LexLocation, AstArgumentName, AstName, AstType, AstTypeList, Type};
 
 #[derive(Clone)]
 pub struct TypeFunction {
@@ -8,3 +8,46 @@ pub struct TypeFunction {
     arg_names: Vec<Option<AstArgumentName>>,
     return_types: AstTypeList,
 }
+
+impl TypeFunction {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        location: LexLocation,
+        generics: Vec<AstName>,
+        generic_packs: Vec<AstName>,
+        arg_types: AstTypeList,
+        arg_names: Vec<Option<AstArgumentName>>,
+        return_types: AstTypeList,
+    ) -> AstType {
+        AstType::new(
+            location,
+            Type::Function(Box::new(TypeFunction {
+                generics,
+                generic_packs,
+                arg_types,
+                arg_names,
+                return_types,
+            })),
+        )
+    }
+
+    pub fn get_generics(&self) -> &[AstName] {
+        &self.generics
+    }
+
+    pub fn get_generic_packs(&self) -> &[AstName] {
+        &self.generic_packs
+    }
+
+    pub fn get_arg_types(&self) -> &AstTypeList {
+        &self.arg_types
+    }
+
+    pub fn get_arg_names(&self) -> &[Option<AstArgumentName>] {
+        &self.arg_names
+    }
+
+    pub fn get_return_types(&self) -> &AstTypeList {
+        &self.return_types
+    }
+}
@@ -18,36 +18,48 @@ pub use table::TypeTable;
 pub use typeof_::*;
 pub use union::*;
 
-use super::{super::LexLocation, AstName, AstType};
+use super::{super::LexLocation, AstExprId, AstName, AstNodePayloadType, AstTypeId};
 
 #[derive(Clone)]
 pub struct TableProp {
     name: AstName,
     location: LexLocation,
-    type_: Box<AstType>,
+    type_: AstTypeId,
 }
 
 impl TableProp {
-    pub fn new(name: AstName, location: LexLocation, type_: Box<AstType>) -> TableProp {
+    pub fn new(name: AstName, location: LexLocation, type_: AstTypeId) -> TableProp {
         TableProp {
             name,
             location,
             type_,
         }
     }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+
+    pub fn get_type(&self) -> AstTypeId {
+        self.type_
+    }
 }
 
 #[derive(Clone)]
 pub struct TableIndexer {
-    index_type: Box<AstType>,
-    result_type: Box<AstType>,
+    index_type: AstTypeId,
+    result_type: AstTypeId,
     location: LexLocation,
 }
 
 impl TableIndexer {
     pub fn new(
-        index_type: Box<AstType>,
-        result_type: Box<AstType>,
+        index_type: AstTypeId,
+        result_type: AstTypeId,
         location: LexLocation,
     ) -> TableIndexer {
         TableIndexer {
@@ -56,4 +68,45 @@ impl TableIndexer {
             location,
         }
     }
+
+    pub fn get_index_type(&self) -> AstTypeId {
+        self.index_type
+    }
+
+    pub fn get_result_type(&self) -> AstTypeId {
+        self.result_type
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+}
+
+#[derive(Clone)]
+pub enum Type {
+    Error(Box<TypeError>),
+    Function(Box<TypeFunction>),
+    Intersection(Vec<AstTypeId>),
+    Reference(Box<TypeReference>),
+    SingletonBool(bool),
+    SingletonString(Vec<u8>),
+    Table(Box<TypeTable>),
+    Typeof(AstExprId),
+    Union(Vec<AstTypeId>),
+}
+
+impl Type {
+    pub fn get_type(&self) -> AstNodePayloadType {
+        match self {
+            Self::Error(_) => AstNodePayloadType::TypeError,
+            Self::Function(_) => AstNodePayloadType::TypeFunction,
+            Self::Intersection(_) => AstNodePayloadType::TypeIntersection,
+            Self::Reference(_) => AstNodePayloadType::TypeReference,
+            Self::SingletonBool(_) => AstNodePayloadType::TypeSingletonBool,
+            Self::SingletonString(_) => AstNodePayloadType::TypeSingletonString,
+            Self::Table(_) => AstNodePayloadType::TypeTable,
+            Self::Typeof(_) => AstNodePayloadType::TypeTypeof,
+            Self::Union(_) => AstNodePayloadType::TypeUnion,
+        }
+    }
 }
@@ -1,13 +1,30 @@
-use super::super::{AstName, AstNodePayload, AstType, AstTypeList, LexLocation};
+use super::super::{AstName, AstNodePayloadType, AstTypeId, AstTypeList, AstTypePack, LexLocation};
 
-pub fn new_type_pack_generic(location: LexLocation, name: AstName) -> Box<AstType> {
-    AstType::new(location, AstNodePayload::TypePackGeneric(name))
+pub fn new_type_pack_generic(location: LexLocation, name: AstName) -> AstTypePack {
+    AstTypePack::new(location, TypePack::Generic(name))
 }
 
-pub fn new_type_pack_explicit(location: LexLocation, type_list: Box<AstTypeList>) -> Box<AstType> {
-    AstType::new(location, AstNodePayload::TypePackExplicit(type_list))
+pub fn new_type_pack_explicit(location: LexLocation, type_list: Box<AstTypeList>) -> AstTypePack {
+    AstTypePack::new(location, TypePack::Explicit(type_list))
 }
 
-pub fn new_type_pack_variadic(location: LexLocation, type_: Box<AstType>) -> Box<AstType> {
-    AstType::new(location, AstNodePayload::TypePackVariadic(type_))
+pub fn new_type_pack_variadic(location: LexLocation, type_: AstTypeId) -> AstTypePack {
+    AstTypePack::new(location, TypePack::Variadic(type_))
+}
+
+#[derive(Clone)]
+pub enum TypePack {
+    Explicit(Box<AstTypeList>),
+    Generic(AstName),
+    Variadic(AstTypeId),
+}
+
+impl TypePack {
+    pub fn get_type(&self) -> AstNodePayloadType {
+        match self {
+            Self::Explicit(_) => AstNodePayloadType::TypePackExplicit,
+            Self::Generic(_) => AstNodePayloadType::TypePackGeneric,
+            Self::Variadic(_) => AstNodePayloadType::TypePackVariadic,
+        }
+    }
 }
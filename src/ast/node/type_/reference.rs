@@ -1,26 +1,39 @@
-use super::super::{AstName, AstNodePayload, AstType, LexLocation};
+use super::super::{AstName, AstType, AstTypeOrPack, LexLocation, Type};
 
 #[derive(Clone)]
 pub struct TypeReference {
     prefix: Option<AstName>,
     name: AstName,
-    parameters: Option<Vec<Box<AstType>>>,
+    parameters: Option<Vec<AstTypeOrPack>>,
 }
 
 impl TypeReference {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         location: LexLocation,
         prefix: Option<AstName>,
         name: AstName,
-        parameters: Option<Vec<Box<AstType>>>,
-    ) -> Box<AstType> {
+        parameters: Option<Vec<AstTypeOrPack>>,
+    ) -> AstType {
         AstType::new(
             location,
-            AstNodePayload::TypeReference(Box::new(TypeReference {
+            Type::Reference(Box::new(TypeReference {
                 prefix,
                 name,
                 parameters,
             })),
         )
     }
+
+    pub fn get_prefix(&self) -> Option<AstName> {
+        self.prefix
+    }
+
+    pub fn get_name(&self) -> AstName {
+        self.name
+    }
+
+    pub fn get_parameters(&self) -> Option<&[AstTypeOrPack]> {
+        self.parameters.as_deref()
+    }
 }
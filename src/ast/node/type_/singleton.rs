@@ -1,9 +1,9 @@
-use super::super::{AstNodePayload, AstType, LexLocation};
+use super::super::{AstType, LexLocation, Type};
 
-pub fn new_type_singleton_bool(location: LexLocation, value: bool) -> Box<AstType> {
-    AstType::new(location, AstNodePayload::TypeSingletonBool(value))
+pub fn new_type_singleton_bool(location: LexLocation, value: bool) -> AstType {
+    AstType::new(location, Type::SingletonBool(value))
 }
 
-pub fn new_type_singleton_string(location: LexLocation, value: String) -> Box<AstType> {
-    AstType::new(location, AstNodePayload::TypeSingletonString(value))
+pub fn new_type_singleton_string(location: LexLocation, value: Vec<u8>) -> AstType {
+    AstType::new(location, Type::SingletonString(value))
 }
@@ -1,5 +1,5 @@
 use super::{
-    super::{AstNodePayload, AstType, LexLocation},
+    super::{AstType, LexLocation, Type},
     TableIndexer, TableProp,
 };
 
@@ -10,14 +10,23 @@ pub struct TypeTable {
 }
 
 impl TypeTable {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         location: LexLocation,
         props: Vec<TableProp>,
         indexer: Option<TableIndexer>,
-    ) -> Box<AstType> {
+    ) -> AstType {
         AstType::new(
             location,
-            AstNodePayload::TypeTable(Box::new(TypeTable { props, indexer })),
+            Type::Table(Box::new(TypeTable { props, indexer })),
         )
     }
+
+    pub fn get_props(&self) -> &[TableProp] {
+        &self.props
+    }
+
+    pub fn get_indexer(&self) -> Option<&TableIndexer> {
+        self.indexer.as_ref()
+    }
 }
@@ -1,5 +1,5 @@
-use super::super::{AstExpr, AstNodePayload, AstType, LexLocation};
+use super::super::{AstExprId, AstType, LexLocation, Type};
 
-pub fn new_type_typeof(location: LexLocation, expr: Box<AstExpr>) -> Box<AstType> {
-    AstType::new(location, AstNodePayload::TypeTypeof(expr))
+pub fn new_type_typeof(location: LexLocation, expr: AstExprId) -> AstType {
+    AstType::new(location, Type::Typeof(expr))
 }
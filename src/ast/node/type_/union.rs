@@ -1,5 +1,5 @@
-use super::super::{AstNodePayload, AstType, LexLocation};
+use super::super::{AstType, AstTypeId, LexLocation, Type};
 
-pub fn new_type_union(location: LexLocation, type_: Vec<Box<AstType>>) -> Box<AstType> {
-    AstType::new(location, AstNodePayload::TypeUnion(type_))
+pub fn new_type_union(location: LexLocation, type_: Vec<AstTypeId>) -> AstType {
+    AstType::new(location, Type::Union(type_))
 }
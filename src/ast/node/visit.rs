@@ -0,0 +1,1781 @@
+use super::{
+    AstArena, AstExprId, AstNodeId, AstStatId, AstTypeId, AstTypeList, AstTypeOrPack,
+    AstTypePackId, Expr, ExprFunction, LocalId, Stat, TableIndexer, TableProp, Type, TypePack,
+};
+
+// callbacks of a traversal, every kind of node has its own visit and leave callbacks which default
+// to the callbacks of its category. visit callbacks run before the children of a node and return
+// whether the children are visited, leave callbacks run after the children even when they were
+// skipped
+pub trait Visitor {
+    fn visit_expr(&mut self, _arena: &AstArena, _id: AstExprId) -> bool {
+        true
+    }
+
+    fn leave_expr(&mut self, _arena: &AstArena, _id: AstExprId) {}
+
+    fn visit_expr_group(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_group(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_constant_nil(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_constant_nil(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_constant_bool(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_constant_bool(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_constant_number(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_constant_number(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_constant_string(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_constant_string(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_local(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_local(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_global(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_global(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_varargs(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_varargs(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_call(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_call(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_index_name(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_index_name(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_index_expr(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_index_expr(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_binary(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_binary(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_function(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_function(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_if_else(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_if_else(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_interp_string(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_interp_string(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_table(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_table(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_type_assertion(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_type_assertion(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_unary(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_unary(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_error(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_error(&mut self, arena: &AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_stat(&mut self, _arena: &AstArena, _id: AstStatId) -> bool {
+        true
+    }
+
+    fn leave_stat(&mut self, _arena: &AstArena, _id: AstStatId) {}
+
+    fn visit_stat_assign(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_assign(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_block(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_block(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_compound_assign(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_compound_assign(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_declare_class(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_declare_class(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_declare_function(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_declare_function(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_declare_global(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_declare_global(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_expr(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_expr(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_for_in(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_for_in(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_function(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_function(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_local(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_local(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_local_function(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_local_function(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_break(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_break(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_continue(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_continue(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_for(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_for(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_if(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_if(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_repeat(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_repeat(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_return(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_return(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_while(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_while(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_type_alias(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_type_alias(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_error(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_error(&mut self, arena: &AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_type(&mut self, _arena: &AstArena, _id: AstTypeId) -> bool {
+        true
+    }
+
+    fn leave_type(&mut self, _arena: &AstArena, _id: AstTypeId) {}
+
+    fn visit_type_error(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_error(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_function(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_function(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_intersection(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_intersection(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_reference(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_reference(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_singleton_bool(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_singleton_bool(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_singleton_string(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_singleton_string(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_table(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_table(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_typeof(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_typeof(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_union(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_union(&mut self, arena: &AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_pack(&mut self, _arena: &AstArena, _id: AstTypePackId) -> bool {
+        true
+    }
+
+    fn leave_type_pack(&mut self, _arena: &AstArena, _id: AstTypePackId) {}
+
+    fn visit_type_pack_explicit(&mut self, arena: &AstArena, id: AstTypePackId) -> bool {
+        self.visit_type_pack(arena, id)
+    }
+
+    fn leave_type_pack_explicit(&mut self, arena: &AstArena, id: AstTypePackId) {
+        self.leave_type_pack(arena, id)
+    }
+
+    fn visit_type_pack_generic(&mut self, arena: &AstArena, id: AstTypePackId) -> bool {
+        self.visit_type_pack(arena, id)
+    }
+
+    fn leave_type_pack_generic(&mut self, arena: &AstArena, id: AstTypePackId) {
+        self.leave_type_pack(arena, id)
+    }
+
+    fn visit_type_pack_variadic(&mut self, arena: &AstArena, id: AstTypePackId) -> bool {
+        self.visit_type_pack(arena, id)
+    }
+
+    fn leave_type_pack_variadic(&mut self, arena: &AstArena, id: AstTypePackId) {
+        self.leave_type_pack(arena, id)
+    }
+
+    fn visit_local(&mut self, _arena: &AstArena, _id: LocalId) -> bool {
+        true
+    }
+
+    fn leave_local(&mut self, _arena: &AstArena, _id: LocalId) {}
+}
+
+// same as Visitor but with mutable access to the arena, nodes can be rewritten in place or
+// replaced by newly allocated ones, the leave callback is picked by the kind the node has after
+// its children were walked
+pub trait VisitorMut {
+    fn visit_expr(&mut self, _arena: &mut AstArena, _id: AstExprId) -> bool {
+        true
+    }
+
+    fn leave_expr(&mut self, _arena: &mut AstArena, _id: AstExprId) {}
+
+    fn visit_expr_group(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_group(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_constant_nil(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_constant_nil(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_constant_bool(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_constant_bool(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_constant_number(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_constant_number(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_constant_string(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_constant_string(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_local(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_local(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_global(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_global(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_varargs(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_varargs(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_call(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_call(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_index_name(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_index_name(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_index_expr(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_index_expr(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_binary(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_binary(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_function(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_function(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_if_else(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_if_else(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_interp_string(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_interp_string(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_table(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_table(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_type_assertion(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_type_assertion(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_unary(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_unary(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_expr_error(&mut self, arena: &mut AstArena, id: AstExprId) -> bool {
+        self.visit_expr(arena, id)
+    }
+
+    fn leave_expr_error(&mut self, arena: &mut AstArena, id: AstExprId) {
+        self.leave_expr(arena, id)
+    }
+
+    fn visit_stat(&mut self, _arena: &mut AstArena, _id: AstStatId) -> bool {
+        true
+    }
+
+    fn leave_stat(&mut self, _arena: &mut AstArena, _id: AstStatId) {}
+
+    fn visit_stat_assign(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_assign(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_block(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_block(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_compound_assign(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_compound_assign(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_declare_class(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_declare_class(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_declare_function(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_declare_function(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_declare_global(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_declare_global(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_expr(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_expr(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_for_in(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_for_in(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_function(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_function(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_local(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_local(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_local_function(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_local_function(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_break(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_break(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_continue(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_continue(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_for(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_for(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_if(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_if(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_repeat(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_repeat(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_return(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_return(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_while(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_while(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_type_alias(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_type_alias(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_stat_error(&mut self, arena: &mut AstArena, id: AstStatId) -> bool {
+        self.visit_stat(arena, id)
+    }
+
+    fn leave_stat_error(&mut self, arena: &mut AstArena, id: AstStatId) {
+        self.leave_stat(arena, id)
+    }
+
+    fn visit_type(&mut self, _arena: &mut AstArena, _id: AstTypeId) -> bool {
+        true
+    }
+
+    fn leave_type(&mut self, _arena: &mut AstArena, _id: AstTypeId) {}
+
+    fn visit_type_error(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_error(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_function(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_function(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_intersection(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_intersection(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_reference(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_reference(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_singleton_bool(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_singleton_bool(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_singleton_string(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_singleton_string(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_table(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_table(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_typeof(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_typeof(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_union(&mut self, arena: &mut AstArena, id: AstTypeId) -> bool {
+        self.visit_type(arena, id)
+    }
+
+    fn leave_type_union(&mut self, arena: &mut AstArena, id: AstTypeId) {
+        self.leave_type(arena, id)
+    }
+
+    fn visit_type_pack(&mut self, _arena: &mut AstArena, _id: AstTypePackId) -> bool {
+        true
+    }
+
+    fn leave_type_pack(&mut self, _arena: &mut AstArena, _id: AstTypePackId) {}
+
+    fn visit_type_pack_explicit(&mut self, arena: &mut AstArena, id: AstTypePackId) -> bool {
+        self.visit_type_pack(arena, id)
+    }
+
+    fn leave_type_pack_explicit(&mut self, arena: &mut AstArena, id: AstTypePackId) {
+        self.leave_type_pack(arena, id)
+    }
+
+    fn visit_type_pack_generic(&mut self, arena: &mut AstArena, id: AstTypePackId) -> bool {
+        self.visit_type_pack(arena, id)
+    }
+
+    fn leave_type_pack_generic(&mut self, arena: &mut AstArena, id: AstTypePackId) {
+        self.leave_type_pack(arena, id)
+    }
+
+    fn visit_type_pack_variadic(&mut self, arena: &mut AstArena, id: AstTypePackId) -> bool {
+        self.visit_type_pack(arena, id)
+    }
+
+    fn leave_type_pack_variadic(&mut self, arena: &mut AstArena, id: AstTypePackId) {
+        self.leave_type_pack(arena, id)
+    }
+
+    fn visit_local(&mut self, _arena: &mut AstArena, _id: LocalId) -> bool {
+        true
+    }
+
+    fn leave_local(&mut self, _arena: &mut AstArena, _id: LocalId) {}
+}
+
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstExprId) {
+    if visit_expr_kind(visitor, arena, id) {
+        walk_children(visitor, arena, AstNodeId::Expr(id));
+    }
+    leave_expr_kind(visitor, arena, id);
+}
+
+pub fn walk_stat<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstStatId) {
+    if visit_stat_kind(visitor, arena, id) {
+        walk_children(visitor, arena, AstNodeId::Stat(id));
+    }
+    leave_stat_kind(visitor, arena, id);
+}
+
+pub fn walk_type<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstTypeId) {
+    if visit_type_kind(visitor, arena, id) {
+        walk_children(visitor, arena, AstNodeId::Type(id));
+    }
+    leave_type_kind(visitor, arena, id);
+}
+
+pub fn walk_type_pack<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstTypePackId) {
+    if visit_type_pack_kind(visitor, arena, id) {
+        walk_children(visitor, arena, AstNodeId::TypePack(id));
+    }
+    leave_type_pack_kind(visitor, arena, id);
+}
+
+pub fn walk_local<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: LocalId) {
+    if visitor.visit_local(arena, id) {
+        walk_children(visitor, arena, AstNodeId::Local(id));
+    }
+    visitor.leave_local(arena, id);
+}
+
+pub fn walk_node<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, node: AstNodeId) {
+    match node {
+        AstNodeId::Expr(id) => walk_expr(visitor, arena, id),
+        AstNodeId::Stat(id) => walk_stat(visitor, arena, id),
+        AstNodeId::Type(id) => walk_type(visitor, arena, id),
+        AstNodeId::TypePack(id) => walk_type_pack(visitor, arena, id),
+        AstNodeId::Local(id) => walk_local(visitor, arena, id),
+    }
+}
+
+fn walk_children<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, node: AstNodeId) {
+    for child in get_children(arena, node) {
+        walk_node(visitor, arena, child);
+    }
+}
+
+pub fn walk_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, arena: &mut AstArena, id: AstExprId) {
+    if visit_expr_kind_mut(visitor, arena, id) {
+        walk_children_mut(visitor, arena, AstNodeId::Expr(id));
+    }
+    leave_expr_kind_mut(visitor, arena, id);
+}
+
+pub fn walk_stat_mut<V: VisitorMut + ?Sized>(visitor: &mut V, arena: &mut AstArena, id: AstStatId) {
+    if visit_stat_kind_mut(visitor, arena, id) {
+        walk_children_mut(visitor, arena, AstNodeId::Stat(id));
+    }
+    leave_stat_kind_mut(visitor, arena, id);
+}
+
+pub fn walk_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, arena: &mut AstArena, id: AstTypeId) {
+    if visit_type_kind_mut(visitor, arena, id) {
+        walk_children_mut(visitor, arena, AstNodeId::Type(id));
+    }
+    leave_type_kind_mut(visitor, arena, id);
+}
+
+pub fn walk_type_pack_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstTypePackId,
+) {
+    if visit_type_pack_kind_mut(visitor, arena, id) {
+        walk_children_mut(visitor, arena, AstNodeId::TypePack(id));
+    }
+    leave_type_pack_kind_mut(visitor, arena, id);
+}
+
+pub fn walk_local_mut<V: VisitorMut + ?Sized>(visitor: &mut V, arena: &mut AstArena, id: LocalId) {
+    if visitor.visit_local(arena, id) {
+        walk_children_mut(visitor, arena, AstNodeId::Local(id));
+    }
+    visitor.leave_local(arena, id);
+}
+
+pub fn walk_node_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    node: AstNodeId,
+) {
+    match node {
+        AstNodeId::Expr(id) => walk_expr_mut(visitor, arena, id),
+        AstNodeId::Stat(id) => walk_stat_mut(visitor, arena, id),
+        AstNodeId::Type(id) => walk_type_mut(visitor, arena, id),
+        AstNodeId::TypePack(id) => walk_type_pack_mut(visitor, arena, id),
+        AstNodeId::Local(id) => walk_local_mut(visitor, arena, id),
+    }
+}
+
+// the children are collected after the node was visited, so they reflect its rewrites
+fn walk_children_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    node: AstNodeId,
+) {
+    for child in get_children(arena, node) {
+        walk_node_mut(visitor, arena, child);
+    }
+}
+
+fn visit_expr_kind<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstExprId) -> bool {
+    match arena[id].get_payload() {
+        Expr::Group(_) => visitor.visit_expr_group(arena, id),
+        Expr::ConstantNil => visitor.visit_expr_constant_nil(arena, id),
+        Expr::ConstantBool(_) => visitor.visit_expr_constant_bool(arena, id),
+        Expr::ConstantNumber(_) => visitor.visit_expr_constant_number(arena, id),
+        Expr::ConstantString(_) => visitor.visit_expr_constant_string(arena, id),
+        Expr::Local(_) => visitor.visit_expr_local(arena, id),
+        Expr::Global(_) => visitor.visit_expr_global(arena, id),
+        Expr::Varargs => visitor.visit_expr_varargs(arena, id),
+        Expr::Call(_) => visitor.visit_expr_call(arena, id),
+        Expr::IndexName(_) => visitor.visit_expr_index_name(arena, id),
+        Expr::IndexExpr(_) => visitor.visit_expr_index_expr(arena, id),
+        Expr::Binary(_) => visitor.visit_expr_binary(arena, id),
+        Expr::Function(_) => visitor.visit_expr_function(arena, id),
+        Expr::IfElse(_) => visitor.visit_expr_if_else(arena, id),
+        Expr::InterpString(_) => visitor.visit_expr_interp_string(arena, id),
+        Expr::Table(_) => visitor.visit_expr_table(arena, id),
+        Expr::TypeAssertion(_) => visitor.visit_expr_type_assertion(arena, id),
+        Expr::Unary(_) => visitor.visit_expr_unary(arena, id),
+        Expr::Error(_) => visitor.visit_expr_error(arena, id),
+    }
+}
+
+fn leave_expr_kind<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstExprId) {
+    match arena[id].get_payload() {
+        Expr::Group(_) => visitor.leave_expr_group(arena, id),
+        Expr::ConstantNil => visitor.leave_expr_constant_nil(arena, id),
+        Expr::ConstantBool(_) => visitor.leave_expr_constant_bool(arena, id),
+        Expr::ConstantNumber(_) => visitor.leave_expr_constant_number(arena, id),
+        Expr::ConstantString(_) => visitor.leave_expr_constant_string(arena, id),
+        Expr::Local(_) => visitor.leave_expr_local(arena, id),
+        Expr::Global(_) => visitor.leave_expr_global(arena, id),
+        Expr::Varargs => visitor.leave_expr_varargs(arena, id),
+        Expr::Call(_) => visitor.leave_expr_call(arena, id),
+        Expr::IndexName(_) => visitor.leave_expr_index_name(arena, id),
+        Expr::IndexExpr(_) => visitor.leave_expr_index_expr(arena, id),
+        Expr::Binary(_) => visitor.leave_expr_binary(arena, id),
+        Expr::Function(_) => visitor.leave_expr_function(arena, id),
+        Expr::IfElse(_) => visitor.leave_expr_if_else(arena, id),
+        Expr::InterpString(_) => visitor.leave_expr_interp_string(arena, id),
+        Expr::Table(_) => visitor.leave_expr_table(arena, id),
+        Expr::TypeAssertion(_) => visitor.leave_expr_type_assertion(arena, id),
+        Expr::Unary(_) => visitor.leave_expr_unary(arena, id),
+        Expr::Error(_) => visitor.leave_expr_error(arena, id),
+    }
+}
+
+fn visit_stat_kind<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstStatId) -> bool {
+    match arena[id].get_payload() {
+        Stat::Assign(_) => visitor.visit_stat_assign(arena, id),
+        Stat::Block(_) => visitor.visit_stat_block(arena, id),
+        Stat::CompoundAssign(_) => visitor.visit_stat_compound_assign(arena, id),
+        Stat::DeclareClass(_) => visitor.visit_stat_declare_class(arena, id),
+        Stat::DeclareFunction(_) => visitor.visit_stat_declare_function(arena, id),
+        Stat::DeclareGlobal(_) => visitor.visit_stat_declare_global(arena, id),
+        Stat::Expr(_) => visitor.visit_stat_expr(arena, id),
+        Stat::ForIn(_) => visitor.visit_stat_for_in(arena, id),
+        Stat::Function(_) => visitor.visit_stat_function(arena, id),
+        Stat::Local(_) => visitor.visit_stat_local(arena, id),
+        Stat::LocalFunction(_) => visitor.visit_stat_local_function(arena, id),
+        Stat::Break => visitor.visit_stat_break(arena, id),
+        Stat::Continue => visitor.visit_stat_continue(arena, id),
+        Stat::For(_) => visitor.visit_stat_for(arena, id),
+        Stat::If(_) => visitor.visit_stat_if(arena, id),
+        Stat::Repeat(_) => visitor.visit_stat_repeat(arena, id),
+        Stat::Return(_) => visitor.visit_stat_return(arena, id),
+        Stat::While(_) => visitor.visit_stat_while(arena, id),
+        Stat::TypeAlias(_) => visitor.visit_stat_type_alias(arena, id),
+        Stat::Error(_) => visitor.visit_stat_error(arena, id),
+    }
+}
+
+fn leave_stat_kind<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstStatId) {
+    match arena[id].get_payload() {
+        Stat::Assign(_) => visitor.leave_stat_assign(arena, id),
+        Stat::Block(_) => visitor.leave_stat_block(arena, id),
+        Stat::CompoundAssign(_) => visitor.leave_stat_compound_assign(arena, id),
+        Stat::DeclareClass(_) => visitor.leave_stat_declare_class(arena, id),
+        Stat::DeclareFunction(_) => visitor.leave_stat_declare_function(arena, id),
+        Stat::DeclareGlobal(_) => visitor.leave_stat_declare_global(arena, id),
+        Stat::Expr(_) => visitor.leave_stat_expr(arena, id),
+        Stat::ForIn(_) => visitor.leave_stat_for_in(arena, id),
+        Stat::Function(_) => visitor.leave_stat_function(arena, id),
+        Stat::Local(_) => visitor.leave_stat_local(arena, id),
+        Stat::LocalFunction(_) => visitor.leave_stat_local_function(arena, id),
+        Stat::Break => visitor.leave_stat_break(arena, id),
+        Stat::Continue => visitor.leave_stat_continue(arena, id),
+        Stat::For(_) => visitor.leave_stat_for(arena, id),
+        Stat::If(_) => visitor.leave_stat_if(arena, id),
+        Stat::Repeat(_) => visitor.leave_stat_repeat(arena, id),
+        Stat::Return(_) => visitor.leave_stat_return(arena, id),
+        Stat::While(_) => visitor.leave_stat_while(arena, id),
+        Stat::TypeAlias(_) => visitor.leave_stat_type_alias(arena, id),
+        Stat::Error(_) => visitor.leave_stat_error(arena, id),
+    }
+}
+
+fn visit_type_kind<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstTypeId) -> bool {
+    match arena[id].get_payload() {
+        Type::Error(_) => visitor.visit_type_error(arena, id),
+        Type::Function(_) => visitor.visit_type_function(arena, id),
+        Type::Intersection(_) => visitor.visit_type_intersection(arena, id),
+        Type::Reference(_) => visitor.visit_type_reference(arena, id),
+        Type::SingletonBool(_) => visitor.visit_type_singleton_bool(arena, id),
+        Type::SingletonString(_) => visitor.visit_type_singleton_string(arena, id),
+        Type::Table(_) => visitor.visit_type_table(arena, id),
+        Type::Typeof(_) => visitor.visit_type_typeof(arena, id),
+        Type::Union(_) => visitor.visit_type_union(arena, id),
+    }
+}
+
+fn leave_type_kind<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstTypeId) {
+    match arena[id].get_payload() {
+        Type::Error(_) => visitor.leave_type_error(arena, id),
+        Type::Function(_) => visitor.leave_type_function(arena, id),
+        Type::Intersection(_) => visitor.leave_type_intersection(arena, id),
+        Type::Reference(_) => visitor.leave_type_reference(arena, id),
+        Type::SingletonBool(_) => visitor.leave_type_singleton_bool(arena, id),
+        Type::SingletonString(_) => visitor.leave_type_singleton_string(arena, id),
+        Type::Table(_) => visitor.leave_type_table(arena, id),
+        Type::Typeof(_) => visitor.leave_type_typeof(arena, id),
+        Type::Union(_) => visitor.leave_type_union(arena, id),
+    }
+}
+
+fn visit_type_pack_kind<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    arena: &AstArena,
+    id: AstTypePackId,
+) -> bool {
+    match arena[id].get_payload() {
+        TypePack::Explicit(_) => visitor.visit_type_pack_explicit(arena, id),
+        TypePack::Generic(_) => visitor.visit_type_pack_generic(arena, id),
+        TypePack::Variadic(_) => visitor.visit_type_pack_variadic(arena, id),
+    }
+}
+
+fn leave_type_pack_kind<V: Visitor + ?Sized>(visitor: &mut V, arena: &AstArena, id: AstTypePackId) {
+    match arena[id].get_payload() {
+        TypePack::Explicit(_) => visitor.leave_type_pack_explicit(arena, id),
+        TypePack::Generic(_) => visitor.leave_type_pack_generic(arena, id),
+        TypePack::Variadic(_) => visitor.leave_type_pack_variadic(arena, id),
+    }
+}
+
+fn visit_expr_kind_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstExprId,
+) -> bool {
+    match arena[id].get_payload() {
+        Expr::Group(_) => visitor.visit_expr_group(arena, id),
+        Expr::ConstantNil => visitor.visit_expr_constant_nil(arena, id),
+        Expr::ConstantBool(_) => visitor.visit_expr_constant_bool(arena, id),
+        Expr::ConstantNumber(_) => visitor.visit_expr_constant_number(arena, id),
+        Expr::ConstantString(_) => visitor.visit_expr_constant_string(arena, id),
+        Expr::Local(_) => visitor.visit_expr_local(arena, id),
+        Expr::Global(_) => visitor.visit_expr_global(arena, id),
+        Expr::Varargs => visitor.visit_expr_varargs(arena, id),
+        Expr::Call(_) => visitor.visit_expr_call(arena, id),
+        Expr::IndexName(_) => visitor.visit_expr_index_name(arena, id),
+        Expr::IndexExpr(_) => visitor.visit_expr_index_expr(arena, id),
+        Expr::Binary(_) => visitor.visit_expr_binary(arena, id),
+        Expr::Function(_) => visitor.visit_expr_function(arena, id),
+        Expr::IfElse(_) => visitor.visit_expr_if_else(arena, id),
+        Expr::InterpString(_) => visitor.visit_expr_interp_string(arena, id),
+        Expr::Table(_) => visitor.visit_expr_table(arena, id),
+        Expr::TypeAssertion(_) => visitor.visit_expr_type_assertion(arena, id),
+        Expr::Unary(_) => visitor.visit_expr_unary(arena, id),
+        Expr::Error(_) => visitor.visit_expr_error(arena, id),
+    }
+}
+
+fn leave_expr_kind_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstExprId,
+) {
+    match arena[id].get_payload() {
+        Expr::Group(_) => visitor.leave_expr_group(arena, id),
+        Expr::ConstantNil => visitor.leave_expr_constant_nil(arena, id),
+        Expr::ConstantBool(_) => visitor.leave_expr_constant_bool(arena, id),
+        Expr::ConstantNumber(_) => visitor.leave_expr_constant_number(arena, id),
+        Expr::ConstantString(_) => visitor.leave_expr_constant_string(arena, id),
+        Expr::Local(_) => visitor.leave_expr_local(arena, id),
+        Expr::Global(_) => visitor.leave_expr_global(arena, id),
+        Expr::Varargs => visitor.leave_expr_varargs(arena, id),
+        Expr::Call(_) => visitor.leave_expr_call(arena, id),
+        Expr::IndexName(_) => visitor.leave_expr_index_name(arena, id),
+        Expr::IndexExpr(_) => visitor.leave_expr_index_expr(arena, id),
+        Expr::Binary(_) => visitor.leave_expr_binary(arena, id),
+        Expr::Function(_) => visitor.leave_expr_function(arena, id),
+        Expr::IfElse(_) => visitor.leave_expr_if_else(arena, id),
+        Expr::InterpString(_) => visitor.leave_expr_interp_string(arena, id),
+        Expr::Table(_) => visitor.leave_expr_table(arena, id),
+        Expr::TypeAssertion(_) => visitor.leave_expr_type_assertion(arena, id),
+        Expr::Unary(_) => visitor.leave_expr_unary(arena, id),
+        Expr::Error(_) => visitor.leave_expr_error(arena, id),
+    }
+}
+
+fn visit_stat_kind_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstStatId,
+) -> bool {
+    match arena[id].get_payload() {
+        Stat::Assign(_) => visitor.visit_stat_assign(arena, id),
+        Stat::Block(_) => visitor.visit_stat_block(arena, id),
+        Stat::CompoundAssign(_) => visitor.visit_stat_compound_assign(arena, id),
+        Stat::DeclareClass(_) => visitor.visit_stat_declare_class(arena, id),
+        Stat::DeclareFunction(_) => visitor.visit_stat_declare_function(arena, id),
+        Stat::DeclareGlobal(_) => visitor.visit_stat_declare_global(arena, id),
+        Stat::Expr(_) => visitor.visit_stat_expr(arena, id),
+        Stat::ForIn(_) => visitor.visit_stat_for_in(arena, id),
+        Stat::Function(_) => visitor.visit_stat_function(arena, id),
+        Stat::Local(_) => visitor.visit_stat_local(arena, id),
+        Stat::LocalFunction(_) => visitor.visit_stat_local_function(arena, id),
+        Stat::Break => visitor.visit_stat_break(arena, id),
+        Stat::Continue => visitor.visit_stat_continue(arena, id),
+        Stat::For(_) => visitor.visit_stat_for(arena, id),
+        Stat::If(_) => visitor.visit_stat_if(arena, id),
+        Stat::Repeat(_) => visitor.visit_stat_repeat(arena, id),
+        Stat::Return(_) => visitor.visit_stat_return(arena, id),
+        Stat::While(_) => visitor.visit_stat_while(arena, id),
+        Stat::TypeAlias(_) => visitor.visit_stat_type_alias(arena, id),
+        Stat::Error(_) => visitor.visit_stat_error(arena, id),
+    }
+}
+
+fn leave_stat_kind_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstStatId,
+) {
+    match arena[id].get_payload() {
+        Stat::Assign(_) => visitor.leave_stat_assign(arena, id),
+        Stat::Block(_) => visitor.leave_stat_block(arena, id),
+        Stat::CompoundAssign(_) => visitor.leave_stat_compound_assign(arena, id),
+        Stat::DeclareClass(_) => visitor.leave_stat_declare_class(arena, id),
+        Stat::DeclareFunction(_) => visitor.leave_stat_declare_function(arena, id),
+        Stat::DeclareGlobal(_) => visitor.leave_stat_declare_global(arena, id),
+        Stat::Expr(_) => visitor.leave_stat_expr(arena, id),
+        Stat::ForIn(_) => visitor.leave_stat_for_in(arena, id),
+        Stat::Function(_) => visitor.leave_stat_function(arena, id),
+        Stat::Local(_) => visitor.leave_stat_local(arena, id),
+        Stat::LocalFunction(_) => visitor.leave_stat_local_function(arena, id),
+        Stat::Break => visitor.leave_stat_break(arena, id),
+        Stat::Continue => visitor.leave_stat_continue(arena, id),
+        Stat::For(_) => visitor.leave_stat_for(arena, id),
+        Stat::If(_) => visitor.leave_stat_if(arena, id),
+        Stat::Repeat(_) => visitor.leave_stat_repeat(arena, id),
+        Stat::Return(_) => visitor.leave_stat_return(arena, id),
+        Stat::While(_) => visitor.leave_stat_while(arena, id),
+        Stat::TypeAlias(_) => visitor.leave_stat_type_alias(arena, id),
+        Stat::Error(_) => visitor.leave_stat_error(arena, id),
+    }
+}
+
+fn visit_type_kind_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstTypeId,
+) -> bool {
+    match arena[id].get_payload() {
+        Type::Error(_) => visitor.visit_type_error(arena, id),
+        Type::Function(_) => visitor.visit_type_function(arena, id),
+        Type::Intersection(_) => visitor.visit_type_intersection(arena, id),
+        Type::Reference(_) => visitor.visit_type_reference(arena, id),
+        Type::SingletonBool(_) => visitor.visit_type_singleton_bool(arena, id),
+        Type::SingletonString(_) => visitor.visit_type_singleton_string(arena, id),
+        Type::Table(_) => visitor.visit_type_table(arena, id),
+        Type::Typeof(_) => visitor.visit_type_typeof(arena, id),
+        Type::Union(_) => visitor.visit_type_union(arena, id),
+    }
+}
+
+fn leave_type_kind_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstTypeId,
+) {
+    match arena[id].get_payload() {
+        Type::Error(_) => visitor.leave_type_error(arena, id),
+        Type::Function(_) => visitor.leave_type_function(arena, id),
+        Type::Intersection(_) => visitor.leave_type_intersection(arena, id),
+        Type::Reference(_) => visitor.leave_type_reference(arena, id),
+        Type::SingletonBool(_) => visitor.leave_type_singleton_bool(arena, id),
+        Type::SingletonString(_) => visitor.leave_type_singleton_string(arena, id),
+        Type::Table(_) => visitor.leave_type_table(arena, id),
+        Type::Typeof(_) => visitor.leave_type_typeof(arena, id),
+        Type::Union(_) => visitor.leave_type_union(arena, id),
+    }
+}
+
+fn visit_type_pack_kind_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstTypePackId,
+) -> bool {
+    match arena[id].get_payload() {
+        TypePack::Explicit(_) => visitor.visit_type_pack_explicit(arena, id),
+        TypePack::Generic(_) => visitor.visit_type_pack_generic(arena, id),
+        TypePack::Variadic(_) => visitor.visit_type_pack_variadic(arena, id),
+    }
+}
+
+fn leave_type_pack_kind_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    arena: &mut AstArena,
+    id: AstTypePackId,
+) {
+    match arena[id].get_payload() {
+        TypePack::Explicit(_) => visitor.leave_type_pack_explicit(arena, id),
+        TypePack::Generic(_) => visitor.leave_type_pack_generic(arena, id),
+        TypePack::Variadic(_) => visitor.leave_type_pack_variadic(arena, id),
+    }
+}
+
+// the direct children of a node in source order, references to locals aren't children,
+// locals are children of the node declaring them
+pub fn get_children(arena: &AstArena, node: AstNodeId) -> Vec<AstNodeId> {
+    let mut children = Children { nodes: Vec::new() };
+
+    match node {
+        AstNodeId::Expr(id) => children.push_expr_children(arena, id),
+        AstNodeId::Stat(id) => children.push_stat_children(arena, id),
+        AstNodeId::Type(id) => children.push_type_children(arena, id),
+        AstNodeId::TypePack(id) => match arena[id].get_payload() {
+            TypePack::Explicit(type_list) => children.push_type_list(type_list),
+            TypePack::Generic(_) => {}
+            TypePack::Variadic(type_) => children.push_type(*type_),
+        },
+        AstNodeId::Local(id) => children.push_type_opt(arena[id].get_annotation()),
+    }
+
+    children.nodes
+}
+
+struct Children {
+    nodes: Vec<AstNodeId>,
+}
+
+impl Children {
+    fn push_expr(&mut self, id: AstExprId) {
+        self.nodes.push(AstNodeId::Expr(id));
+    }
+
+    fn push_exprs(&mut self, ids: &[AstExprId]) {
+        self.nodes.extend(ids.iter().map(|&id| AstNodeId::Expr(id)));
+    }
+
+    fn push_expr_opt(&mut self, id: Option<AstExprId>) {
+        if let Some(id) = id {
+            self.push_expr(id);
+        }
+    }
+
+    fn push_stat(&mut self, id: AstStatId) {
+        self.nodes.push(AstNodeId::Stat(id));
+    }
+
+    fn push_stats(&mut self, ids: &[AstStatId]) {
+        self.nodes.extend(ids.iter().map(|&id| AstNodeId::Stat(id)));
+    }
+
+    fn push_type(&mut self, id: AstTypeId) {
+        self.nodes.push(AstNodeId::Type(id));
+    }
+
+    fn push_types(&mut self, ids: &[AstTypeId]) {
+        self.nodes.extend(ids.iter().map(|&id| AstNodeId::Type(id)));
+    }
+
+    fn push_type_opt(&mut self, id: Option<AstTypeId>) {
+        if let Some(id) = id {
+            self.push_type(id);
+        }
+    }
+
+    fn push_type_pack_opt(&mut self, id: Option<AstTypePackId>) {
+        if let Some(id) = id {
+            self.nodes.push(AstNodeId::TypePack(id));
+        }
+    }
+
+    fn push_type_list(&mut self, type_list: &AstTypeList) {
+        self.push_types(type_list.get_types());
+        self.push_type_pack_opt(type_list.get_tail_type());
+    }
+
+    fn push_locals(&mut self, ids: &[LocalId]) {
+        self.nodes
+            .extend(ids.iter().map(|&id| AstNodeId::Local(id)));
+    }
+
+    fn push_indexer(&mut self, indexer: &TableIndexer) {
+        self.push_type(indexer.get_index_type());
+        self.push_type(indexer.get_result_type());
+    }
+
+    fn push_function(&mut self, function: &ExprFunction) {
+        if let Some(self_) = function.get_self() {
+            self.nodes.push(AstNodeId::Local(self_));
+        }
+        self.push_locals(function.get_args());
+        self.push_type_pack_opt(function.get_vararg_annotation());
+        if let Some(return_annotation) = function.get_return_annotation() {
+            self.push_type_list(return_annotation);
+        }
+        self.push_stat(function.get_body());
+    }
+
+    // the indexer is placed between the properties it was written between
+    fn push_table_type(&mut self, props: &[TableProp], indexer: Option<&TableIndexer>) {
+        let indexer_begin = indexer.map(|indexer| indexer.get_location().get_begin());
+        let mut indexer = indexer;
+
+        for prop in props {
+            if let (Some(current), Some(begin)) = (indexer, indexer_begin) {
+                if prop.get_location().get_begin() > begin {
+                    self.push_indexer(current);
+                    indexer = None;
+                }
+            }
+
+            self.push_type(prop.get_type());
+        }
+
+        if let Some(indexer) = indexer {
+            self.push_indexer(indexer);
+        }
+    }
+
+    fn push_expr_children(&mut self, arena: &AstArena, id: AstExprId) {
+        match arena[id].get_payload() {
+            Expr::Group(expr) => self.push_expr(*expr),
+            Expr::ConstantNil
+            | Expr::ConstantBool(_)
+            | Expr::ConstantNumber(_)
+            | Expr::ConstantString(_)
+            | Expr::Local(_)
+            | Expr::Global(_)
+            | Expr::Varargs => {}
+            Expr::Call(call) => {
+                self.push_expr(call.get_func());
+                self.push_exprs(call.get_args());
+            }
+            Expr::IndexName(index) => self.push_expr(index.get_expr()),
+            Expr::IndexExpr(index) => {
+                self.push_expr(index.get_expr());
+                self.push_expr(index.get_index());
+            }
+            Expr::Binary(binary) => {
+                self.push_expr(binary.get_left());
+                self.push_expr(binary.get_right());
+            }
+            Expr::Function(function) => self.push_function(function),
+            Expr::IfElse(if_else) => {
+                self.push_expr(if_else.get_condition());
+                self.push_expr_opt(if_else.get_true_expr());
+                self.push_expr_opt(if_else.get_false_expr());
+            }
+            Expr::InterpString(interp) => self.push_exprs(interp.get_expressions()),
+            Expr::Table(items) => {
+                for item in items {
+                    self.push_expr_opt(item.get_key());
+                    self.push_expr(item.get_value());
+                }
+            }
+            Expr::TypeAssertion(assertion) => {
+                self.push_expr(assertion.get_expr());
+                self.push_type(assertion.get_annotation());
+            }
+            Expr::Unary(unary) => self.push_expr(unary.get_expr()),
+            Expr::Error(error) => self.push_exprs(error.get_expressions()),
+        }
+    }
+
+    fn push_stat_children(&mut self, arena: &AstArena, id: AstStatId) {
+        match arena[id].get_payload() {
+            Stat::Assign(assign) => {
+                self.push_exprs(assign.get_vars());
+                self.push_exprs(assign.get_values());
+            }
+            Stat::Block(body) => self.push_stats(body),
+            Stat::CompoundAssign(assign) => {
+                self.push_expr(assign.get_var());
+                self.push_expr(assign.get_value());
+            }
+            Stat::DeclareClass(class) => {
+                for prop in class.get_props() {
+                    self.push_type(prop.get_type());
+                }
+                if let Some(indexer) = class.get_indexer() {
+                    self.push_indexer(indexer);
+                }
+            }
+            Stat::DeclareFunction(function) => {
+                self.push_type_list(function.get_params());
+                self.push_type_list(function.get_return_types());
+            }
+            Stat::DeclareGlobal(global) => self.push_type(global.get_type()),
+            Stat::Expr(expr) => self.push_expr(expr.get_expr()),
+            Stat::ForIn(for_in) => {
+                self.push_locals(for_in.get_vars());
+                self.push_exprs(for_in.get_values());
+                self.push_stat(for_in.get_body());
+            }
+            Stat::Function(function) => {
+                self.push_expr(function.get_name());
+                self.push_function(function.get_function());
+            }
+            Stat::Local(local) => {
+                self.push_locals(local.get_vars());
+                self.push_exprs(local.get_values());
+            }
+            Stat::LocalFunction(function) => {
+                self.nodes.push(AstNodeId::Local(function.get_name()));
+                self.push_function(function.get_function());
+            }
+            Stat::Break | Stat::Continue => {}
+            Stat::For(for_) => {
+                self.nodes.push(AstNodeId::Local(for_.get_var()));
+                self.push_expr(for_.get_from());
+                self.push_expr(for_.get_to());
+                self.push_expr_opt(for_.get_step());
+                self.push_stat(for_.get_body());
+            }
+            Stat::If(if_) => {
+                self.push_expr(if_.get_condition());
+                self.push_stat(if_.get_then_body());
+                if let Some(else_body) = if_.get_else_body() {
+                    self.push_stat(else_body);
+                }
+            }
+            Stat::Repeat(repeat) => {
+                self.push_stat(repeat.get_body());
+                self.push_expr(repeat.get_condition());
+            }
+            Stat::Return(return_) => self.push_exprs(return_.get_list()),
+            Stat::While(while_) => {
+                self.push_expr(while_.get_condition());
+                self.push_stat(while_.get_body());
+            }
+            Stat::TypeAlias(alias) => {
+                for generic in alias.get_generics() {
+                    self.push_type_opt(generic.get_default_value());
+                }
+                for generic in alias.get_generic_packs() {
+                    self.push_type_pack_opt(generic.get_default_value());
+                }
+                self.push_type(alias.get_type());
+            }
+            Stat::Error(error) => {
+                self.push_exprs(error.get_expressions());
+                self.push_stats(error.get_statements());
+            }
+        }
+    }
+
+    fn push_type_children(&mut self, arena: &AstArena, id: AstTypeId) {
+        match arena[id].get_payload() {
+            Type::Error(error) => self.push_types(error.get_types()),
+            Type::Function(function) => {
+                self.push_type_list(function.get_arg_types());
+                self.push_type_list(function.get_return_types());
+            }
+            Type::Intersection(types) | Type::Union(types) => self.push_types(types),
+            Type::Reference(reference) => {
+                for parameter in reference.get_parameters().unwrap_or_default() {
+                    match *parameter {
+                        AstTypeOrPack::Type(type_) => self.push_type(type_),
+                        AstTypeOrPack::TypePack(pack) => self.push_type_pack_opt(Some(pack)),
+                    }
+                }
+            }
+            Type::SingletonBool(_) | Type::SingletonString(_) => {}
+            Type::Table(table) => self.push_table_type(table.get_props(), table.get_indexer()),
+            Type::Typeof(expr) => self.push_expr(*expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::test_util::{block_body, expr_shape, return_list};
+    use super::super::super::{ParseOptions, Parser};
+    use super::super::BinaryOperator;
+    use super::*;
+
+    // records the traversal order, function bodies and table types are skipped
+    struct TraceVisitor {
+        trace: Vec<String>,
+    }
+
+    impl Visitor for TraceVisitor {
+        fn visit_expr(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+            self.trace
+                .push(format!("{:?}", arena[id].get_payload_type()));
+            true
+        }
+
+        fn leave_expr(&mut self, _arena: &AstArena, _id: AstExprId) {
+            self.trace.push(String::from("/"));
+        }
+
+        fn leave_expr_call(&mut self, _arena: &AstArena, _id: AstExprId) {
+            self.trace.push(String::from("/call"));
+        }
+
+        fn visit_expr_function(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+            self.visit_expr(arena, id);
+            false
+        }
+
+        fn visit_type(&mut self, arena: &AstArena, id: AstTypeId) -> bool {
+            self.trace
+                .push(format!("{:?}", arena[id].get_payload_type()));
+            true
+        }
+
+        fn visit_type_table(&mut self, _arena: &AstArena, _id: AstTypeId) -> bool {
+            self.trace.push(String::from("TypeTable"));
+            false
+        }
+
+        fn visit_type_pack(&mut self, arena: &AstArena, id: AstTypePackId) -> bool {
+            self.trace
+                .push(format!("{:?}", arena[id].get_payload_type()));
+            true
+        }
+
+        fn visit_local(&mut self, _arena: &AstArena, _id: LocalId) -> bool {
+            self.trace.push(String::from("Local"));
+            true
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        let trace = |src: &str| {
+            let result = Parser::parse(src, ParseOptions::default());
+            assert!(result.get_errors().is_empty());
+
+            let mut visitor = TraceVisitor { trace: Vec::new() };
+            walk_stat(&mut visitor, result.get_arena(), result.get_root_id());
+            visitor.trace.join(" ")
+        };
+
+        assert_eq!(
+            trace("return -a, f(1)"),
+            "ExprUnary ExprGlobal / / ExprCall ExprGlobal / ExprConstantNumber / /call"
+        );
+        assert_eq!(trace("return function(x) return x end"), "ExprFunction /");
+        assert_eq!(
+            trace("local x: number = y :: string"),
+            "Local TypeReference ExprTypeAssertion ExprGlobal / TypeReference /"
+        );
+        assert_eq!(
+            trace("type F = (number, ...string) -> T..."),
+            "TypeFunction TypeReference TypePackVariadic TypeReference TypePackGeneric"
+        );
+        assert_eq!(
+            trace("type F = Foo<{ [string]: number }, typeof(a)>"),
+            "TypeReference TypeTable TypeTypeof ExprGlobal /"
+        );
+
+        // children are listed in source order, the indexer is kept between the properties
+        let result = Parser::parse(
+            "type T = { a: number, [string]: boolean, b: nil }",
+            ParseOptions::default(),
+        );
+        let arena = result.get_arena();
+        let type_ = match arena[block_body(arena, result.get_root_id())[0]].get_payload() {
+            Stat::TypeAlias(alias) => alias.get_type(),
+            _ => panic!("failed"),
+        };
+        let names: Vec<String> = get_children(arena, AstNodeId::Type(type_))
+            .iter()
+            .map(|child| match *child {
+                AstNodeId::Type(id) => match arena[id].get_payload() {
+                    Type::Reference(reference) => {
+                        String::from(result.get_names().get_value(reference.get_name()))
+                    }
+                    _ => String::from("?"),
+                },
+                _ => panic!("failed"),
+            })
+            .collect();
+        assert_eq!(names, vec!["number", "string", "boolean", "nil"]);
+    }
+
+    // traces statements and expressions, skipping the children of `if` statements
+    struct SkipVisitor {
+        trace: Vec<String>,
+    }
+
+    impl Visitor for SkipVisitor {
+        fn visit_stat(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+            self.trace
+                .push(format!("{:?}", arena[id].get_payload_type()));
+            true
+        }
+
+        fn leave_stat(&mut self, _arena: &AstArena, _id: AstStatId) {
+            self.trace.push(String::from("/"));
+        }
+
+        fn visit_stat_if(&mut self, arena: &AstArena, id: AstStatId) -> bool {
+            self.visit_stat(arena, id);
+            false
+        }
+
+        fn visit_expr(&mut self, arena: &AstArena, id: AstExprId) -> bool {
+            self.trace
+                .push(format!("{:?}", arena[id].get_payload_type()));
+            true
+        }
+    }
+
+    #[test]
+    fn test_visitor_skip_stat() {
+        let result = Parser::parse(
+            "if a then local x = f() end return b",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+
+        let mut visitor = SkipVisitor { trace: Vec::new() };
+        walk_stat(&mut visitor, result.get_arena(), result.get_root_id());
+        assert_eq!(
+            visitor.trace.join(" "),
+            "StatBlock StatIf / StatReturn ExprGlobal / /"
+        );
+    }
+
+    // folds additions of constant numbers after their operands were folded
+    struct FoldVisitor;
+
+    impl VisitorMut for FoldVisitor {
+        fn leave_expr_binary(&mut self, arena: &mut AstArena, id: AstExprId) {
+            let binary = match arena[id].get_payload() {
+                Expr::Binary(binary) if binary.get_operator() == BinaryOperator::Add => binary,
+                _ => return,
+            };
+            let value = match (
+                arena[binary.get_left()].get_payload(),
+                arena[binary.get_right()].get_payload(),
+            ) {
+                (Expr::ConstantNumber(left), Expr::ConstantNumber(right)) => left + right,
+                _ => return,
+            };
+
+            *arena[id].get_payload_mut() = Expr::ConstantNumber(value);
+        }
+    }
+
+    #[test]
+    fn test_visitor_mut() {
+        let mut result = Parser::parse("return 1 + 2 + 3, a + 1", ParseOptions::default());
+        let root = result.get_root_id();
+        walk_stat_mut(&mut FoldVisitor, result.get_arena_mut(), root);
+
+        let arena = result.get_arena();
+        let list = return_list(arena, block_body(arena, root)[0]);
+        match arena[list[0]].get_payload() {
+            Expr::ConstantNumber(value) => assert_eq!(*value, 6.0),
+            _ => panic!("failed"),
+        }
+        assert_eq!(expr_shape(arena, list[1], result.get_names()), "(Add a 1)");
+    }
+}
@@ -0,0 +1,120 @@
+// how a numeric literal was converted, anything but malformed still carries a usable value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberParseResult {
+    Ok,
+    // decimal integer that can't be represented exactly as a double
+    Imprecise,
+    Malformed,
+    // binary and hexadecimal integers are saturated to 2^64 when they exceed 64 bits
+    BinOverflow,
+    HexOverflow,
+}
+
+// converts the text of a numeric literal to its value following the rules of luau,
+// digit separators must already be removed
+pub fn to_number(text: &str) -> (f64, NumberParseResult) {
+    let bytes = text.as_bytes();
+
+    if bytes.len() > 2 && bytes[0] == b'0' {
+        match bytes[1] {
+            b'b' | b'B' => return parse_integer(&text[2..], 2),
+            b'x' | b'X' => return parse_integer(&text[2..], 16),
+            _ => {}
+        }
+    }
+
+    parse_double(text)
+}
+
+fn parse_integer(digits: &str, radix: u32) -> (f64, NumberParseResult) {
+    let mut value: u64 = 0;
+    let mut overflow = false;
+
+    for ch in digits.chars() {
+        let digit = match ch.to_digit(radix) {
+            Some(digit) => u64::from(digit),
+            None => return (0.0, NumberParseResult::Malformed),
+        };
+
+        match value
+            .checked_mul(u64::from(radix))
+            .and_then(|value| value.checked_add(digit))
+        {
+            Some(next) => value = next,
+            None => overflow = true,
+        }
+    }
+
+    if overflow {
+        let result = if radix == 2 {
+            NumberParseResult::BinOverflow
+        } else {
+            NumberParseResult::HexOverflow
+        };
+
+        (u64::MAX as f64, result)
+    } else {
+        (value as f64, NumberParseResult::Ok)
+    }
+}
+
+// <digits>[.<digits>][(e|E)[+|-]<digits>] with at least one mantissa digit
+fn is_decimal(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    let skip_digits = |i: &mut usize| {
+        let start = *i;
+        while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+            *i += 1;
+        }
+
+        *i - start
+    };
+
+    let mut mantissa = skip_digits(&mut i);
+    if i < bytes.len() && bytes[i] == b'.' {
+        i += 1;
+        mantissa += skip_digits(&mut i);
+    }
+
+    if mantissa == 0 {
+        return false;
+    }
+
+    if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+        i += 1;
+
+        if i < bytes.len() && (bytes[i] == b'+' || bytes[i] == b'-') {
+            i += 1;
+        }
+
+        if skip_digits(&mut i) == 0 {
+            return false;
+        }
+    }
+
+    i == bytes.len()
+}
+
+fn parse_double(text: &str) -> (f64, NumberParseResult) {
+    if !is_decimal(text) {
+        return (0.0, NumberParseResult::Malformed);
+    }
+
+    // values out of range become infinity, like strtod
+    let value = match text.parse::<f64>() {
+        Ok(value) => value,
+        Err(_) => return (0.0, NumberParseResult::Malformed),
+    };
+
+    // large integer literals are checked against their exact decimal representation
+    if value >= (1u64 << 53) as f64
+        && text.bytes().all(|ch| ch.is_ascii_digit())
+        && format!("{:.0}", value) != text
+    {
+        return (value, NumberParseResult::Imprecise);
+    }
+
+    (value, NumberParseResult::Ok)
+}
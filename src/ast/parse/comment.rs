@@ -1,4 +1,5 @@
 use super::super::{LexLocation, LexType};
+use super::{ParseError, ParseErrorCode, Severity};
 
 pub struct Comment {
     type_: LexType,
@@ -9,4 +10,157 @@ impl Comment {
     pub fn new(type_: LexType, location: LexLocation) -> Self {
         Comment { type_, location }
     }
+
+    pub fn get_type(&self) -> LexType {
+        self.type_.clone()
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+}
+
+// type checking mode selected by a hot comment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    NoCheck,
+    Nonstrict,
+    Strict,
+}
+
+// every directive luau understands, unknown ones are reported with the closest match
+const HOT_COMMENTS: [&str; 6] = [
+    "nolint",
+    "nocheck",
+    "nonstrict",
+    "strict",
+    "optimize",
+    "native",
+];
+
+// directives further away than this don't get a suggestion
+const SUGGESTION_DISTANCE: usize = 4;
+
+pub struct HotComment {
+    header: bool,
+    location: LexLocation,
+    content: String,
+}
+
+impl HotComment {
+    pub fn new(header: bool, location: LexLocation, content: String) -> Self {
+        HotComment {
+            header,
+            location,
+            content,
+        }
+    }
+
+    pub fn is_header(&self) -> bool {
+        self.header
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+
+    pub fn get_content(&self) -> &str {
+        &self.content
+    }
+}
+
+impl HotComment {
+    // the mode only applies when the directive precedes the first statement
+    pub fn get_mode(&self) -> Option<Mode> {
+        if !self.header {
+            return None;
+        }
+
+        match self.content.as_str() {
+            "nocheck" => Some(Mode::NoCheck),
+            "nonstrict" => Some(Mode::Nonstrict),
+            "strict" => Some(Mode::Strict),
+            _ => None,
+        }
+    }
+
+    // returns a warning for directives that are misplaced, unknown or have malformed arguments
+    pub fn check(&self) -> Option<ParseError> {
+        // --!<space> is reserved for informational comments
+        if self.content.is_empty() || self.content.starts_with([' ', '\t']) {
+            return None;
+        }
+
+        let warning = |message: &str| {
+            Some(
+                ParseError::new(ParseErrorCode::CommentDirective, self.location, message)
+                    .with_severity(Severity::Warning),
+            )
+        };
+
+        if !self.header {
+            return warning(
+                "Comment directive is ignored because it is placed after the first non-comment token",
+            );
+        }
+
+        let (name, arguments) = match self.content.find([' ', '\t']) {
+            Some(index) => (&self.content[..index], Some(self.content[index..].trim())),
+            None => (self.content.as_str(), None),
+        };
+
+        match name {
+            "nolint" => None,
+            "nocheck" | "nonstrict" | "strict" => arguments.and_then(|_| {
+                warning(
+                    "Comment directive with the type checking mode has extra symbols at the end of the line",
+                )
+            }),
+            "native" => arguments.and_then(|_| {
+                warning("native directive has extra symbols at the end of the line")
+            }),
+            "optimize" => match arguments {
+                None | Some("") => warning("optimize directive requires an optimization level"),
+                Some("0") | Some("1") | Some("2") => None,
+                Some(level) => warning(&format!(
+                    "optimize directive uses unknown optimization level '{}', 0..2 expected",
+                    level
+                )),
+            },
+            _ => {
+                let suggestion = HOT_COMMENTS
+                    .iter()
+                    .map(|directive| (edit_distance(name, directive), *directive))
+                    .filter(|(distance, _)| *distance <= SUGGESTION_DISTANCE)
+                    .min_by_key(|(distance, _)| *distance);
+
+                match suggestion {
+                    Some((_, directive)) => warning(&format!(
+                        "Unknown comment directive '{}'; did you mean '{}'?",
+                        name, directive
+                    ))
+                    .map(|error| error.with_suggestion(&format!("--!{}", directive))),
+                    None => warning(&format!("Unknown comment directive '{}'", name)),
+                }
+            }
+        }
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_ch) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b_ch) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_ch != *b_ch);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
 }
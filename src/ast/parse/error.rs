@@ -1,6 +1,197 @@
 use super::super::LexLocation;
+use std::fmt::Display;
 
+// error codes are part of the public api, existing codes must never be renumbered
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorCode {
+    UnexpectedToken,
+    UnmatchedDelimiter,
+    ExpectedIdentifier,
+    ExpectedType,
+    IncompleteStatement,
+    InvalidAssignmentTarget,
+    BreakOutsideLoop,
+    VarargOutsideFunction,
+    AmbiguousCall,
+    MalformedString,
+    MalformedNumber,
+    MixedUnionIntersection,
+    DuplicateTableIndexer,
+    UnexpectedTypePack,
+    UnitType,
+    InvalidDeclaration,
+    UnannotatedDeclarationParameter,
+    MissingSelfParameter,
+    GenericOrder,
+    MissingGenericDefault,
+    ExpectedTypePack,
+    ContinueOutsideLoop,
+    MalformedInterpolatedString,
+    CommentDirective,
+    InvalidCharacter,
+    MalformedComment,
+    RecursionLimit,
+    ImpreciseNumber,
+}
+
+impl ParseErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParseErrorCode::UnexpectedToken => "P0001",
+            ParseErrorCode::UnmatchedDelimiter => "P0002",
+            ParseErrorCode::ExpectedIdentifier => "P0003",
+            ParseErrorCode::ExpectedType => "P0004",
+            ParseErrorCode::IncompleteStatement => "P0005",
+            ParseErrorCode::InvalidAssignmentTarget => "P0006",
+            ParseErrorCode::BreakOutsideLoop => "P0007",
+            ParseErrorCode::VarargOutsideFunction => "P0008",
+            ParseErrorCode::AmbiguousCall => "P0009",
+            ParseErrorCode::MalformedString => "P0010",
+            ParseErrorCode::MalformedNumber => "P0011",
+            ParseErrorCode::MixedUnionIntersection => "P0012",
+            ParseErrorCode::DuplicateTableIndexer => "P0013",
+            ParseErrorCode::UnexpectedTypePack => "P0014",
+            ParseErrorCode::UnitType => "P0015",
+            ParseErrorCode::InvalidDeclaration => "P0016",
+            ParseErrorCode::UnannotatedDeclarationParameter => "P0017",
+            ParseErrorCode::MissingSelfParameter => "P0018",
+            ParseErrorCode::GenericOrder => "P0019",
+            ParseErrorCode::MissingGenericDefault => "P0020",
+            ParseErrorCode::ExpectedTypePack => "P0021",
+            ParseErrorCode::ContinueOutsideLoop => "P0022",
+            ParseErrorCode::MalformedInterpolatedString => "P0023",
+            ParseErrorCode::CommentDirective => "P0024",
+            ParseErrorCode::InvalidCharacter => "P0025",
+            ParseErrorCode::MalformedComment => "P0026",
+            ParseErrorCode::RecursionLimit => "P0027",
+            ParseErrorCode::ImpreciseNumber => "P0028",
+        }
+    }
+}
+
+impl Display for ParseErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+// secondary location attached to a diagnostic, e.g. where an unmatched block was opened
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseErrorLabel {
+    location: LexLocation,
+    message: String,
+}
+
+impl ParseErrorLabel {
+    pub fn new(location: LexLocation, message: &str) -> Self {
+        ParseErrorLabel {
+            location,
+            message: String::from(message),
+        }
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct ParseError {
+    code: ParseErrorCode,
+    severity: Severity,
     location: LexLocation,
     message: String,
+    labels: Vec<ParseErrorLabel>,
+    // replacement text for the primary location that fixes the error
+    suggestion: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(code: ParseErrorCode, location: LexLocation, message: &str) -> Self {
+        ParseError {
+            code,
+            severity: Severity::Error,
+            location,
+            message: String::from(message),
+            labels: Vec::new(),
+            suggestion: None,
+        }
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_label(mut self, location: LexLocation, message: &str) -> Self {
+        self.labels.push(ParseErrorLabel::new(location, message));
+        self
+    }
+
+    pub fn with_suggestion(mut self, suggestion: &str) -> Self {
+        self.suggestion = Some(String::from(suggestion));
+        self
+    }
+
+    pub fn get_code(&self) -> ParseErrorCode {
+        self.code
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn get_labels(&self) -> &[ParseErrorLabel] {
+        &self.labels
+    }
+
+    pub fn get_suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
 }
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let begin = self.location.get_begin();
+
+        // locations are zero based, diagnostics are reported one based
+        write!(
+            f,
+            "{}:{}: {}[{}]: {}",
+            begin.get_line() + 1,
+            begin.get_column() + 1,
+            self.severity,
+            self.code,
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,242 @@
+use super::super::{LexLocation, LexPosition};
+use std::iter::Peekable;
+use std::str::Chars;
+
+// decodes the escape sequences of a quoted string body into the bytes of the string value,
+// every malformed escape sequence is returned with the location it spans in the source
+pub fn decode_escapes(
+    raw: &str,
+    begin: LexPosition,
+) -> (Vec<u8>, Vec<(LexLocation, &'static str)>) {
+    let mut decoder = EscapeDecoder {
+        chars: raw.chars().peekable(),
+        line: begin.get_line(),
+        column: begin.get_column(),
+        offset: begin.get_offset(),
+        value: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    loop {
+        let start = decoder.position();
+
+        match decoder.next() {
+            Some('\\') => decoder.read_escape(start),
+            Some(ch) => decoder.push_char(ch),
+            None => break,
+        }
+    }
+
+    (decoder.value, decoder.errors)
+}
+
+// the value of a long string, the newline right after the opening bracket is skipped and '\r\n'
+// becomes '\n', a lone '\r' isn't a line break and is kept
+pub fn decode_multiline(raw: &str) -> Vec<u8> {
+    let raw = raw.as_bytes();
+    let raw = if raw.starts_with(b"\r\n") {
+        &raw[2..]
+    } else if raw.starts_with(b"\n") {
+        &raw[1..]
+    } else {
+        raw
+    };
+
+    let mut value: Vec<u8> = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] == b'\r' && raw.get(i + 1) == Some(&b'\n') {
+            i += 1;
+        }
+
+        value.push(raw[i]);
+        i += 1;
+    }
+
+    value
+}
+
+struct EscapeDecoder<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: u32,
+    column: u32,
+    offset: u32,
+    value: Vec<u8>,
+    errors: Vec<(LexLocation, &'static str)>,
+}
+
+impl<'a> EscapeDecoder<'a> {
+    fn position(&self) -> LexPosition {
+        LexPosition::new(self.line, self.column).with_offset(self.offset)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
+        self.offset += ch.len_utf8() as u32;
+
+        Some(ch)
+    }
+
+    fn next_hex_digit(&mut self) -> Option<u32> {
+        let digit = self.peek()?.to_digit(16)?;
+        self.next();
+
+        Some(digit)
+    }
+
+    fn push_char(&mut self, ch: char) {
+        let mut buf = [0u8; 4];
+        self.value
+            .extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+    }
+
+    fn report(&mut self, start: LexPosition, message: &'static str) {
+        let location = LexLocation::new(start, self.position());
+        self.errors.push((location, message));
+    }
+
+    fn read_escape(&mut self, start: LexPosition) {
+        match self.next() {
+            // the lexer never produces a string ending with a lone backslash
+            None => {}
+            Some('\n') => self.value.push(b'\n'),
+            Some('\r') => {
+                self.value.push(b'\n');
+
+                if self.peek() == Some('\n') {
+                    self.next();
+                }
+            }
+            Some('a') => self.value.push(0x07),
+            Some('b') => self.value.push(0x08),
+            Some('f') => self.value.push(0x0c),
+            Some('n') => self.value.push(b'\n'),
+            Some('r') => self.value.push(b'\r'),
+            Some('t') => self.value.push(b'\t'),
+            Some('v') => self.value.push(0x0b),
+            Some('x') => self.read_hex_escape(start),
+            Some('u') => self.read_unicode_escape(start),
+            Some('z') => {
+                while matches!(
+                    self.peek(),
+                    Some(' ' | '\t' | '\r' | '\n' | '\x0b' | '\x0c')
+                ) {
+                    self.next();
+                }
+            }
+            Some(ch) if ch.is_ascii_digit() => self.read_decimal_escape(start, ch),
+            // any other escaped character stands for itself, this covers \\, \" and \'
+            Some(ch) => self.push_char(ch),
+        }
+    }
+
+    // \xXX, exactly two hexadecimal digits
+    fn read_hex_escape(&mut self, start: LexPosition) {
+        let mut code = 0;
+
+        for _ in 0..2 {
+            if let Some(digit) = self.next_hex_digit() {
+                code = code * 16 + digit;
+            } else {
+                self.report(
+                    start,
+                    "Invalid hexadecimal escape sequence, expected two hexadecimal digits after '\\x'",
+                );
+                return;
+            }
+        }
+
+        self.value.push(code as u8);
+    }
+
+    // \ddd, up to three decimal digits
+    fn read_decimal_escape(&mut self, start: LexPosition, first: char) {
+        let mut code = first.to_digit(10).unwrap_or(0);
+
+        for _ in 0..2 {
+            match self.peek().and_then(|ch| ch.to_digit(10)) {
+                Some(digit) => {
+                    self.next();
+                    code = code * 10 + digit;
+                }
+                None => break,
+            }
+        }
+
+        if code > 0xff {
+            self.report(
+                start,
+                "Decimal escape sequence is out of range, expected at most 255",
+            );
+        } else {
+            self.value.push(code as u8);
+        }
+    }
+
+    // \u{XXX}, the code point is encoded as utf-8
+    fn read_unicode_escape(&mut self, start: LexPosition) {
+        if self.peek() != Some('{') {
+            self.report(
+                start,
+                "Invalid unicode escape sequence, expected '{' after '\\u'",
+            );
+            return;
+        }
+        self.next();
+
+        let mut code: u32 = 0;
+        let mut digits = 0;
+        while let Some(digit) = self.next_hex_digit() {
+            code = code.saturating_mul(16).saturating_add(digit);
+            digits += 1;
+        }
+
+        if digits == 0 || self.peek() != Some('}') {
+            self.report(
+                start,
+                "Invalid unicode escape sequence, expected hexadecimal digits followed by '}'",
+            );
+            return;
+        }
+        self.next();
+
+        if code > 0x10ffff {
+            self.report(
+                start,
+                "Unicode escape sequence is out of range, expected at most 10FFFF",
+            );
+        } else {
+            self.push_code_point(code);
+        }
+    }
+
+    // surrogates are encoded as well, so this can't go through char::encode_utf8
+    fn push_code_point(&mut self, code: u32) {
+        if code < 0x80 {
+            self.value.push(code as u8);
+        } else if code < 0x800 {
+            self.value.push((0xc0 | (code >> 6)) as u8);
+            self.value.push((0x80 | (code & 0x3f)) as u8);
+        } else if code < 0x10000 {
+            self.value.push((0xe0 | (code >> 12)) as u8);
+            self.value.push((0x80 | ((code >> 6) & 0x3f)) as u8);
+            self.value.push((0x80 | (code & 0x3f)) as u8);
+        } else {
+            self.value.push((0xf0 | (code >> 18)) as u8);
+            self.value.push((0x80 | ((code >> 12) & 0x3f)) as u8);
+            self.value.push((0x80 | ((code >> 6) & 0x3f)) as u8);
+            self.value.push((0x80 | (code & 0x3f)) as u8);
+        }
+    }
+}
@@ -1,7 +1,12 @@
 mod comment;
 mod error;
+mod escape;
+mod options;
 mod parser;
+mod result;
 
-pub use comment::Comment;
-pub use error::ParseError;
+pub use comment::{Comment, HotComment, Mode};
+pub use error::{ParseError, ParseErrorCode, ParseErrorLabel, Severity};
+pub use options::ParseOptions;
 pub use parser::*;
+pub use result::ParseResult;
@@ -0,0 +1,47 @@
+#[derive(Clone, Copy)]
+pub struct ParseOptions {
+    allow_type_annotations: bool,
+    capture_comments: bool,
+    allow_declaration_syntax: bool,
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        ParseOptions {
+            allow_type_annotations: true,
+            capture_comments: false,
+            allow_declaration_syntax: false,
+        }
+    }
+
+    pub fn get_allow_type_annotations(&self) -> bool {
+        self.allow_type_annotations
+    }
+
+    pub fn set_allow_type_annotations(&mut self, allow_type_annotations: bool) {
+        self.allow_type_annotations = allow_type_annotations;
+    }
+
+    pub fn get_capture_comments(&self) -> bool {
+        self.capture_comments
+    }
+
+    pub fn set_capture_comments(&mut self, capture_comments: bool) {
+        self.capture_comments = capture_comments;
+    }
+
+    // `declare` statements are only allowed in definition files
+    pub fn get_allow_declaration_syntax(&self) -> bool {
+        self.allow_declaration_syntax
+    }
+
+    pub fn set_allow_declaration_syntax(&mut self, allow_declaration_syntax: bool) {
+        self.allow_declaration_syntax = allow_declaration_syntax;
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions::new()
+    }
+}
@@ -1,64 +1,229 @@
 use super::super::*;
-use std::str::Chars;
+use super::escape::{decode_escapes, decode_multiline};
 
-pub struct ParseError {
-    location: LexLocation,
-    message: String,
-}
+use std::collections::HashMap;
 
-impl ParseError {
-    pub fn new(location: LexLocation, message: &str) -> Self {
-        ParseError {
-            location,
-            message: String::from(message),
-        }
-    }
-}
+// placeholder for names that failed to parse
+pub(crate) const NAME_ERROR: &str = "%error-id%";
+
+// like luau's LuauRecursionLimit, deeper nesting is reported instead of overflowing the stack
+const RECURSION_LIMIT: u32 = 1000;
+
+// a local name with its location and optional annotation
+type Binding = (AstName, LexLocation, Option<AstTypeId>);
 
-use std::collections::HashMap;
 pub struct Parser<'src_lf> {
+    options: ParseOptions,
     lexer: Lexer<'src_lf>,
-    local_map: HashMap<AstName, AstLocal>,
-    local_stack: Vec<&'src_lf AstLocal>,
-    error_msgs: Vec<String>,
+    arena: AstArena,
+    local_map: HashMap<AstName, LocalId>,
+    local_stack: Vec<LocalId>,
+    errors: Vec<ParseError>,
+    comments: Vec<Comment>,
+    hot_comments: Vec<HotComment>,
+    hot_comment_header: bool,
     function_stack: Vec<(bool, u32)>,
+    match_recovery_stop_on_token: Vec<u32>,
+    recursion_counter: u32,
+
+    name_self: AstName,
+    name_number: AstName,
+    name_nil: AstName,
+    name_error: AstName,
 }
 
 impl<'src_lf> Parser<'src_lf> {
     pub fn new(src: &'src_lf str) -> Self {
+        Parser::with_options(src, ParseOptions::default())
+    }
+
+    pub fn with_options(src: &'src_lf str, options: ParseOptions) -> Self {
+        Parser::with_lexer(Lexer::new(src), options)
+    }
+
+    fn with_lexer(mut lexer: Lexer<'src_lf>, options: ParseOptions) -> Self {
+        let names = lexer.get_names_mut();
+        let (name_self, _) = names.get_or_add("self");
+        let (name_number, _) = names.get_or_add("number");
+        let (name_nil, _) = names.get_or_add("nil");
+        let (name_error, _) = names.get_or_add(NAME_ERROR);
+
         let mut result = Parser {
-            lexer: Lexer::new(src),
+            options,
+            lexer,
+            arena: AstArena::new(),
             local_map: HashMap::new(),
             local_stack: Vec::new(),
-            error_msgs: Vec::new(),
-            function_stack: Vec::new(),
+            errors: Vec::new(),
+            comments: Vec::new(),
+            hot_comments: Vec::new(),
+            hot_comment_header: true,
+            // the chunk itself is a vararg function
+            function_stack: vec![(true, 0)],
+            match_recovery_stop_on_token: vec![0; LexType::count() as usize],
+            recursion_counter: 0,
+            name_self,
+            name_number,
+            name_nil,
+            name_error,
         };
+        result.match_recovery_stop_on_token[LexType::Eof.code() as usize] = 1;
         result.next_lexeme();
 
+        // all hot comments parsed after the first non-comment lexeme are not header comments
+        result.hot_comment_header = false;
+
         result
     }
 
-    fn report_type_error(&mut self, is_mission: bool, error_msg: &str) -> Box<AstType> {
-        self.error_msgs.push(String::from(error_msg));
+    pub fn parse(src: &str, options: ParseOptions) -> ParseResult {
+        Parser::with_options(src, options).parse_result()
+    }
 
-        TypeError::new(
-            self.get_location(),
-            Vec::new(),
-            is_mission,
-            self.error_msgs.len() - 1,
+    // invalid utf-8 sequences are reported where they appear outside of strings and comments,
+    // inside of them they are replaced by U+FFFD, byte offsets of locations index src
+    pub fn parse_bytes(src: &[u8], options: ParseOptions) -> ParseResult {
+        let (text, invalid_utf8) = Lexer::decode_lossy(src);
+
+        Parser::with_lexer(Lexer::with_invalid_utf8(&text, invalid_utf8), options).parse_result()
+    }
+
+    fn parse_result(mut self) -> ParseResult {
+        let root = self.parse_chunk();
+
+        // directives are only checked once all of them have been collected
+        let warnings: Vec<ParseError> = self
+            .hot_comments
+            .iter()
+            .filter_map(|hot_comment| hot_comment.check())
+            .collect();
+        for warning in warnings {
+            self.report_error(warning);
+        }
+
+        ParseResult::new(
+            root,
+            self.arena,
+            self.errors,
+            self.comments,
+            self.hot_comments,
+            self.lexer.into_names(),
         )
     }
 
-    fn report_expr_error(&mut self, error_msg: &str) -> Box<AstExpr> {
-        self.error_msgs.push(String::from(error_msg));
+    fn add<T: ArenaNode>(&mut self, node: T) -> NodeId<T> {
+        self.arena.alloc(node)
+    }
+
+    fn intern(&mut self, value: &str) -> AstName {
+        self.lexer.get_names_mut().get_or_add(value).0
+    }
+
+    fn is_name(&self, name: AstName, value: &str) -> bool {
+        self.lexer.get_names().get_value(name).eq(value)
+    }
+
+    // whether the current lexeme is a name with this text
+    fn is_lexeme_name(&self, value: &str) -> bool {
+        match self.get_lexeme() {
+            LexType::Name(name) => self.is_name(name, value),
+            _ => false,
+        }
+    }
+
+    fn report_error(&mut self, error: ParseError) -> usize {
+        // to reduce the number of errors reported for incomplete statements, skip multiple errors at the same location
+        // for example, consider 'local a = (((b + ' where multiple tokens haven't been written yet
+        if let Some(last) = self.errors.last() {
+            if last.get_location() == error.get_location() {
+                return self.errors.len() - 1;
+            }
+        }
+
+        self.errors.push(error);
+
+        self.errors.len() - 1
+    }
+
+    fn report(&mut self, location: LexLocation, code: ParseErrorCode, error_msg: &str) -> usize {
+        self.report_error(ParseError::new(code, location, error_msg))
+    }
+
+    fn report_type_error(
+        &mut self,
+        location: LexLocation,
+        code: ParseErrorCode,
+        types: Vec<AstTypeId>,
+        is_missing: bool,
+        error_msg: &str,
+    ) -> AstTypeId {
+        let message_index = self.report(location, code, error_msg);
+
+        self.add(TypeError::new(location, types, is_missing, message_index))
+    }
+
+    fn report_expr_error(
+        &mut self,
+        location: LexLocation,
+        code: ParseErrorCode,
+        expressions: Vec<AstExprId>,
+        error_msg: &str,
+    ) -> AstExprId {
+        let message_index = self.report(location, code, error_msg);
+
+        self.add(ExprError::new(location, expressions, message_index))
+    }
+
+    fn report_stat_error(
+        &mut self,
+        location: LexLocation,
+        code: ParseErrorCode,
+        expressions: Vec<AstExprId>,
+        statements: Vec<AstStatId>,
+        error_msg: &str,
+    ) -> AstStatId {
+        let message_index = self.report(location, code, error_msg);
+
+        self.add(StatError::new(
+            location,
+            expressions,
+            statements,
+            message_index,
+        ))
+    }
 
-        ExprError::new(self.get_location(), Vec::new(), self.error_msgs.len() - 1)
+    // returns the index of the reported error once the nesting is too deep, callers save the
+    // counter before incrementing it and restore it when they return
+    fn increment_recursion_counter(&mut self, context: &str) -> Option<usize> {
+        self.recursion_counter += 1;
+
+        if self.recursion_counter > RECURSION_LIMIT {
+            Some(self.report(
+                self.get_location(),
+                ParseErrorCode::RecursionLimit,
+                &format!(
+                    "Exceeded allowed recursion depth; simplify your {} to make the code compile",
+                    context
+                ),
+            ))
+        } else {
+            None
+        }
     }
 
     fn get_lexeme(&self) -> LexType {
         self.lexer.get_current_type()
     }
 
+    // the current lexeme as it's spelled in diagnostics
+    fn get_lexeme_text(&self) -> String {
+        self.get_lexeme().to_source_string(self.lexer.get_names())
+    }
+
+    fn get_current(&self) -> Lexeme {
+        self.lexer.get_current()
+    }
+
     fn get_ahead_lexeme(&self) -> LexType {
         self.lexer.get_ahead().get_type()
     }
@@ -72,1123 +237,3027 @@ impl<'src_lf> Parser<'src_lf> {
     }
 
     fn next_lexeme(&mut self) {
-        self.lexer.next(true);
+        loop {
+            let lexeme = self.lexer.next(false);
+
+            match lexeme.get_type() {
+                LexType::Comment(content) => {
+                    if self.options.get_capture_comments() {
+                        self.comments
+                            .push(Comment::new(lexeme.get_type(), lexeme.get_location()));
+                    }
+
+                    // comments starting with ! are hot comments and contain directives
+                    if let Some(content) = content.strip_prefix('!') {
+                        self.hot_comments.push(HotComment::new(
+                            self.hot_comment_header,
+                            lexeme.get_location(),
+                            String::from(content.trim_end()),
+                        ));
+                    }
+                }
+                LexType::BlockComment => {
+                    if self.options.get_capture_comments() {
+                        self.comments
+                            .push(Comment::new(lexeme.get_type(), lexeme.get_location()));
+                    }
+                }
+                LexType::BrokenComment => {
+                    if self.options.get_capture_comments() {
+                        self.comments
+                            .push(Comment::new(lexeme.get_type(), lexeme.get_location()));
+                    }
+
+                    self.report_lexeme_error(&lexeme, ParseErrorCode::MalformedComment);
+                }
+                // characters that can't start a lexeme are reported and skipped
+                LexType::Error | LexType::BrokenUnicode => {
+                    self.report_lexeme_error(&lexeme, ParseErrorCode::InvalidCharacter);
+                }
+                _ => break,
+            }
+        }
     }
 
-    fn parse_string_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        match self.get_lexeme() {
-            LexType::RawString(value) => {
+    fn report_lexeme_error(&mut self, lexeme: &Lexeme, code: ParseErrorCode) -> usize {
+        let message = lexeme.get_error().unwrap_or("Malformed lexeme").to_owned();
+
+        self.report(lexeme.get_location(), code, &message)
+    }
+
+    fn expect_and_consume_fail(&mut self, type_: LexType, context: Option<&str>) {
+        let message = if let Some(context) = context {
+            format!(
+                "Expected {} when parsing {}, got {}",
+                type_,
+                context,
+                self.get_lexeme_text()
+            )
+        } else {
+            format!("Expected {}, got {}", type_, self.get_lexeme_text())
+        };
+
+        self.report(
+            self.get_location(),
+            ParseErrorCode::UnexpectedToken,
+            &message,
+        );
+    }
+
+    fn expect_and_consume(&mut self, type_: LexType, context: &str) -> bool {
+        if self.get_lexeme().ne(&type_) {
+            self.expect_and_consume_fail(type_.clone(), Some(context));
+
+            // check if this is an extra token and the expected token is next
+            if self.get_ahead_lexeme().eq(&type_) {
+                // skip invalid and consume expected
                 self.next_lexeme();
-                Ok(new_constant_string(self.get_location(), value))
-            }
-            LexType::QuotedString(value) => {
                 self.next_lexeme();
-                Ok(new_constant_string(self.get_location(), value))
             }
-            _ => Err(self.report_expr_error("String literal contains malformed escape sequence")),
+
+            false
+        } else {
+            self.next_lexeme();
+
+            true
         }
     }
 
-    fn parse_nil_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        let location = self.get_location();
+    fn expect_match_and_consume_fail(&mut self, type_: LexType, begin: &Lexeme) {
+        let begin_position = begin.get_location().get_begin();
+        let message = if self.get_location().get_begin().get_line() != begin_position.get_line() {
+            format!(
+                "Expected {} (to close {} at line {}), got {}",
+                type_,
+                begin.get_type(),
+                begin_position.get_line() + 1,
+                self.get_lexeme_text()
+            )
+        } else {
+            format!(
+                "Expected {} (to close {} at column {}), got {}",
+                type_,
+                begin.get_type(),
+                begin_position.get_column() + 1,
+                self.get_lexeme_text()
+            )
+        };
 
-        if self.get_lexeme().eq(&LexType::Nil) {
-            self.next_lexeme();
+        self.report_error(
+            ParseError::new(
+                ParseErrorCode::UnmatchedDelimiter,
+                self.get_location(),
+                &message,
+            )
+            .with_label(
+                begin.get_location(),
+                &format!("{} opened here", begin.get_type()),
+            ),
+        );
+    }
+
+    fn expect_match_and_consume(&mut self, type_: LexType, begin: &Lexeme) -> bool {
+        if self.get_lexeme().ne(&type_) {
+            self.expect_match_and_consume_fail(type_.clone(), begin);
 
-            Ok(new_constant_nil(location))
+            // check if this is an extra token and the expected token is next
+            if self.get_ahead_lexeme().eq(&type_) {
+                // skip invalid and consume expected
+                self.next_lexeme();
+                self.next_lexeme();
+
+                return true;
+            }
+
+            false
         } else {
             self.next_lexeme();
 
-            Err(self.report_expr_error("unexpected nil"))
+            true
         }
     }
 
-    fn parse_bool_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        let location = self.get_location();
+    // same as expect_match_and_consume, but on failure searches for the expected token until the end of the line
+    fn expect_match_and_consume_search(&mut self, type_: LexType, begin: &Lexeme) -> bool {
+        if self.get_lexeme().ne(&type_) {
+            self.expect_match_and_consume_fail(type_.clone(), begin);
 
-        match self.get_lexeme() {
-            LexType::True => {
-                self.next_lexeme();
+            // previous location is taken because 'current' lexeme is already the next token
+            let current_line = self.get_previous_location().get_end().get_line();
 
-                Ok(new_constant_bool(location, true))
-            }
-            LexType::False => {
+            // stop on tokens that can be handled by the parsing functions above the current one
+            while self.get_location().get_begin().get_line() == current_line
+                && self.get_lexeme().ne(&type_)
+                && self.match_recovery_stop_on_token[self.get_lexeme().code() as usize] == 0
+            {
                 self.next_lexeme();
-
-                Ok(new_constant_bool(location, false))
             }
-            _ => {
+
+            if self.get_lexeme().eq(&type_) {
                 self.next_lexeme();
 
-                Err(self.report_expr_error("unexpected bool"))
+                return true;
             }
-        }
-    }
 
-    fn parse_binary<'a>(clear_value_chars: Chars<'a>) -> Result<f64, ()> {
-        let mut value: i64 = 0;
-        for c in clear_value_chars {
-            value = (value << 1)
-                + match c {
-                    '0' => 0,
-                    '1' => 1,
-                    _ => return Err(()),
-                }
+            false
+        } else {
+            self.next_lexeme();
+
+            true
         }
-        Ok(value as f64)
     }
 
-    fn parse_hex<'a>(clear_value_chars: Chars<'a>) -> Result<f64, ()> {
-        let mut value: i64 = 0;
-
-        for c in clear_value_chars {
-            value = (value << 4)
-                + i64::from(match c {
-                    ('0'..='9') => u32::from(c) - u32::from('0'),
-                    ('a'..='f') => u32::from(c) - u32::from('a') + 10,
-                    ('A'..='F') => u32::from(c) - u32::from('A') + 10,
-                    _ => return Err(()),
-                })
-        }
-        Ok(value as f64)
+    fn block_follow(&self) -> bool {
+        matches!(
+            self.get_lexeme(),
+            LexType::Eof | LexType::Else | LexType::ElseIf | LexType::End | LexType::Until
+        )
     }
 
-    fn parse_number_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        if let LexType::Number(value) = self.get_lexeme() {
-            let location = self.get_location();
-            self.next_lexeme();
+    fn push_local(&mut self, binding: Binding) -> LocalId {
+        let (name, location, annotation) = binding;
+        let (_, loop_depth) = *self.function_stack.last().unwrap();
 
-            let clear_value = value.replace("_", "");
+        let local = AstLocal::new(
+            name,
+            location,
+            self.local_map.get(&name).copied(),
+            self.function_stack.len() - 1,
+            loop_depth,
+            annotation,
+        );
 
-            let dec_fn = || {
-                if let Ok(result) = clear_value.parse::<f64>() {
-                    Ok(result)
-                } else {
-                    Err(())
-                }
-            };
+        let id = self.arena.alloc(local);
+        self.local_map.insert(name, id);
+        self.local_stack.push(id);
 
-            let mut clear_value_chars = clear_value.chars();
-            Ok(new_constant_number(
-                location,
-                if clear_value_chars.next().eq(&Some('0')) {
-                    match clear_value_chars.next() {
-                        Some('b') | Some('B') => {
-                            if let Ok(value) = Parser::parse_binary(clear_value_chars) {
-                                value
-                            } else {
-                                return Err(self.report_expr_error("unexpected binary number"));
-                            }
-                        }
-                        Some('x') | Some('X') => {
-                            if let Ok(value) = Parser::parse_hex(clear_value_chars) {
-                                value
-                            } else {
-                                return Err(self.report_expr_error("unexpected hex number"));
-                            }
-                        }
-                        _ => {
-                            if let Ok(value) = dec_fn() {
-                                value
-                            } else {
-                                return Err(self.report_expr_error("unexpected dec number"));
-                            }
-                        }
-                    }
-                } else {
-                    if let Ok(value) = dec_fn() {
-                        value
-                    } else {
-                        return Err(self.report_expr_error("unexpected dec number"));
-                    }
-                },
-            ))
-        } else {
-            Err(self.report_expr_error("unexpected number"))
-        }
+        id
     }
 
-    fn parse_unary_operator(&self, lexeme: LexType) -> Option<UnaryOperator> {
-        Some(match lexeme {
-            LexType::Not => UnaryOperator::Not,
-            LexType::Sub => UnaryOperator::Minus,
-            LexType::Sharp => UnaryOperator::Len,
-            _ => return None,
-        })
+    fn save_locals(&self) -> usize {
+        self.local_stack.len()
     }
 
-    fn parse_binary_operator(&self, lexeme: LexType) -> Option<BinaryOperator> {
-        Some(match lexeme {
-            LexType::Add => BinaryOperator::Add,
-            LexType::Sub => BinaryOperator::Sub,
-            LexType::Mul => BinaryOperator::Mul,
-            LexType::Div => BinaryOperator::Div,
-            LexType::Mod => BinaryOperator::Mod,
-            LexType::Pow => BinaryOperator::Pow,
-            LexType::Dot2 => BinaryOperator::Concat,
-            LexType::NotEqual => BinaryOperator::NotEqual,
-            LexType::Equal => BinaryOperator::Equal,
-            LexType::Less => BinaryOperator::Less,
-            LexType::LessEqual => BinaryOperator::LessEqual,
-            LexType::Greater => BinaryOperator::Greater,
-            LexType::GreaterEqual => BinaryOperator::GreaterEqual,
-            LexType::And => BinaryOperator::And,
-            LexType::Or => BinaryOperator::Or,
-            _ => return None,
-        })
+    fn restore_locals(&mut self, offset: usize) {
+        for id in self.local_stack.drain(offset..).rev() {
+            let local = &self.arena[id];
+
+            if let Some(shadow) = local.get_shadow() {
+                self.local_map.insert(local.get_name(), shadow);
+            } else {
+                self.local_map.remove(&local.get_name());
+            }
+        }
     }
 
-    fn parse_name(&mut self) -> Result<(AstName, LexLocation), Box<AstExpr>> {
-        if let LexType::Name(value) = self.get_lexeme() {
-            let location = self.get_location();
-            self.next_lexeme();
+    fn parse_chunk(&mut self) -> AstStatId {
+        let result = self.parse_block();
 
-            Ok((AstName::new(value), location))
-        } else {
-            Err(self.report_expr_error("unexpected name"))
+        if self.get_lexeme().ne(&LexType::Eof) {
+            self.expect_and_consume_fail(LexType::Eof, None);
         }
+
+        result
     }
 
-    fn parse_name_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        let (name, location) = self.parse_name()?;
+    fn parse_block(&mut self) -> AstStatId {
+        let locals_begin = self.save_locals();
 
-        Ok(if let Some(local) = self.local_map.get(&name) {
-            ExprLocal::new(
-                location,
-                local.clone(),
-                local
-                    .get_function_depth()
-                    .ne(&(self.function_stack.len() - 1)),
-            )
-        } else {
-            new_expr_global(location, name)
-        })
+        let result = self.parse_block_no_scope();
+
+        self.restore_locals(locals_begin);
+
+        result
     }
 
-    fn parse_prefix_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        if self.get_lexeme().eq(&LexType::LeftRoundBracket) {
-            let start = self.get_location();
-            self.next_lexeme();
-            let expr = self.parse_expr(0)?;
-            let end = self.get_location();
+    // chunk ::= {stat [`;']} [laststat [`;']]
+    // block ::= chunk
+    fn parse_block_no_scope(&mut self) -> AstStatId {
+        let mut body: Vec<AstStatId> = Vec::new();
+        let begin = self.get_previous_location().get_end();
+
+        let recursion_counter_old = self.recursion_counter;
+        if let Some(message_index) = self.increment_recursion_counter("block") {
+            self.recursion_counter = recursion_counter_old;
+
+            return self.add(StatError::new(
+                self.get_location(),
+                Vec::new(),
+                Vec::new(),
+                message_index,
+            ));
+        }
 
-            if self.get_lexeme().ne(&LexType::RightRoundBracket) {
-                Err(self.report_expr_error("unexpected right round bracket"))
-            } else {
-                self.next_lexeme();
+        while !self.block_follow() {
+            let stat = self.parse_stat();
 
-                Ok(new_expr_group(
-                    LexLocation::new(start.get_begin(), end.get_end()),
-                    expr,
-                ))
+            if self.get_lexeme().eq(&LexType::Semicolon) {
+                self.next_lexeme();
+                self.arena[stat].set_has_semicolon(true);
             }
-        } else {
-            self.parse_name_expr()
-        }
-    }
 
-    fn parse_table_constructor(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        let mut items: Vec<TableItem> = Vec::new();
-        let start = self.get_location().get_begin();
+            let is_last_stat = matches!(
+                self.arena[stat].get_payload_type(),
+                AstNodePayloadType::StatBreak
+                    | AstNodePayloadType::StatContinue
+                    | AstNodePayloadType::StatReturn
+            );
+            body.push(stat);
 
-        if self.get_lexeme().ne(&LexType::LeftCurlyBracket) {
-            return Err(self.report_expr_error("unexpected table constructor"));
+            if is_last_stat {
+                break;
+            }
         }
-        self.next_lexeme();
-
-        while self.get_lexeme().ne(&LexType::RightCurlyBracket) {
-            match self.get_lexeme() {
-                LexType::LeftSquareBracket => {
-                    self.next_lexeme();
 
-                    let key = self.parse_expr(0)?;
+        self.recursion_counter = recursion_counter_old;
 
-                    if self.get_lexeme().ne(&LexType::RightSquareBracket) {
-                        return Err(self.report_expr_error("unexpected table constructor"));
-                    }
-                    self.next_lexeme();
+        self.add(new_stat_block(
+            LexLocation::new(begin, self.get_location().get_begin()),
+            body,
+        ))
+    }
 
-                    if self.get_lexeme().ne(&LexType::Assign) {
-                        return Err(self.report_expr_error("unexpected table constructor"));
-                    }
-                    self.next_lexeme();
+    fn parse_stat(&mut self) -> AstStatId {
+        match self.get_lexeme() {
+            LexType::If => self.parse_if_stat(),
+            LexType::While => self.parse_while_stat(),
+            LexType::Do => self.parse_do_stat(),
+            LexType::For => self.parse_for_stat(),
+            LexType::Repeat => self.parse_repeat_stat(),
+            LexType::Function => self.parse_function_stat(),
+            LexType::Local => self.parse_local_stat(),
+            LexType::Return => self.parse_return_stat(),
+            LexType::Break => self.parse_break_stat(),
+            _ => self.parse_expr_stat(),
+        }
+    }
 
-                    let value = self.parse_expr(0)?;
+    // if exp then block {elseif exp then block} [else block] end
+    fn parse_if_stat(&mut self) -> AstStatId {
+        let begin = self.get_location().get_begin();
+        self.next_lexeme(); // skip if / elseif
 
-                    items.push(TableItem::new(TableKind::General, key, value));
-                }
-                LexType::Name(_) => {
-                    if self.get_ahead_lexeme().eq(&LexType::Assign) {
-                        let (name, name_location) = self.parse_name()?;
+        let condition = self.parse_expr(0);
 
-                        // skip '='
-                        self.next_lexeme();
+        let match_then = self.get_current();
+        let has_then = self.expect_and_consume(LexType::Then, "if statement");
 
-                        let key = new_constant_string(name_location, name.get_value());
-                        let value = self.parse_expr(0)?;
+        let then_body = self.parse_block();
 
-                        items.push(TableItem::new(TableKind::Record, key, value));
-                    } else {
-                        let expr = self.parse_expr(0)?;
+        let mut else_body: Option<AstStatId> = None;
+        let mut else_location: Option<LexLocation> = None;
+        let end;
+        let mut has_end = false;
 
-                        items.push(TableItem::new(TableKind::List, AstExpr::new_nil(), expr));
-                    }
-                }
-                _ => {
-                    let expr = self.parse_expr(0)?;
+        if self.get_lexeme().eq(&LexType::ElseIf) {
+            else_location = Some(self.get_location());
 
-                    items.push(TableItem::new(TableKind::List, AstExpr::new_nil(), expr));
-                }
-            }
+            let else_if = self.parse_if_stat();
+            end = self.arena[else_if].get_location();
+            else_body = Some(else_if);
+        } else {
+            let mut match_then_else = match_then.clone();
 
-            if self.get_lexeme().eq(&LexType::Comma) || self.get_lexeme().eq(&LexType::Semicolon) {
+            if self.get_lexeme().eq(&LexType::Else) {
+                else_location = Some(self.get_location());
+                match_then_else = self.get_current();
                 self.next_lexeme();
+
+                let body = self.parse_block();
+                let location = LexLocation::new(
+                    match_then_else.get_location().get_end(),
+                    self.arena[body].get_location().get_end(),
+                );
+                self.arena[body].set_location(location);
+                else_body = Some(body);
             }
-        }
 
-        if self.get_lexeme().ne(&LexType::RightCurlyBracket) {
-            return Err(self.report_expr_error("unexpected table constructor"));
+            end = self.get_location();
+            has_end = self.expect_match_and_consume(LexType::End, &match_then_else);
         }
-        self.next_lexeme();
-
-        let end = self.get_location().get_end();
 
-        Ok(new_expr_table(LexLocation::new(start, end), items))
+        self.add(StatIf::new(
+            LexLocation::new(begin, end.get_end()),
+            condition,
+            then_body,
+            else_body,
+            has_then,
+            match_then.get_location(),
+            else_location,
+            has_end,
+        ))
     }
 
-    fn parse_expr_list(&mut self, args: &mut Vec<Box<AstExpr>>) -> Result<(), Box<AstExpr>> {
-        args.push(self.parse_expr(0)?);
+    // while exp do block end
+    fn parse_while_stat(&mut self) -> AstStatId {
+        let begin = self.get_location().get_begin();
+        self.next_lexeme(); // skip while
 
-        while self.get_lexeme().eq(&LexType::Comma) {
-            self.next_lexeme();
-            args.push(self.parse_expr(0)?);
-        }
+        let condition = self.parse_expr(0);
+
+        let match_do = self.get_current();
+        let has_do = self.expect_and_consume(LexType::Do, "while loop");
+
+        self.function_stack.last_mut().unwrap().1 += 1;
+        let body = self.parse_block();
+        self.function_stack.last_mut().unwrap().1 -= 1;
+
+        let end = self.get_location();
+        let has_end = self.expect_match_and_consume(LexType::End, &match_do);
 
-        Ok(())
+        self.add(StatWhile::new(
+            LexLocation::new(begin, end.get_end()),
+            condition,
+            body,
+            has_do,
+            match_do.get_location(),
+            has_end,
+        ))
     }
 
-    fn parse_function_args_expr(
-        &mut self,
-        func: Box<AstExpr>,
-        has_self: bool,
-        self_location: LexLocation,
-    ) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        Ok(match self.get_lexeme() {
-            // <func>(<arg>[,<arg>])
-            LexType::LeftRoundBracket => {
-                let arg_start = self.get_location().get_end();
-                self.next_lexeme();
+    // repeat block until exp
+    fn parse_repeat_stat(&mut self) -> AstStatId {
+        let begin = self.get_location().get_begin();
 
-                let mut args: Vec<Box<AstExpr>> = Vec::new();
-                if self.get_lexeme().ne(&LexType::RightRoundBracket) {
-                    self.parse_expr_list(&mut args)?;
-                }
-                let end = self.get_location();
-                let arg_end = end.get_end();
-                self.next_lexeme();
+        let match_repeat = self.get_current();
+        self.next_lexeme(); // skip repeat
 
-                ExprCall::new(
-                    LexLocation::new(func.get_location().get_begin(), end.get_end()),
-                    func,
-                    args,
-                    has_self,
-                    LexLocation::new(arg_start, arg_end),
-                )
-            }
-            // <func>{<table>}
-            LexType::LeftCurlyBracket => {
-                let arg_start = self.get_location().get_end();
-                let expr = self.parse_table_constructor()?;
-                let arg_end = self.get_previous_location().get_end();
+        let locals_begin = self.save_locals();
 
-                ExprCall::new(
-                    LexLocation::new(
-                        func.get_location().get_begin(),
-                        expr.get_location().get_end(),
-                    ),
-                    func,
-                    vec![expr],
-                    has_self,
-                    LexLocation::new(arg_start, arg_end),
-                )
-            }
-            // <func>"string"
-            LexType::RawString(_) | LexType::QuotedString(_) => {
-                let arg_location = self.get_location();
-                let expr = self.parse_string_expr()?;
+        self.function_stack.last_mut().unwrap().1 += 1;
+        let body = self.parse_block_no_scope();
+        self.function_stack.last_mut().unwrap().1 -= 1;
 
-                ExprCall::new(
-                    LexLocation::new(
-                        func.get_location().get_begin(),
-                        expr.get_location().get_end(),
-                    ),
-                    func,
-                    vec![expr],
-                    has_self,
-                    arg_location,
-                )
-            }
-            _ => return Err(self.report_expr_error("unexpected function call")),
-        })
+        let has_until = self.expect_match_and_consume(LexType::Until, &match_repeat);
+
+        // the until condition can see the locals declared in the loop body
+        let condition = self.parse_expr(0);
+
+        self.restore_locals(locals_begin);
+
+        self.add(StatRepeat::new(
+            LexLocation::new(begin, self.arena[condition].get_location().get_end()),
+            condition,
+            body,
+            has_until,
+        ))
     }
 
-    fn parse_primary_expr(&mut self, as_statement: bool) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        let start = self.get_location();
-        let mut expr = self.parse_prefix_expr()?;
-        Ok(loop {
-            match self.get_lexeme() {
-                // <expr>.<index name>
-                LexType::Dot => {
-                    let op_position = self.get_location().get_begin();
-                    let (index_name, index_location) = self.parse_name()?;
+    // do block end
+    fn parse_do_stat(&mut self) -> AstStatId {
+        let begin = self.get_location().get_begin();
 
-                    expr = ExprIndexName::new(
-                        LexLocation::new(start.get_begin(), index_location.get_end()),
-                        expr,
-                        index_name,
-                        index_location,
-                        op_position,
-                        '.',
-                    );
-                }
-                // <expr>[<index_expr>]
-                LexType::LeftSquareBracket => {
-                    self.next_lexeme();
-                    let index = self.parse_expr(0)?;
-                    let end = self.get_location();
-                    if self.get_lexeme().ne(&LexType::RightSquareBracket) {
-                        return Err(self.report_expr_error("unexpected ]"));
-                    }
-                    self.next_lexeme();
+        let match_do = self.get_current();
+        self.next_lexeme(); // skip do
 
-                    expr = ExprIndexExpr::new(
-                        LexLocation::new(start.get_begin(), end.get_end()),
-                        expr,
-                        index,
-                    );
-                }
-                // <expr>:<index_name>
-                LexType::Colon => {
-                    let op_position = self.get_location().get_begin();
-                    self.next_lexeme();
+        let body = self.parse_block();
+        let location = LexLocation::new(begin, self.arena[body].get_location().get_end());
+        self.arena[body].set_location(location);
 
-                    let (index, index_location) = self.parse_name()?;
-                    let func = ExprIndexName::new(
-                        LexLocation::new(start.get_begin(), index_location.get_end()),
-                        expr,
-                        index,
-                        index_location,
-                        op_position,
-                        ':',
-                    );
+        self.expect_match_and_consume(LexType::End, &match_do);
 
-                    expr = self.parse_function_args_expr(func, true, index_location)?;
-                }
-                // <expr>(...) | {<table>} | "<string>"
-                LexType::LeftRoundBracket
-                | LexType::LeftCurlyBracket
-                | LexType::QuotedString(_)
-                | LexType::RawString(_) => {
-                    expr = self.parse_function_args_expr(expr, false, LexLocation::zero())?;
-                }
-                _ => break expr,
-            }
-        })
+        body
     }
 
-    fn parse_if_else_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        let mut has_else = false;
-        let start = self.get_location().get_begin();
-        self.next_lexeme();
+    // break
+    fn parse_break_stat(&mut self) -> AstStatId {
+        let location = self.get_location();
+        self.next_lexeme(); // skip break
 
-        let condition = self.parse_expr(0)?;
+        let (_, loop_depth) = *self.function_stack.last().unwrap();
+        if loop_depth.eq(&0) {
+            let stat = self.add(new_stat_break(location));
 
-        if self.get_lexeme().ne(&LexType::Then) {
-            return Err(self.report_expr_error("unexpected then"));
+            return self.report_stat_error(
+                location,
+                ParseErrorCode::BreakOutsideLoop,
+                Vec::new(),
+                vec![stat],
+                "break statement must be inside a loop",
+            );
         }
-        self.next_lexeme();
-        let true_expr = self.parse_expr(0)?;
 
-        Ok(match self.get_lexeme() {
-            LexType::ElseIf => {
-                let false_expr = self.parse_if_else_expr()?;
+        self.add(new_stat_break(location))
+    }
 
-                ExprIfElse::new(
-                    LexLocation::new(start, false_expr.get_location().get_end()),
-                    condition,
-                    Some(true_expr),
-                    Some(false_expr),
-                )
-            }
-            LexType::Else => {
-                self.next_lexeme();
+    // continue
+    fn parse_continue_stat(&mut self, location: LexLocation) -> AstStatId {
+        // note: the token is already parsed for us
+        let (_, loop_depth) = *self.function_stack.last().unwrap();
+        if loop_depth.eq(&0) {
+            let stat = self.add(new_stat_continue(location));
+
+            return self.report_stat_error(
+                location,
+                ParseErrorCode::ContinueOutsideLoop,
+                Vec::new(),
+                vec![stat],
+                "continue statement must be inside a loop",
+            );
+        }
 
-                let false_expr = self.parse_expr(0)?;
+        self.add(new_stat_continue(location))
+    }
 
-                ExprIfElse::new(
-                    LexLocation::new(start, false_expr.get_location().get_end()),
-                    condition,
-                    Some(true_expr),
-                    Some(false_expr),
-                )
-            }
-            LexType::End => {
+    // for binding `=' exp `,' exp [`,' exp] do block end |
+    // for bindinglist in explist do block end |
+    fn parse_for_stat(&mut self) -> AstStatId {
+        let begin = self.get_location().get_begin();
+        self.next_lexeme(); // skip for
+
+        let var_name = self.parse_binding();
+
+        if self.get_lexeme().eq(&LexType::Assign) {
+            self.next_lexeme();
+
+            let from = self.parse_expr(0);
+            self.expect_and_consume(LexType::Comma, "index range");
+            let to = self.parse_expr(0);
+
+            let mut step: Option<AstExprId> = None;
+            if self.get_lexeme().eq(&LexType::Comma) {
                 self.next_lexeme();
 
-                ExprIfElse::new(
-                    LexLocation::new(start, self.get_location().get_end()),
-                    condition,
-                    Some(true_expr),
-                    None,
-                )
+                step = Some(self.parse_expr(0));
             }
-            _ => return Err(self.report_expr_error("unexpected if then expr")),
-        })
-    }
 
-    fn parse_simple_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        let start = self.get_location();
+            let match_do = self.get_current();
+            let has_do = self.expect_and_consume(LexType::Do, "for loop");
 
-        match self.get_lexeme() {
-            LexType::Nil => self.parse_nil_expr(),
-            LexType::True | LexType::False => self.parse_bool_expr(),
-            LexType::QuotedString(_) | LexType::RawString(_) | LexType::BrokenString => {
-                self.parse_string_expr()
-            }
-            LexType::Number(_) => self.parse_number_expr(),
-            // TODO impl function
-            LexType::Function => Err(self.report_expr_error("todo")),
-            LexType::Dot3 => {
+            let locals_begin = self.save_locals();
+
+            let var = self.push_local(var_name);
+
+            self.function_stack.last_mut().unwrap().1 += 1;
+            let body = self.parse_block();
+            self.function_stack.last_mut().unwrap().1 -= 1;
+
+            self.restore_locals(locals_begin);
+
+            let end = self.get_location();
+            let has_end = self.expect_match_and_consume(LexType::End, &match_do);
+
+            self.add(StatFor::new(
+                LexLocation::new(begin, end.get_end()),
+                var,
+                from,
+                to,
+                step,
+                body,
+                has_do,
+                match_do.get_location(),
+                has_end,
+            ))
+        } else {
+            let mut names = vec![var_name];
+
+            if self.get_lexeme().eq(&LexType::Comma) {
                 self.next_lexeme();
 
-                if let Some(function_stack) = self.function_stack.last() {
-                    Ok(new_expr_varargs(start))
-                } else {
-                    Err(self.report_expr_error("unexpected ..."))
-                }
+                self.parse_binding_list(&mut names, false);
             }
-            LexType::LeftCurlyBracket => self.parse_table_constructor(),
-            LexType::If => self.parse_if_else_expr(),
-            _ => self.parse_primary_expr(false),
+
+            let in_location = self.get_location();
+            let has_in = self.expect_and_consume(LexType::In, "for loop");
+
+            let mut values: Vec<AstExprId> = Vec::new();
+            self.parse_expr_list(&mut values);
+
+            let match_do = self.get_current();
+            let has_do = self.expect_and_consume(LexType::Do, "for loop");
+
+            let locals_begin = self.save_locals();
+
+            let vars = names
+                .into_iter()
+                .map(|name| self.push_local(name))
+                .collect::<Vec<LocalId>>();
+
+            self.function_stack.last_mut().unwrap().1 += 1;
+            let body = self.parse_block();
+            self.function_stack.last_mut().unwrap().1 -= 1;
+
+            self.restore_locals(locals_begin);
+
+            let end = self.get_location();
+            let has_end = self.expect_match_and_consume(LexType::End, &match_do);
+
+            self.add(StatForIn::new(
+                LexLocation::new(begin, end.get_end()),
+                vars,
+                values,
+                body,
+                has_in,
+                in_location,
+                has_do,
+                match_do.get_location(),
+                has_end,
+            ))
         }
     }
 
-    fn parse_assertion_expr(&mut self) -> Result<Box<AstExpr>, Box<AstExpr>> {
+    // function funcname funcbody
+    // funcname ::= Name {`.' Name} [`:' Name]
+    fn parse_function_stat(&mut self) -> AstStatId {
         let begin = self.get_location().get_begin();
-        let expr = self.parse_simple_expr()?;
-
-        Ok(if self.get_lexeme().eq(&LexType::DoubleColon) {
-            self.next_lexeme();
 
-            let annotation = self.parse_type_annotation()?;
+        let match_function = self.get_current();
+        self.next_lexeme(); // skip function
 
-            ExprTypeAssertion::new(
-                LexLocation::new(begin, annotation.get_location().get_end()),
-                expr,
-                annotation,
-            )
+        let mut debug_name = if let LexType::Name(name) = self.get_lexeme() {
+            name
         } else {
-            expr
-        })
-    }
+            self.intern("")
+        };
 
-    fn parse_expr(&mut self, limit: usize) -> Result<Box<AstExpr>, Box<AstExpr>> {
-        let start = self.get_location();
+        // parse funcname into a chain of indexing operators
+        let mut expr = self.parse_name_expr("function name");
 
-        let mut expr = if let Some(operator) = self.parse_unary_operator(self.get_lexeme()) {
+        while self.get_lexeme().eq(&LexType::Dot) {
+            let op_position = self.get_location().get_begin();
             self.next_lexeme();
-            let sub_expr = self.parse_expr(8)?;
 
-            ExprUnary::new(
-                LexLocation::new(start.get_begin(), sub_expr.get_location().get_end()),
-                operator,
-                sub_expr,
-            )
-        } else {
-            self.parse_assertion_expr()?
-        };
+            let (name, name_location) = self.parse_name("field name");
 
-        let mut operator = self.parse_binary_operator(self.get_lexeme());
-        while operator.is_some() {
+            debug_name = name;
+            expr = self.add(ExprIndexName::new(
+                LexLocation::new(begin, name_location.get_end()),
+                expr,
+                name,
+                name_location,
+                op_position,
+                '.',
+            ));
+        }
+
+        // finish with :
+        let mut has_self = false;
+        if self.get_lexeme().eq(&LexType::Colon) {
+            let op_position = self.get_location().get_begin();
             self.next_lexeme();
 
-            let next = self.parse_expr(limit)?;
-            expr = ExprBinary::new(
-                LexLocation::new(start.get_begin(), next.get_location().get_end()),
-                operator.unwrap(),
+            let (name, name_location) = self.parse_name("method name");
+
+            debug_name = name;
+            expr = self.add(ExprIndexName::new(
+                LexLocation::new(begin, name_location.get_end()),
                 expr,
-                next,
-            );
+                name,
+                name_location,
+                op_position,
+                ':',
+            ));
 
-            operator = self.parse_binary_operator(self.get_lexeme());
+            has_self = true;
         }
 
-        Ok(expr)
+        let (function, location) = self.parse_function_body(has_self, &match_function, debug_name);
+
+        self.add(StatFunction::new(
+            LexLocation::new(begin, location.get_end()),
+            expr,
+            function,
+        ))
     }
 
-    fn parse_nil_type(&mut self) -> Result<Box<AstType>, Box<AstType>> {
-        let location = self.get_location();
+    // local function Name funcbody |
+    // local bindinglist [`=' explist]
+    fn parse_local_stat(&mut self) -> AstStatId {
+        let begin = self.get_location().get_begin();
+        self.next_lexeme(); // skip local
 
-        if self.get_lexeme().eq(&LexType::Nil) {
+        if self.get_lexeme().eq(&LexType::Function) {
+            let match_function = self.get_current();
             self.next_lexeme();
 
-            Ok(TypeReference::new(
-                location,
-                None,
-                AstName::new(String::from("nil")),
-                None,
+            let (name, name_location) = self.parse_name("variable name");
+
+            let var = self.push_local((name, name_location, None));
+
+            let (function, location) = self.parse_function_body(false, &match_function, name);
+
+            self.add(StatLocalFunction::new(
+                LexLocation::new(begin, location.get_end()),
+                var,
+                function,
             ))
         } else {
-            Err(self.report_type_error(true, "unexpected nil type reference"))
-        }
-    }
+            let mut names: Vec<Binding> = Vec::new();
+            self.parse_binding_list(&mut names, false);
 
-    fn parse_bool_type(&mut self) -> Result<Box<AstType>, Box<AstType>> {
-        let location = self.get_location();
+            let mut values: Vec<AstExprId> = Vec::new();
+            let mut equals_sign_location: Option<LexLocation> = None;
 
-        match self.get_lexeme() {
-            LexType::True => {
+            if self.get_lexeme().eq(&LexType::Assign) {
+                equals_sign_location = Some(self.get_location());
                 self.next_lexeme();
 
-                Ok(new_type_singleton_bool(location, true))
+                self.parse_expr_list(&mut values);
             }
-            LexType::False => {
-                self.next_lexeme();
 
-                Ok(new_type_singleton_bool(location, false))
-            }
-            _ => {
-                self.next_lexeme();
+            let vars = names
+                .into_iter()
+                .map(|name| self.push_local(name))
+                .collect::<Vec<LocalId>>();
 
-                Err(self.report_type_error(true, "unexpected type singleton bool"))
-            }
+            let end = if let Some(value) = values.last() {
+                self.arena[*value].get_location()
+            } else {
+                self.get_previous_location()
+            };
+
+            self.add(StatLocal::new(
+                LexLocation::new(begin, end.get_end()),
+                vars,
+                values,
+                equals_sign_location,
+            ))
         }
     }
 
-    fn parse_string_type(&mut self) -> Result<Box<AstType>, Box<AstType>> {
+    // return [explist]
+    fn parse_return_stat(&mut self) -> AstStatId {
         let location = self.get_location();
+        self.next_lexeme(); // skip return
 
-        match self.get_lexeme() {
-            LexType::RawString(value) => {
-                self.next_lexeme();
-
-                Ok(new_type_singleton_string(location, value))
-            }
-            LexType::QuotedString(value) => {
-                self.next_lexeme();
+        let mut list: Vec<AstExprId> = Vec::new();
 
-                Ok(new_type_singleton_string(location, value))
-            }
-            LexType::BrokenString => {
-                self.next_lexeme();
+        if !self.block_follow() && self.get_lexeme().ne(&LexType::Semicolon) {
+            self.parse_expr_list(&mut list);
+        }
 
-                Err(self.report_type_error(false, "unexpected type singleton string broken string"))
-            }
-            _ => {
-                self.next_lexeme();
+        let end = if let Some(expr) = list.last() {
+            self.arena[*expr].get_location()
+        } else {
+            location
+        };
 
-                Err(self.report_type_error(true, "unexpected type singleton string"))
-            }
-        }
+        self.add(StatReturn::new(
+            LexLocation::new(location.get_begin(), end.get_end()),
+            list,
+        ))
     }
 
-    fn parse_typeof_type(&mut self, begin: LexPosition) -> Result<Box<AstType>, Box<AstType>> {
-        if self.get_lexeme().ne(&LexType::LeftRoundBracket) {
-            return Err(self.report_type_error(false, "unexpected typeof"));
-        }
-        self.next_lexeme();
+    // type Name [`<' varlist `>'] `=' typeannotation
+    fn parse_type_alias_stat(&mut self, begin: LexPosition, exported: bool) -> AstStatId {
+        // note: `type` token is already parsed for us, so we just need to parse the rest
+        let (name, _) = self.parse_name("type name");
 
-        let expr = self.parse_expr(0)?;
-        let end = self.get_location().get_end();
+        let (generics, generic_packs) = self.parse_generic_type_list(true);
 
-        if self.get_lexeme().ne(&LexType::RightRoundBracket) {
-            return Err(self.report_type_error(false, "unexpected typeof"));
-        }
-        self.next_lexeme();
+        self.expect_and_consume(LexType::Assign, "type alias");
 
-        Ok(new_type_typeof(LexLocation::new(begin, end), expr))
-    }
+        let type_ = self.parse_type_annotation();
 
-    fn should_parse_type_pack_annotation(&self) -> bool {
-        match self.get_lexeme() {
-            LexType::Dot3 => true,
-            LexType::Name(_) => self.get_ahead_lexeme().eq(&LexType::Dot3),
-            _ => false,
-        }
+        self.add(StatTypeAlias::new(
+            LexLocation::new(begin, self.arena[type_].get_location().get_end()),
+            name,
+            generics,
+            generic_packs,
+            type_,
+            exported,
+        ))
     }
 
-    fn parse_type_annotation(&mut self) -> Result<Box<AstType>, Box<AstType>> {
-        let begin = self.get_location().get_begin();
-        let mut parts: Vec<Box<AstType>> = Vec::new();
-        if let Some(value) = self.parse_simple_type_annotation(false)?.0 {
-            parts.push(value);
-        } else {
-            return Err(self.report_type_error(false, "unexpected type annotation"));
-        }
+    // declare function Name [`<' varlist `>'] `(' [parlist] `)' [`:' ReturnType] |
+    // declare class Name [extends Name] {classprop} end |
+    // declare Name `:' TypeAnnotation
+    fn parse_declaration(&mut self, start: LexLocation) -> AstStatId {
+        // `declare` token is already parsed at this point
+        if self.get_lexeme().eq(&LexType::Function) {
+            self.next_lexeme();
 
-        self.parse_type_annotation_parts(&mut parts, begin)
-    }
+            let (name, _) = self.parse_name("global function name");
 
-    fn parse_type_annotation_parts(
-        &mut self,
-        parts: &mut Vec<Box<AstType>>,
-        begin: LexPosition,
-    ) -> Result<Box<AstType>, Box<AstType>> {
-        let mut is_union = false;
-        let mut is_intersection = false;
-        loop {
-            match self.get_lexeme() {
-                LexType::SingletonOr => {
-                    self.next_lexeme();
+            let (generics, generic_packs) = self.parse_generic_type_list(false);
 
-                    if let Some(value) = self.parse_simple_type_annotation(false)?.0 {
-                        parts.push(value);
-                    } else {
-                        return Err(self.report_type_error(false, "unexpected type annotation"));
-                    }
-                    is_union = true;
-                }
-                LexType::QuestionMark => {
-                    let location = self.get_location();
+            let match_paren = self.get_current();
+            self.expect_and_consume(LexType::LeftRoundBracket, "global function declaration");
 
-                    self.next_lexeme();
-                    parts.push(TypeReference::new(
-                        location,
-                        None,
-                        AstName::new(String::from("nil")),
-                        None,
-                    ));
-                    is_union = true;
-                }
-                LexType::SingletonAnd => {
-                    self.next_lexeme();
+            let mut args: Vec<Binding> = Vec::new();
+            let mut vararg = false;
+            let mut vararg_annotation: Option<AstTypePackId> = None;
 
-                    if let Some(value) = self.parse_simple_type_annotation(false)?.0 {
-                        parts.push(value);
-                    } else {
-                        return Err(self.report_type_error(false, "unexpected type annotation"));
-                    }
-                    is_intersection = true;
+            if self.get_lexeme().ne(&LexType::RightRoundBracket) {
+                let (is_vararg, _, annotation) = self.parse_binding_list(&mut args, true);
+
+                vararg = is_vararg;
+                vararg_annotation = annotation;
+            }
+
+            self.expect_match_and_consume(LexType::RightRoundBracket, &match_paren);
+
+            let return_types = self
+                .parse_optional_return_type_annotation()
+                .unwrap_or_else(|| AstTypeList::new(Vec::new(), None));
+
+            let location =
+                LexLocation::new(start.get_begin(), self.get_previous_location().get_end());
+
+            let mut vars: Vec<AstTypeId> = Vec::new();
+            let mut var_names: Vec<Option<AstArgumentName>> = Vec::new();
+
+            for (name, name_location, annotation) in args {
+                if let Some(annotation) = annotation {
+                    vars.push(annotation);
+                    var_names.push(Some(AstArgumentName::new(name, name_location)));
+                } else {
+                    return self.report_stat_error(
+                        location,
+                        ParseErrorCode::UnannotatedDeclarationParameter,
+                        Vec::new(),
+                        Vec::new(),
+                        "All declaration parameters must be annotated",
+                    );
                 }
-                _ => break,
             }
-        }
 
-        if parts.len().eq(&1) {
-            Ok(*parts.first().unwrap())
-        } else {
-            let end = self.get_location().get_end();
-            if is_union && is_intersection {
-                Err(self
-                    .report_type_error(false, "mixing union and intersection types is not allowed"))
-            } else if is_union {
-                Ok(new_type_union(LexLocation::new(begin, end), *parts))
-            } else if is_intersection {
-                Ok(new_type_intersection(LexLocation::new(begin, end), *parts))
-            } else {
-                Err(self
-                    .report_type_error(false, "composite type was not an intersection or union"))
+            if vararg && vararg_annotation.is_none() {
+                return self.report_stat_error(
+                    location,
+                    ParseErrorCode::UnannotatedDeclarationParameter,
+                    Vec::new(),
+                    Vec::new(),
+                    "All declaration parameters must be annotated",
+                );
             }
-        }
-    }
 
-    fn parse_type_pack_annotation(&mut self) -> Result<Box<AstTypePack>, Box<AstTypePack>> {
-        Ok(if self.get_lexeme().eq(&LexType::Dot3) {
-            let begin = self.get_location().get_begin();
+            self.add(StatDeclareFunction::new(
+                location,
+                name,
+                generics.iter().map(|generic| generic.get_name()).collect(),
+                generic_packs
+                    .iter()
+                    .map(|generic| generic.get_name())
+                    .collect(),
+                AstTypeList::new(vars, vararg_annotation),
+                var_names,
+                return_types,
+            ))
+        } else if self.is_lexeme_name("class") {
+            let match_class = self.get_current();
             self.next_lexeme();
-            let vararg = self.parse_type_annotation()?;
 
-            new_type_pack_variadic(
-                LexLocation::new(begin, vararg.get_location().get_end()),
-                vararg,
-            )
-        } else {
-            match self.get_lexeme() {
-                LexType::Name(_) => {
-                    if self.get_ahead_lexeme().eq(&LexType::Dot3) {
-                        let (name, name_location) = self.parse_name()?;
+            let (name, _) = self.parse_name("class name");
 
-                        self.next_lexeme();
+            let mut super_name: Option<AstName> = None;
+            if self.is_lexeme_name("extends") {
+                self.next_lexeme();
+
+                super_name = Some(self.parse_name("superclass name").0);
+            }
 
-                        new_type_pack_generic(name_location, name)
+            let mut props: Vec<DeclaredClassProp> = Vec::new();
+            let mut indexer: Option<TableIndexer> = None;
+
+            while !matches!(self.get_lexeme(), LexType::End | LexType::Eof) {
+                let prop_start = self.get_location();
+
+                if self.get_lexeme().eq(&LexType::Function) {
+                    props.push(self.parse_declared_class_method());
+                } else if self.get_lexeme().eq(&LexType::LeftSquareBracket)
+                    && matches!(
+                        self.get_ahead_lexeme(),
+                        LexType::RawString(_) | LexType::QuotedString(_)
+                    )
+                {
+                    // [<string>]: <type>
+                    let match_bracket = self.get_current();
+                    self.next_lexeme(); // skip [
+
+                    let name = self.parse_string_name();
+
+                    self.expect_match_and_consume(LexType::RightSquareBracket, &match_bracket);
+                    self.expect_and_consume(LexType::Colon, "property type annotation");
+
+                    let type_ = self.parse_type_annotation();
+
+                    props.push(DeclaredClassProp::new(name, type_, false));
+                } else if self.get_lexeme().eq(&LexType::LeftSquareBracket) {
+                    // [<type>]: <type>
+                    if let Some(first_indexer) = &indexer {
+                        let first_location = first_indexer.get_location();
+                        let bad_indexer = self.parse_type_indexer_annotation();
+
+                        self.report_error(
+                            ParseError::new(
+                                ParseErrorCode::DuplicateTableIndexer,
+                                bad_indexer.get_location(),
+                                "Cannot have more than one class indexer",
+                            )
+                            .with_label(first_location, "first indexer declared here"),
+                        );
                     } else {
-                        AstTypePack::new_nil()
+                        indexer = Some(self.parse_type_indexer_annotation());
                     }
+                } else {
+                    // <name>: <type>
+                    let (prop_name, _) = self.parse_name("property name");
+
+                    self.expect_and_consume(LexType::Colon, "property type annotation");
+
+                    let type_ = self.parse_type_annotation();
+
+                    props.push(DeclaredClassProp::new(prop_name, type_, false));
+                }
+
+                // make sure the parser advances, otherwise the same lexeme would be parsed again
+                if prop_start == self.get_location() {
+                    self.next_lexeme();
                 }
-                _ => AstTypePack::new_nil(),
             }
-        })
-    }
 
-    fn parse_type_or_pack_annotation(&mut self) -> Result<Box<AstType>, Box<AstType>> {
-        let begin = self.get_location().get_begin();
+            let end = self.get_location();
+            self.expect_match_and_consume(LexType::End, &match_class);
+
+            self.add(StatDeclareClass::new(
+                LexLocation::new(start.get_begin(), end.get_end()),
+                name,
+                super_name,
+                props,
+                indexer,
+            ))
+        } else if let LexType::Name(_) = self.get_lexeme() {
+            let (name, _) = self.parse_name("global variable name");
 
-        let type_or_pack = self.parse_simple_type_annotation(true)?;
+            self.expect_and_consume(LexType::Colon, "global variable declaration");
 
-        if let Some(type_pack) = type_or_pack.1 {
-            Ok(type_pack)
-        } else if let Some(type_) = type_or_pack.0 {
-            let parts: Vec<Box<AstType>> = Vec::new();
-            parts.push(type_);
+            let type_ = self.parse_type_annotation();
 
-            self.parse_type_annotation_parts(&mut parts, begin)
+            self.add(StatDeclareGlobal::new(
+                LexLocation::new(
+                    start.get_begin(),
+                    self.arena[type_].get_location().get_end(),
+                ),
+                name,
+                type_,
+            ))
         } else {
-            Err(self.report_type_error(true, "unexpected simple type annotation"))
+            self.report_stat_error(
+                start,
+                ParseErrorCode::InvalidDeclaration,
+                Vec::new(),
+                Vec::new(),
+                "declare must be followed by an identifier, 'function', or 'class'",
+            )
         }
     }
 
-    fn parse_type_parameters(&mut self) -> Result<Option<Vec<Box<AstTypePack>>>, Box<AstType>> {
-        let parameters: Vec<Box<AstTypePack>> = Vec::new();
+    // classmethod ::= function Name [`<' varlist `>'] `(' self [`,' parlist] `)' [`:' ReturnType]
+    fn parse_declared_class_method(&mut self) -> DeclaredClassProp {
+        self.next_lexeme(); // skip function
 
-        if self.get_lexeme().eq(&LexType::Less) {
-            let begin = self.get_location().get_begin();
-            self.next_lexeme();
+        let start = self.get_location().get_begin();
+        let (name, _) = self.parse_name("function name");
 
-            loop {
-                if self.should_parse_type_pack_annotation() {
-                    parameters.push(self.parse_type_pack_annotation()?);
-                } else if self.get_lexeme().eq(&LexType::LeftRoundBracket) {
-                    parameters.push(self.parse_type_or_pack_annotation()?);
-                } else if self.get_lexeme().eq(&LexType::Greater) && parameters.is_empty() {
-                    break;
-                } else {
-                    parameters.push(self.parse_type_annotation()?);
-                }
+        let (generics, generic_packs) = self.parse_generic_type_list(false);
 
-                if self.get_lexeme().eq(&LexType::Comma) {
-                    self.next_lexeme();
-                } else {
-                    break;
-                }
-            }
+        let match_paren = self.get_current();
+        self.expect_and_consume(LexType::LeftRoundBracket, "function parameter list start");
+
+        let mut args: Vec<Binding> = Vec::new();
+        let mut vararg = false;
+        let mut vararg_annotation: Option<AstTypePackId> = None;
+
+        if self.get_lexeme().ne(&LexType::RightRoundBracket) {
+            let (is_vararg, _, annotation) = self.parse_binding_list(&mut args, true);
+
+            vararg = is_vararg;
+            vararg_annotation = annotation;
+        }
+
+        self.expect_match_and_consume(LexType::RightRoundBracket, &match_paren);
+
+        let return_types = self
+            .parse_optional_return_type_annotation()
+            .unwrap_or_else(|| AstTypeList::new(Vec::new(), None));
+
+        let location = LexLocation::new(start, self.get_previous_location().get_end());
+
+        let has_self = matches!(
+            args.first(),
+            Some((name, _, None)) if name.eq(&self.name_self)
+        );
+        if !has_self {
+            let type_ = self.report_type_error(
+                location,
+                ParseErrorCode::MissingSelfParameter,
+                Vec::new(),
+                false,
+                "'self' must be present as the unannotated first parameter",
+            );
+
+            return DeclaredClassProp::new(name, type_, true);
+        }
 
-            if self.get_lexeme().ne(&LexType::Greater) {
-                return Err(self.report_type_error(false, "unexpected type parameters"));
+        let mut vars: Vec<AstTypeId> = Vec::new();
+        let mut var_names: Vec<Option<AstArgumentName>> = Vec::new();
+
+        // skip the self parameter
+        for (arg_name, arg_location, annotation) in args.into_iter().skip(1) {
+            var_names.push(Some(AstArgumentName::new(arg_name, arg_location)));
+
+            if let Some(annotation) = annotation {
+                vars.push(annotation);
+            } else {
+                vars.push(self.report_type_error(
+                    location,
+                    ParseErrorCode::UnannotatedDeclarationParameter,
+                    Vec::new(),
+                    false,
+                    "All declaration parameters aside from 'self' must be annotated",
+                ));
             }
-            self.next_lexeme();
         }
 
-        Ok(if parameters.is_empty() {
-            None
-        } else {
-            Some(parameters)
-        })
+        if vararg && vararg_annotation.is_none() {
+            self.report(
+                location,
+                ParseErrorCode::UnannotatedDeclarationParameter,
+                "All declaration parameters aside from 'self' must be annotated",
+            );
+        }
+
+        let type_ = self.add(TypeFunction::new(
+            location,
+            generics.iter().map(|generic| generic.get_name()).collect(),
+            generic_packs
+                .iter()
+                .map(|generic| generic.get_name())
+                .collect(),
+            AstTypeList::new(vars, vararg_annotation),
+            var_names,
+            return_types,
+        ));
+
+        DeclaredClassProp::new(name, type_, true)
     }
 
-    fn parse_name_type(
-        &mut self,
-        begin: LexPosition,
-        name: AstName,
-    ) -> Result<Box<AstType>, Box<AstType>> {
-        let mut real_name = name;
-        let mut prefix: Option<AstName> = None;
+    fn get_identifier(&self, expr: AstExprId) -> Option<AstName> {
+        match self.arena[expr].get_payload() {
+            Expr::Global(name) => Some(*name),
+            Expr::Local(local) => Some(self.arena[local.get_local()].get_name()),
+            _ => None,
+        }
+    }
 
-        if self.get_lexeme().eq(&LexType::Dot) {
-            self.next_lexeme();
+    fn is_expr_lvalue(&self, expr: AstExprId) -> bool {
+        matches!(
+            self.arena[expr].get_payload_type(),
+            AstNodePayloadType::ExprLocal
+                | AstNodePayloadType::ExprGlobal
+                | AstNodePayloadType::ExprIndexExpr
+                | AstNodePayloadType::ExprIndexName
+        )
+    }
 
-            prefix = Some(name);
-            let (sub_name, _) = self.parse_name()?;
-            real_name = sub_name;
+    // exp | varlist `=' explist
+    fn parse_expr_stat(&mut self) -> AstStatId {
+        let start = self.get_location();
+
+        let expr = self.parse_primary_expr(true);
+
+        if self.arena[expr]
+            .get_payload_type()
+            .eq(&AstNodePayloadType::ExprCall)
+        {
+            return self.add(StatExpr::new(self.arena[expr].get_location(), expr));
         }
 
-        let parameters = self.parse_type_parameters()?;
+        // if the next token is , or =, it's an assignment (, means it's an assignment with multiple variables)
+        if self.get_lexeme().eq(&LexType::Comma) || self.get_lexeme().eq(&LexType::Assign) {
+            return self.parse_assignment(expr);
+        }
 
-        let end = self.get_location().get_end();
-        Ok(TypeReference::new(
-            LexLocation::new(begin, end),
-            prefix,
-            real_name,
-            parameters,
-        ))
-    }
+        // if the next token is a compound assignment operator, it's a compound assignment (these don't support multiple variables)
+        if let Some(operator) = self.parse_compound_operator(self.get_lexeme()) {
+            return self.parse_compound_assignment(expr, operator);
+        }
 
-    fn parse_name_or_typeof_type(&mut self) -> Result<Box<AstType>, Box<AstType>> {
-        let begin = self.get_location().get_begin();
-        let prefix: Option<AstName> = None;
-        let (name, _) = self.parse_name()?;
+        // we know this isn't a call or an assignment; therefore it must be a context-sensitive keyword such as `type` or `continue`
+        if let Some(ident) = self.get_identifier(expr) {
+            if self.options.get_allow_type_annotations() {
+                if self.is_name(ident, "type") {
+                    return self
+                        .parse_type_alias_stat(self.arena[expr].get_location().get_begin(), false);
+                }
 
-        if name.eq_str("typeof") {
-            self.parse_typeof_type(begin)
+                if self.is_name(ident, "export") && self.is_lexeme_name("type") {
+                    self.next_lexeme();
+
+                    return self
+                        .parse_type_alias_stat(self.arena[expr].get_location().get_begin(), true);
+                }
+            }
+
+            if self.options.get_allow_declaration_syntax() && self.is_name(ident, "declare") {
+                return self.parse_declaration(self.arena[expr].get_location());
+            }
+
+            // continue is only a keyword when it ends the statement, otherwise it's a regular name
+            if self.is_name(ident, "continue")
+                && (self.block_follow() || self.get_lexeme().eq(&LexType::Semicolon))
+            {
+                return self.parse_continue_stat(self.arena[expr].get_location());
+            }
+        }
+
+        // make sure the parser advances, otherwise the block would try to parse the same lexeme again
+        if start == self.get_location() {
+            self.next_lexeme();
+        }
+
+        self.report_stat_error(
+            self.arena[expr].get_location(),
+            ParseErrorCode::IncompleteStatement,
+            vec![expr],
+            Vec::new(),
+            "Incomplete statement: expected assignment or a function call",
+        )
+    }
+
+    fn check_expr_lvalue(&mut self, expr: AstExprId) -> AstExprId {
+        if self.is_expr_lvalue(expr) {
+            expr
         } else {
-            self.parse_name_type(begin, name)
+            self.report_expr_error(
+                self.arena[expr].get_location(),
+                ParseErrorCode::InvalidAssignmentTarget,
+                vec![expr],
+                "Assigned expression must be a variable or a field",
+            )
         }
     }
 
-    fn parse_type_indexer_annotation(&mut self) -> Result<TableIndexer, Box<AstType>> {
-        let begin = self.get_location().get_begin();
-        self.next_lexeme();
+    // varlist `=' explist
+    fn parse_assignment(&mut self, initial: AstExprId) -> AstStatId {
+        let mut vars: Vec<AstExprId> = vec![self.check_expr_lvalue(initial)];
 
-        let index = self.parse_type_annotation()?;
+        while self.get_lexeme().eq(&LexType::Comma) {
+            self.next_lexeme();
 
-        if self.get_lexeme().ne(&LexType::RightSquareBracket) {
-            return Err(self.report_type_error(false, "unexpected parse type indexer annotation"));
+            let expr = self.parse_primary_expr(true);
+            vars.push(self.check_expr_lvalue(expr));
         }
-        self.next_lexeme();
 
-        if self.get_lexeme().ne(&LexType::Colon) {
-            return Err(self.report_type_error(false, "unexpected parse type indexer annotation"));
-        }
-        self.next_lexeme();
+        self.expect_and_consume(LexType::Assign, "assignment");
 
-        let result = self.parse_type_annotation()?;
+        let mut values: Vec<AstExprId> = Vec::new();
+        self.parse_expr_list(&mut values);
 
-        Ok(TableIndexer::new(
-            index,
-            result,
-            LexLocation::new(begin, result.get_location().get_end()),
+        self.add(StatAssign::new(
+            LexLocation::new(
+                self.arena[*vars.first().unwrap()]
+                    .get_location()
+                    .get_begin(),
+                self.arena[*values.last().unwrap()].get_location().get_end(),
+            ),
+            vars,
+            values,
         ))
     }
 
-    fn parse_table_type_annotation(&mut self) -> Result<Box<AstType>, Box<AstType>> {
-        let mut props: Vec<TableProp> = Vec::new();
-        let mut indexer: Option<TableIndexer> = None;
+    fn parse_compound_operator(&self, lexeme: LexType) -> Option<BinaryOperator> {
+        Some(match lexeme {
+            LexType::AddAssign => BinaryOperator::Add,
+            LexType::SubAssign => BinaryOperator::Sub,
+            LexType::MulAssign => BinaryOperator::Mul,
+            LexType::DivAssign => BinaryOperator::Div,
+            LexType::FloorDivAssign => BinaryOperator::FloorDiv,
+            LexType::ModAssign => BinaryOperator::Mod,
+            LexType::PowAssign => BinaryOperator::Pow,
+            LexType::ConcatAssign => BinaryOperator::Concat,
+            _ => return None,
+        })
+    }
 
-        let begin = self.get_location().get_begin();
+    // var [`+=' | `-=' | `*=' | `/=' | `//=' | `%=' | `^=' | `..='] exp
+    fn parse_compound_assignment(
+        &mut self,
+        initial: AstExprId,
+        operator: BinaryOperator,
+    ) -> AstStatId {
+        let var = self.check_expr_lvalue(initial);
 
-        if self.get_lexeme().ne(&LexType::LeftCurlyBracket) {
-            return Err(self.report_type_error(false, "unexpected parse table type annotation"));
-        }
         self.next_lexeme();
 
-        while self.get_lexeme().ne(&LexType::RightCurlyBracket) {
-            let parse_name_props_append = || -> Result<(), Box<AstType>> {
-                let (name, name_location) = self.parse_name()?;
-
-                if self.get_lexeme().ne(&LexType::Colon) {
-                    return Err(self.report_type_error(false, "table field"));
-                }
-                self.next_lexeme();
+        let value = self.parse_expr(0);
 
-                let type_ = self.parse_type_annotation()?;
+        self.add(StatCompoundAssign::new(
+            LexLocation::new(
+                self.arena[var].get_location().get_begin(),
+                self.arena[value].get_location().get_end(),
+            ),
+            operator,
+            var,
+            value,
+        ))
+    }
 
-                props.push(TableProp::new(name, name_location, type_));
+    // funcbody ::= [`<' varlist `>'] `(' [parlist] `)' [`:' ReturnType] block end
+    // parlist ::= bindinglist [`,' `...' [`:' TypePack]] | `...' [`:' TypePack]
+    fn parse_function_body(
+        &mut self,
+        has_self: bool,
+        match_function: &Lexeme,
+        debug_name: AstName,
+    ) -> (Box<ExprFunction>, LexLocation) {
+        let begin = match_function.get_location();
 
-                Ok(())
-            };
+        let (generics, generic_packs) = self.parse_generic_type_list(false);
 
-            if self.get_lexeme().eq(&LexType::LeftSquareBracket) {
-                let parse_string_props_append = |value: String| -> Result<(), Box<AstType>> {
-                    let location = self.get_location();
-                    self.next_lexeme(); // skip LeftSquareBracket
-                    self.next_lexeme(); // skip value
+        let match_paren = self.get_current();
+        self.expect_and_consume(LexType::LeftRoundBracket, "function");
 
-                    if self.get_lexeme().ne(&LexType::RightSquareBracket) {
-                        return Err(self.report_type_error(false, "table field"));
-                    }
-                    self.next_lexeme();
+        let mut args: Vec<Binding> = Vec::new();
+        let mut vararg = false;
+        let mut vararg_location = LexLocation::zero();
+        let mut vararg_annotation: Option<AstTypePackId> = None;
 
-                    if self.get_lexeme().ne(&LexType::Semicolon) {
-                        return Err(self.report_type_error(false, "table field"));
-                    }
-                    self.next_lexeme();
+        self.match_recovery_stop_on_token[LexType::RightRoundBracket.code() as usize] += 1;
 
-                    let type_ = self.parse_type_annotation()?;
+        if self.get_lexeme().ne(&LexType::RightRoundBracket) {
+            let (is_vararg, location, annotation) = self.parse_binding_list(&mut args, true);
 
-                    if value.is_empty() {
-                        return Err(self.report_type_error(false, "table field"));
-                    } else {
-                        props.push(TableProp::new(AstName::new(value), location, type_));
-                    }
+            vararg = is_vararg;
+            vararg_location = location;
+            vararg_annotation = annotation;
+        }
 
-                    Ok(())
-                };
+        self.match_recovery_stop_on_token[LexType::RightRoundBracket.code() as usize] -= 1;
 
-                match self.get_ahead_lexeme() {
-                    LexType::QuotedString(value) => {
-                        parse_string_props_append(value)?;
-                    }
-                    LexType::RawString(value) => {
-                        parse_string_props_append(value)?;
-                    }
-                    _ => {
-                        if indexer.is_some() {
-                            return Err(self.report_type_error(
-                                false,
-                                "cannot have more than one table indexer",
-                            ));
-                        } else {
-                            indexer = Some(self.parse_type_indexer_annotation()?);
-                        }
-                    }
-                }
-            }
-            if props.is_empty() && indexer.is_none() {
-                let parse_indexer = || -> Result<(), Box<AstType>> {
-                    let type_ = self.parse_type_annotation()?;
+        let arg_location = if match_paren.get_type().eq(&LexType::LeftRoundBracket)
+            && self.get_lexeme().eq(&LexType::RightRoundBracket)
+        {
+            Some(LexLocation::new(
+                match_paren.get_location().get_begin(),
+                self.get_location().get_end(),
+            ))
+        } else {
+            None
+        };
 
-                    indexer = Some(TableIndexer::new(
-                        TypeReference::new(
-                            type_.get_location(),
-                            None,
-                            AstName::new(String::from("number")),
-                            None,
-                        ),
-                        type_,
-                        type_.get_location(),
-                    ));
-                    Ok(())
-                };
+        self.expect_match_and_consume_search(LexType::RightRoundBracket, &match_paren);
 
-                if let LexType::Name(_) = self.get_lexeme() {
-                    if self.get_ahead_lexeme().eq(&LexType::Colon) {
-                        parse_name_props_append()?;
-                    } else {
-                        parse_indexer()?;
-                        break;
-                    }
-                } else {
-                    parse_indexer()?;
-                    break;
-                }
-            } else {
-                parse_name_props_append()?;
-            }
+        let return_annotation = self.parse_optional_return_type_annotation();
 
-            match self.get_lexeme() {
-                LexType::Comma | LexType::Semicolon => self.next_lexeme(),
-                _ => break,
-            }
-        }
+        let locals_begin = self.save_locals();
 
-        let end = self.get_location().get_end();
+        self.function_stack.push((vararg, 0));
 
-        if self.get_lexeme().ne(&LexType::RightCurlyBracket) {
-            return Err(self.report_type_error(false, "unexpected parse table type annotation"));
-        }
-        self.next_lexeme();
+        let self_ = if has_self {
+            Some(self.push_local((self.name_self, begin, None)))
+        } else {
+            None
+        };
 
-        Ok(TypeTable::new(LexLocation::new(begin, end), props, indexer))
-    }
+        let vars = args
+            .into_iter()
+            .map(|arg| self.push_local(arg))
+            .collect::<Vec<LocalId>>();
 
-    fn parse_generic_type_list(
-        &mut self,
-        with_default_values: bool,
-    ) -> Result<
-        (
-            Vec<(AstName, LexLocation, Option<Box<AstType>>)>,
-            Vec<(AstName, LexLocation, Option<Box<AstType>>)>,
-        ),
-        Box<AstType>,
-    > {
-        let mut names: Vec<(AstName, LexLocation, Option<Box<AstType>>)> = Vec::new();
-        let mut name_packs: Vec<(AstName, LexLocation, Option<Box<AstType>>)> = Vec::new();
+        self.match_recovery_stop_on_token[LexType::End.code() as usize] += 1;
 
-        if self.get_lexeme().eq(&LexType::Less) {
-            let begin = self.get_location().get_begin();
-            self.next_lexeme();
+        let body = self.parse_block();
 
-            let mut seen_pack = false;
-            let mut seen_default = false;
+        self.match_recovery_stop_on_token[LexType::End.code() as usize] -= 1;
 
-            loop {
-                let (name, name_location) = self.parse_name()?;
+        self.function_stack.pop();
+        self.restore_locals(locals_begin);
 
-                if self.get_lexeme() == LexType::Dot3 || seen_pack {
-                    seen_pack = true;
+        let end = self.get_location();
+        let has_end = self.expect_match_and_consume(LexType::End, match_function);
 
-                    if self.get_lexeme() == LexType::Dot3 {
-                        self.next_lexeme();
-                    }
+        (
+            ExprFunction::new(
+                generics.iter().map(|generic| generic.get_name()).collect(),
+                generic_packs
+                    .iter()
+                    .map(|generic| generic.get_name())
+                    .collect(),
+                self_,
+                vars,
+                return_annotation,
+                vararg,
+                vararg_location,
+                vararg_annotation,
+                body,
+                self.function_stack.len(),
+                debug_name,
+                has_end,
+                arg_location,
+            ),
+            LexLocation::new(begin.get_begin(), end.get_end()),
+        )
+    }
 
-                    if with_default_values && self.get_lexeme() == LexType::Assign {
-                        seen_default = true;
-                        self.next_lexeme();
+    // binding ::= Name [`:` TypeAnnotation]
+    fn parse_binding(&mut self) -> Binding {
+        let (name, location) = self.parse_name("variable name");
+        let annotation = self.parse_optional_type_annotation();
 
-                        if self.should_parse_type_pack_annotation() {
-                            name_packs.push((
-                                name,
-                                name_location,
-                                Some(self.parse_type_pack_annotation()?),
-                            ));
-                        } else if self.get_lexeme() == LexType::LeftRoundBracket {
-                            name_packs.push((
-                                name,
-                                name_location,
-                                Some(self.parse_type_or_pack_annotation()?),
-                            ));
-                        }
-                    } else {
-                        name_packs.push((name, name_location, None))
-                    }
-                } else {
-                    if with_default_values && self.get_lexeme().eq(&LexType::Assign) {
-                        seen_default = true;
-                        self.next_lexeme();
+        (name, location, annotation)
+    }
 
-                        names.push((name, name_location, Some(self.parse_type_annotation()?)));
-                    } else {
-                        names.push((name, name_location, None));
-                    }
-                }
+    // bindinglist ::= (binding | `...' [`:' TypePack]) [`,' bindinglist]
+    fn parse_binding_list(
+        &mut self,
+        result: &mut Vec<Binding>,
+        allow_dot3: bool,
+    ) -> (bool, LexLocation, Option<AstTypePackId>) {
+        loop {
+            if self.get_lexeme().eq(&LexType::Dot3) && allow_dot3 {
+                let location = self.get_location();
+                self.next_lexeme();
 
-                if self.get_lexeme() == LexType::Comma {
+                let mut annotation: Option<AstTypePackId> = None;
+                if self.options.get_allow_type_annotations()
+                    && self.get_lexeme().eq(&LexType::Colon)
+                {
                     self.next_lexeme();
-                } else {
-                    break;
+
+                    annotation = Some(self.parse_variadic_argument_annotation());
                 }
+
+                return (true, location, annotation);
             }
 
-            if self.get_lexeme().ne(&LexType::Greater) {
-                return Err(self.report_type_error(false, "unexpected generic type list"));
+            result.push(self.parse_binding());
+
+            if self.get_lexeme().ne(&LexType::Comma) {
+                break;
             }
             self.next_lexeme();
         }
 
-        Ok((names, name_packs))
+        (false, LexLocation::zero(), None)
     }
 
-    fn parse_simple_type_annotation(
-        &mut self,
-        allow_pack: bool,
-    ) -> Result<(Option<Box<AstType>>, Option<Box<AstTypePack>>), Box<AstType>> {
-        let begin = self.get_location().get_begin();
+    // varargannotation ::= Name `...' | TypeAnnotation
+    fn parse_variadic_argument_annotation(&mut self) -> AstTypePackId {
+        if let LexType::Name(_) = self.get_lexeme() {
+            if self.get_ahead_lexeme().eq(&LexType::Dot3) {
+                if let Some(type_pack) = self.parse_type_pack_annotation() {
+                    return type_pack;
+                }
+            }
+        }
+
+        let type_ = self.parse_type_annotation();
+
+        self.add(new_type_pack_variadic(
+            self.arena[type_].get_location(),
+            type_,
+        ))
+    }
+
+    fn parse_optional_return_type_annotation(&mut self) -> Option<AstTypeList> {
+        if self.options.get_allow_type_annotations() && self.get_lexeme().eq(&LexType::Colon) {
+            self.next_lexeme();
+
+            let (_, return_types) = self.parse_return_type_annotation();
+
+            Some(return_types)
+        } else {
+            None
+        }
+    }
+
+    // ReturnType ::= TypeAnnotation | `(' [TypeList] `)' [`->' ReturnType] | TypePack
+    fn parse_return_type_annotation(&mut self) -> (LexLocation, AstTypeList) {
+        let begin = self.get_current();
+
+        if self.get_lexeme().ne(&LexType::LeftRoundBracket) {
+            if self.should_parse_type_pack_annotation() {
+                if let Some(tail_type) = self.parse_type_pack_annotation() {
+                    return (
+                        self.arena[tail_type].get_location(),
+                        AstTypeList::new(Vec::new(), Some(tail_type)),
+                    );
+                }
+            }
+
+            let type_ = self.parse_type_annotation();
+
+            return (
+                self.arena[type_].get_location(),
+                AstTypeList::new(vec![type_], None),
+            );
+        }
+
+        self.next_lexeme();
+
+        let inner_begin = self.get_location().get_begin();
+
+        let mut types: Vec<AstTypeId> = Vec::new();
+        let mut names: Vec<Option<AstArgumentName>> = Vec::new();
+        let mut tail_type: Option<AstTypePackId> = None;
+
+        // possibly () -> ReturnType
+        if self.get_lexeme().ne(&LexType::RightRoundBracket) {
+            tail_type = self.parse_type_list(&mut types, &mut names);
+        }
+
+        let location = LexLocation::new(
+            begin.get_location().get_begin(),
+            self.get_location().get_end(),
+        );
+        self.expect_match_and_consume(LexType::RightRoundBracket, &begin);
+
+        if self.get_lexeme().ne(&LexType::SkinnyArrow) && names.is_empty() {
+            // if it turns out that it's just '(A)', there may be unions/intersections to follow, so fold over it
+            if types.len() == 1 && tail_type.is_none() {
+                let is_followed = matches!(
+                    self.get_lexeme(),
+                    LexType::SingletonOr | LexType::SingletonAnd | LexType::QuestionMark
+                );
+                let type_ = self.parse_type_annotation_parts(types, inner_begin);
+                let end = if is_followed {
+                    self.arena[type_].get_location().get_end()
+                } else {
+                    location.get_end()
+                };
+
+                return (
+                    LexLocation::new(location.get_begin(), end),
+                    AstTypeList::new(vec![type_], None),
+                );
+            }
+
+            return (location, AstTypeList::new(types, tail_type));
+        }
+
+        let function_type = self.parse_function_type_annotation_tail(
+            &begin,
+            Vec::new(),
+            Vec::new(),
+            AstTypeList::new(types, tail_type),
+            names,
+        );
+
+        (
+            LexLocation::new(
+                location.get_begin(),
+                self.arena[function_type].get_location().get_end(),
+            ),
+            AstTypeList::new(vec![function_type], None),
+        )
+    }
+
+    // TypeList ::= [Name `:'] TypeAnnotation [`,' TypeList] | TypePack
+    fn parse_type_list(
+        &mut self,
+        result: &mut Vec<AstTypeId>,
+        result_names: &mut Vec<Option<AstArgumentName>>,
+    ) -> Option<AstTypePackId> {
+        loop {
+            if self.should_parse_type_pack_annotation() {
+                return self.parse_type_pack_annotation();
+            }
+
+            let current = self.get_current();
+            if let LexType::Name(name) = current.get_type() {
+                if self.get_ahead_lexeme().eq(&LexType::Colon) {
+                    // fill in previous argument names with empty slots
+                    while result_names.len() < result.len() {
+                        result_names.push(None);
+                    }
+
+                    result_names.push(Some(AstArgumentName::new(name, current.get_location())));
+                    self.next_lexeme();
+
+                    self.expect_and_consume(LexType::Colon, "type list");
+                } else if !result_names.is_empty() {
+                    // if we have a type with named arguments, provide elements for all types
+                    result_names.push(None);
+                }
+            } else if !result_names.is_empty() {
+                result_names.push(None);
+            }
+
+            result.push(self.parse_type_annotation());
+
+            if self.get_lexeme().ne(&LexType::Comma) {
+                break;
+            }
+            self.next_lexeme();
+        }
+
+        None
+    }
+
+    fn parse_optional_type_annotation(&mut self) -> Option<AstTypeId> {
+        if self.options.get_allow_type_annotations() && self.get_lexeme().eq(&LexType::Colon) {
+            self.next_lexeme();
+
+            Some(self.parse_type_annotation())
+        } else {
+            None
+        }
+    }
+
+    // consumes the current string token and decodes its value, every malformed escape sequence
+    // is reported at its own location and the index of the last one is returned
+    fn parse_char_array(&mut self) -> Result<Vec<u8>, usize> {
+        let location = self.get_location();
+
+        match self.get_lexeme() {
+            LexType::RawString(value) => {
+                self.next_lexeme();
+
+                Ok(decode_multiline(&value))
+            }
+            LexType::QuotedString(value)
+            | LexType::InterpStringBegin(value)
+            | LexType::InterpStringMid(value)
+            | LexType::InterpStringEnd(value)
+            | LexType::InterpStringSimple(value) => {
+                self.next_lexeme();
+
+                // the body starts right after the opening quote, backtick or brace
+                let begin = location.get_begin();
+                let begin = LexPosition::new(begin.get_line(), begin.get_column() + 1)
+                    .with_offset(begin.get_offset() + 1);
+
+                let (value, errors) = decode_escapes(&value, begin);
+
+                let mut message_index = None;
+                for (location, message) in errors {
+                    message_index =
+                        Some(self.report(location, ParseErrorCode::MalformedString, message));
+                }
+
+                match message_index {
+                    Some(message_index) => Err(message_index),
+                    None => Ok(value),
+                }
+            }
+            _ => Err(self.report(
+                location,
+                ParseErrorCode::MalformedString,
+                "String literal contains malformed escape sequence",
+            )),
+        }
+    }
+
+    // string keys of table types and declared classes become property names
+    fn parse_string_name(&mut self) -> AstName {
+        let location = self.get_location();
+
+        match self.parse_char_array() {
+            Ok(value) => match String::from_utf8(value) {
+                Ok(value) => self.intern(&value),
+                Err(_) => {
+                    self.report(
+                        location,
+                        ParseErrorCode::MalformedString,
+                        "Property name must be a valid UTF-8 string",
+                    );
+
+                    self.name_error
+                }
+            },
+            Err(_) => self.name_error,
+        }
+    }
+
+    fn parse_string_expr(&mut self) -> AstExprId {
+        let location = self.get_location();
+
+        match self.parse_char_array() {
+            Ok(value) => self.add(new_constant_string(location, value)),
+            Err(message_index) => self.add(ExprError::new(location, Vec::new(), message_index)),
+        }
+    }
+
+    // `<string>{<expr>}<string>`
+    fn parse_interp_string(&mut self) -> AstExprId {
+        let start = self.get_location();
+        let mut end;
+
+        let mut strings: Vec<Vec<u8>> = Vec::new();
+        let mut expressions: Vec<AstExprId> = Vec::new();
+        let mut message_index: Option<usize> = None;
+
+        loop {
+            let section = self.get_lexeme();
+            end = self.get_location();
+
+            match self.parse_char_array() {
+                Ok(value) => strings.push(value),
+                Err(index) => message_index = Some(index),
+            }
+
+            if matches!(
+                section,
+                LexType::InterpStringEnd(_) | LexType::InterpStringSimple(_)
+            ) {
+                break;
+            }
+
+            match self.get_lexeme() {
+                LexType::InterpStringMid(_) | LexType::InterpStringEnd(_) => {
+                    // the next section is still parsed as part of this string
+                    let index = self.report(
+                        end,
+                        ParseErrorCode::MalformedInterpolatedString,
+                        "Malformed interpolated string, expected expression inside '{}'",
+                    );
+                    message_index = Some(index);
+
+                    continue;
+                }
+                LexType::BrokenString => {
+                    self.next_lexeme();
+
+                    message_index = Some(self.report(
+                        end,
+                        ParseErrorCode::MalformedInterpolatedString,
+                        "Malformed interpolated string, did you forget to add a '`'?",
+                    ));
+                    break;
+                }
+                _ => expressions.push(self.parse_expr(0)),
+            }
+
+            match self.get_lexeme() {
+                LexType::InterpStringMid(_) | LexType::InterpStringEnd(_) => {}
+                LexType::BrokenInterpDoubleBrace => {
+                    let location = self.get_location();
+                    self.next_lexeme();
+
+                    message_index = Some(self.report_interp_double_brace_error(location));
+                    break;
+                }
+                LexType::BrokenString => {
+                    let location = self.get_location();
+                    self.next_lexeme();
+
+                    message_index = Some(self.report(
+                        location,
+                        ParseErrorCode::MalformedInterpolatedString,
+                        "Malformed interpolated string, did you forget to add a '`'?",
+                    ));
+                    break;
+                }
+                _ => {
+                    message_index = Some(self.report(
+                        self.get_location(),
+                        ParseErrorCode::MalformedInterpolatedString,
+                        &format!(
+                            "Malformed interpolated string, expected '}}', got {}",
+                            self.get_lexeme_text()
+                        ),
+                    ));
+                    break;
+                }
+            }
+        }
+
+        let location = LexLocation::new(start.get_begin(), end.get_end());
+
+        match message_index {
+            Some(message_index) => self.add(ExprError::new(location, expressions, message_index)),
+            None => self.add(ExprInterpString::new(location, strings, expressions)),
+        }
+    }
+
+    fn report_interp_double_brace_error(&mut self, location: LexLocation) -> usize {
+        self.report_error(
+            ParseError::new(
+                ParseErrorCode::MalformedInterpolatedString,
+                location,
+                "Double braces are not permitted within interpolated strings, did you mean '\\{'?",
+            )
+            .with_suggestion("\\{"),
+        )
+    }
+
+    fn parse_nil_expr(&mut self) -> AstExprId {
+        let location = self.get_location();
+
+        if self.get_lexeme().eq(&LexType::Nil) {
+            self.next_lexeme();
+
+            self.add(new_constant_nil(location))
+        } else {
+            self.report_expr_error(
+                location,
+                ParseErrorCode::UnexpectedToken,
+                Vec::new(),
+                &format!("Expected nil, got {}", self.get_lexeme_text()),
+            )
+        }
+    }
+
+    fn parse_bool_expr(&mut self) -> AstExprId {
+        let location = self.get_location();
+
+        match self.get_lexeme() {
+            LexType::True => {
+                self.next_lexeme();
+
+                self.add(new_constant_bool(location, true))
+            }
+            LexType::False => {
+                self.next_lexeme();
+
+                self.add(new_constant_bool(location, false))
+            }
+            _ => self.report_expr_error(
+                location,
+                ParseErrorCode::UnexpectedToken,
+                Vec::new(),
+                &format!("Expected true or false, got {}", self.get_lexeme_text()),
+            ),
+        }
+    }
+
+    fn parse_number_expr(&mut self) -> AstExprId {
+        let location = self.get_location();
+
+        if let LexType::Number(value) = self.get_lexeme() {
+            self.next_lexeme();
+
+            // digit separators carry no meaning
+            let (value, result) = to_number(&value.replace('_', ""));
+
+            // like luau's integer parsing lint, literals that lose precision are warnings
+            let warning = match result {
+                NumberParseResult::Ok => None,
+                NumberParseResult::Malformed => {
+                    return self.report_expr_error(
+                        location,
+                        ParseErrorCode::MalformedNumber,
+                        Vec::new(),
+                        "Malformed number",
+                    );
+                }
+                NumberParseResult::Imprecise => Some(
+                    "Number literal exceeded available precision and was truncated to closest \
+                     representable number",
+                ),
+                NumberParseResult::BinOverflow => Some(
+                    "Binary number literal exceeded available precision and was truncated to 2^64",
+                ),
+                NumberParseResult::HexOverflow => Some(
+                    "Hexadecimal number literal exceeded available precision and was truncated \
+                     to 2^64",
+                ),
+            };
+
+            if let Some(warning) = warning {
+                self.report_error(
+                    ParseError::new(ParseErrorCode::ImpreciseNumber, location, warning)
+                        .with_severity(Severity::Warning),
+                );
+            }
+
+            self.add(new_constant_number(location, value))
+        } else {
+            self.report_expr_error(
+                location,
+                ParseErrorCode::UnexpectedToken,
+                Vec::new(),
+                &format!("Expected number, got {}", self.get_lexeme_text()),
+            )
+        }
+    }
+
+    fn parse_unary_operator(&self, lexeme: LexType) -> Option<UnaryOperator> {
+        Some(match lexeme {
+            LexType::Not => UnaryOperator::Not,
+            LexType::Sub => UnaryOperator::Minus,
+            LexType::Sharp => UnaryOperator::Len,
+            _ => return None,
+        })
+    }
+
+    fn parse_binary_operator(&self, lexeme: LexType) -> Option<BinaryOperator> {
+        Some(match lexeme {
+            LexType::Add => BinaryOperator::Add,
+            LexType::Sub => BinaryOperator::Sub,
+            LexType::Mul => BinaryOperator::Mul,
+            LexType::Div => BinaryOperator::Div,
+            LexType::FloorDiv => BinaryOperator::FloorDiv,
+            LexType::Mod => BinaryOperator::Mod,
+            LexType::Pow => BinaryOperator::Pow,
+            LexType::Dot2 => BinaryOperator::Concat,
+            LexType::NotEqual => BinaryOperator::NotEqual,
+            LexType::Equal => BinaryOperator::Equal,
+            LexType::Less => BinaryOperator::Less,
+            LexType::LessEqual => BinaryOperator::LessEqual,
+            LexType::Greater => BinaryOperator::Greater,
+            LexType::GreaterEqual => BinaryOperator::GreaterEqual,
+            LexType::And => BinaryOperator::And,
+            LexType::Or => BinaryOperator::Or,
+            _ => return None,
+        })
+    }
+
+    fn report_name_error(&mut self, context: &str) {
+        self.report(
+            self.get_location(),
+            ParseErrorCode::ExpectedIdentifier,
+            &format!(
+                "Expected identifier when parsing {}, got {}",
+                context,
+                self.get_lexeme_text()
+            ),
+        );
+    }
+
+    fn parse_name_opt(&mut self, context: &str) -> Option<(AstName, LexLocation)> {
+        let location = self.get_location();
+
+        if let LexType::Name(name) = self.get_lexeme() {
+            self.next_lexeme();
+
+            Some((name, location))
+        } else {
+            self.report_name_error(context);
+
+            None
+        }
+    }
+
+    fn parse_name(&mut self, context: &str) -> (AstName, LexLocation) {
+        if let Some(result) = self.parse_name_opt(context) {
+            result
+        } else {
+            // the error is already reported, recover with a placeholder name at the current lexeme
+            (self.name_error, self.get_location())
+        }
+    }
+
+    fn parse_name_expr(&mut self, context: &str) -> AstExprId {
+        let (name, location) = if let Some(result) = self.parse_name_opt(context) {
+            result
+        } else {
+            return self.add(ExprError::new(
+                self.get_location(),
+                Vec::new(),
+                self.errors.len() - 1,
+            ));
+        };
+
+        if let Some(&id) = self.local_map.get(&name) {
+            let upvalue = self.arena[id]
+                .get_function_depth()
+                .ne(&(self.function_stack.len() - 1));
+
+            self.add(ExprLocal::new(location, id, upvalue))
+        } else {
+            self.add(new_expr_global(location, name))
+        }
+    }
+
+    fn parse_prefix_expr(&mut self) -> AstExprId {
+        if self.get_lexeme().eq(&LexType::LeftRoundBracket) {
+            let start = self.get_location();
+
+            let match_paren = self.get_current();
+            self.next_lexeme();
+
+            let expr = self.parse_expr(0);
+
+            let end = self.get_location();
+
+            if self.get_lexeme().ne(&LexType::RightRoundBracket) {
+                self.expect_match_and_consume_fail(LexType::RightRoundBracket, &match_paren);
+
+                self.add(ExprError::new(
+                    LexLocation::new(start.get_begin(), end.get_end()),
+                    vec![expr],
+                    self.errors.len() - 1,
+                ))
+            } else {
+                self.next_lexeme();
+
+                self.add(new_expr_group(
+                    LexLocation::new(start.get_begin(), end.get_end()),
+                    expr,
+                ))
+            }
+        } else {
+            self.parse_name_expr("expression")
+        }
+    }
+
+    // tableconstructor ::= `{' [fieldlist] `}'
+    // fieldlist ::= field {fieldsep field} [fieldsep]
+    // field ::= `[' exp `]' `=' exp | Name `=' exp | exp
+    // fieldsep ::= `,' | `;'
+    fn parse_table_constructor(&mut self) -> AstExprId {
+        let mut items: Vec<TableItem> = Vec::new();
+        let start = self.get_location().get_begin();
+
+        let match_brace = self.get_current();
+        self.expect_and_consume(LexType::LeftCurlyBracket, "table literal");
+
+        while self.get_lexeme().ne(&LexType::RightCurlyBracket) {
+            match self.get_lexeme() {
+                LexType::LeftSquareBracket => {
+                    let match_location_bracket = self.get_current();
+                    self.next_lexeme();
+
+                    let key = self.parse_expr(0);
+
+                    self.expect_match_and_consume(
+                        LexType::RightSquareBracket,
+                        &match_location_bracket,
+                    );
+                    self.expect_and_consume(LexType::Assign, "table field");
+
+                    let value = self.parse_expr(0);
+
+                    items.push(TableItem::new(TableKind::General, Some(key), value));
+                }
+                LexType::Name(_) if self.get_ahead_lexeme().eq(&LexType::Assign) => {
+                    let (name, name_location) = self.parse_name("table field");
+
+                    self.expect_and_consume(LexType::Assign, "table field");
+
+                    let key = self.add(new_constant_string(
+                        name_location,
+                        self.lexer.get_names().get_value(name).as_bytes().to_vec(),
+                    ));
+                    let value = self.parse_expr(0);
+
+                    items.push(TableItem::new(TableKind::Record, Some(key), value));
+                }
+                _ => {
+                    let expr = self.parse_expr(0);
+
+                    items.push(TableItem::new(TableKind::List, None, expr));
+                }
+            }
+
+            if self.get_lexeme().eq(&LexType::Comma) || self.get_lexeme().eq(&LexType::Semicolon) {
+                self.next_lexeme();
+            } else if self.get_lexeme().ne(&LexType::RightCurlyBracket) {
+                break;
+            }
+        }
+
+        let mut end = self.get_location();
+
+        if !self.expect_match_and_consume(LexType::RightCurlyBracket, &match_brace) {
+            end = self.get_previous_location();
+        }
+
+        self.add(new_expr_table(
+            LexLocation::new(start, end.get_end()),
+            items,
+        ))
+    }
+
+    fn parse_expr_list(&mut self, args: &mut Vec<AstExprId>) {
+        args.push(self.parse_expr(0));
+
+        while self.get_lexeme().eq(&LexType::Comma) {
+            self.next_lexeme();
+            args.push(self.parse_expr(0));
+        }
+    }
+
+    fn report_ambiguous_call_error(&mut self) {
+        self.report(
+            self.get_location(), ParseErrorCode::AmbiguousCall,
+            "Ambiguous syntax: this looks like an argument list for a function call, but could also be a start of new statement; use ';' to separate statements",
+        );
+    }
+
+    fn parse_function_args_expr(&mut self, func: AstExprId, has_self: bool) -> AstExprId {
+        match self.get_lexeme() {
+            // <func>(<arg>[,<arg>])
+            LexType::LeftRoundBracket => {
+                let arg_start = self.get_location().get_end();
+
+                if self.arena[func].get_location().get_end().get_line()
+                    != self.get_location().get_begin().get_line()
+                {
+                    self.report_ambiguous_call_error();
+                }
+
+                let match_paren = self.get_current();
+                self.next_lexeme();
+
+                let mut args: Vec<AstExprId> = Vec::new();
+                if self.get_lexeme().ne(&LexType::RightRoundBracket) {
+                    self.parse_expr_list(&mut args);
+                }
+                let end = self.get_location();
+                let arg_end = end.get_end();
+
+                self.expect_match_and_consume(LexType::RightRoundBracket, &match_paren);
+
+                self.add(ExprCall::new(
+                    LexLocation::new(self.arena[func].get_location().get_begin(), end.get_end()),
+                    func,
+                    args,
+                    has_self,
+                    LexLocation::new(arg_start, arg_end),
+                ))
+            }
+            // <func>{<table>}
+            LexType::LeftCurlyBracket => {
+                let arg_start = self.get_location().get_end();
+                let expr = self.parse_table_constructor();
+                let arg_end = self.get_previous_location().get_end();
+
+                self.add(ExprCall::new(
+                    LexLocation::new(
+                        self.arena[func].get_location().get_begin(),
+                        self.arena[expr].get_location().get_end(),
+                    ),
+                    func,
+                    vec![expr],
+                    has_self,
+                    LexLocation::new(arg_start, arg_end),
+                ))
+            }
+            // <func>"string"
+            LexType::RawString(_) | LexType::QuotedString(_) => {
+                let arg_location = self.get_location();
+                let expr = self.parse_string_expr();
+
+                self.add(ExprCall::new(
+                    LexLocation::new(
+                        self.arena[func].get_location().get_begin(),
+                        self.arena[expr].get_location().get_end(),
+                    ),
+                    func,
+                    vec![expr],
+                    has_self,
+                    arg_location,
+                ))
+            }
+            _ => {
+                let location = self.get_location();
+
+                self.report_expr_error(
+                    LexLocation::new(
+                        self.arena[func].get_location().get_begin(),
+                        location.get_end(),
+                    ),
+                    ParseErrorCode::UnexpectedToken,
+                    vec![func],
+                    &format!(
+                        "Expected '(', '{{' or <string> when parsing function call, got {}",
+                        self.get_lexeme_text()
+                    ),
+                )
+            }
+        }
+    }
+
+    // primaryexp -> prefixexp { `.' NAME | `[' exp `]' | `:' NAME funcargs | funcargs }
+    fn parse_primary_expr(&mut self, as_statement: bool) -> AstExprId {
+        let start = self.get_location();
+        let recursion_counter_old = self.recursion_counter;
+
+        let mut expr = self.parse_prefix_expr();
+        let result = loop {
+            match self.get_lexeme() {
+                // <expr>.<index name>
+                LexType::Dot => {
+                    let op_position = self.get_location().get_begin();
+                    self.next_lexeme();
+
+                    let (index_name, index_location) = self.parse_name("field name");
+
+                    expr = self.add(ExprIndexName::new(
+                        LexLocation::new(start.get_begin(), index_location.get_end()),
+                        expr,
+                        index_name,
+                        index_location,
+                        op_position,
+                        '.',
+                    ));
+                }
+                // <expr>[<index_expr>]
+                LexType::LeftSquareBracket => {
+                    let match_bracket = self.get_current();
+                    self.next_lexeme();
+
+                    let index = self.parse_expr(0);
+                    let end = self.get_location();
+
+                    self.expect_match_and_consume(LexType::RightSquareBracket, &match_bracket);
+
+                    expr = self.add(ExprIndexExpr::new(
+                        LexLocation::new(start.get_begin(), end.get_end()),
+                        expr,
+                        index,
+                    ));
+                }
+                // <expr>:<index_name>
+                LexType::Colon => {
+                    let op_position = self.get_location().get_begin();
+                    self.next_lexeme();
+
+                    let (index, index_location) = self.parse_name("method name");
+                    let func = self.add(ExprIndexName::new(
+                        LexLocation::new(start.get_begin(), index_location.get_end()),
+                        expr,
+                        index,
+                        index_location,
+                        op_position,
+                        ':',
+                    ));
+
+                    expr = self.parse_function_args_expr(func, true);
+                }
+                // <expr>(...)
+                LexType::LeftRoundBracket => {
+                    // a call on a new line is ambiguous outside of statements, leave it to the caller
+                    if !as_statement
+                        && self.arena[expr].get_location().get_end().get_line()
+                            != self.get_location().get_begin().get_line()
+                    {
+                        self.report_ambiguous_call_error();
+                        break expr;
+                    }
+
+                    expr = self.parse_function_args_expr(expr, false);
+                }
+                // <expr>{<table>} | "<string>"
+                LexType::LeftCurlyBracket | LexType::QuotedString(_) | LexType::RawString(_) => {
+                    expr = self.parse_function_args_expr(expr, false);
+                }
+                _ => break expr,
+            }
+
+            // the loop isn't recursive but the trees it builds are nested without a bound
+            if let Some(message_index) = self.increment_recursion_counter("expression") {
+                let location = self.arena[expr].get_location();
+                break self.add(ExprError::new(location, vec![expr], message_index));
+            }
+        };
+
+        self.recursion_counter = recursion_counter_old;
+
+        result
+    }
+
+    fn parse_if_else_expr(&mut self) -> AstExprId {
+        let start = self.get_location().get_begin();
+        self.next_lexeme();
+
+        let condition = self.parse_expr(0);
+
+        self.expect_and_consume(LexType::Then, "if then else expression");
+
+        let true_expr = self.parse_expr(0);
+
+        match self.get_lexeme() {
+            LexType::ElseIf => {
+                let false_expr = self.parse_if_else_expr();
+
+                self.add(ExprIfElse::new(
+                    LexLocation::new(start, self.arena[false_expr].get_location().get_end()),
+                    condition,
+                    Some(true_expr),
+                    Some(false_expr),
+                ))
+            }
+            LexType::Else => {
+                self.next_lexeme();
+
+                let false_expr = self.parse_expr(0);
+
+                self.add(ExprIfElse::new(
+                    LexLocation::new(start, self.arena[false_expr].get_location().get_end()),
+                    condition,
+                    Some(true_expr),
+                    Some(false_expr),
+                ))
+            }
+            LexType::End => {
+                let end = self.get_location();
+                self.next_lexeme();
+
+                self.add(ExprIfElse::new(
+                    LexLocation::new(start, end.get_end()),
+                    condition,
+                    Some(true_expr),
+                    None,
+                ))
+            }
+            _ => {
+                let location = self.get_location();
+
+                self.report_expr_error(
+                    LexLocation::new(start, location.get_end()),
+                    ParseErrorCode::UnexpectedToken,
+                    vec![condition, true_expr],
+                    &format!(
+                        "Expected 'else' when parsing if then else expression, got {}",
+                        self.get_lexeme_text()
+                    ),
+                )
+            }
+        }
+    }
+
+    fn parse_simple_expr(&mut self) -> AstExprId {
+        let start = self.get_location();
+
+        match self.get_lexeme() {
+            LexType::Nil => self.parse_nil_expr(),
+            LexType::True | LexType::False => self.parse_bool_expr(),
+            LexType::QuotedString(_) | LexType::RawString(_) => self.parse_string_expr(),
+            LexType::BrokenString => {
+                let lexeme = self.get_current();
+                self.next_lexeme();
+
+                let message_index =
+                    self.report_lexeme_error(&lexeme, ParseErrorCode::MalformedString);
+                self.add(ExprError::new(start, Vec::new(), message_index))
+            }
+            LexType::InterpStringBegin(_) | LexType::InterpStringSimple(_) => {
+                self.parse_interp_string()
+            }
+            LexType::BrokenInterpDoubleBrace => {
+                self.next_lexeme();
+
+                let message_index = self.report_interp_double_brace_error(start);
+                self.add(ExprError::new(start, Vec::new(), message_index))
+            }
+            LexType::Number(_) => self.parse_number_expr(),
+            LexType::Function => {
+                let match_function = self.get_current();
+                self.next_lexeme();
+
+                let debug_name = self.intern("");
+                let (function, location) =
+                    self.parse_function_body(false, &match_function, debug_name);
+
+                self.add(new_expr_function(location, function))
+            }
+            LexType::Dot3 => {
+                self.next_lexeme();
+
+                let (vararg, _) = *self.function_stack.last().unwrap();
+                if vararg {
+                    self.add(new_expr_varargs(start))
+                } else {
+                    self.report_expr_error(
+                        start,
+                        ParseErrorCode::VarargOutsideFunction,
+                        Vec::new(),
+                        "Cannot use '...' outside of a vararg function",
+                    )
+                }
+            }
+            LexType::LeftCurlyBracket => self.parse_table_constructor(),
+            LexType::If => self.parse_if_else_expr(),
+            _ => self.parse_primary_expr(false),
+        }
+    }
+
+    fn parse_assertion_expr(&mut self) -> AstExprId {
+        let begin = self.get_location().get_begin();
+        let expr = self.parse_simple_expr();
+
+        if self.get_lexeme().eq(&LexType::DoubleColon) {
+            self.next_lexeme();
+
+            let annotation = self.parse_type_annotation();
+
+            self.add(ExprTypeAssertion::new(
+                LexLocation::new(begin, self.arena[annotation].get_location().get_end()),
+                expr,
+                annotation,
+            ))
+        } else {
+            expr
+        }
+    }
+
+    // expr ::= (simpleexp | unop expr) { binop expr }
+    fn parse_expr(&mut self, limit: usize) -> AstExprId {
+        let start = self.get_location();
+
+        let recursion_counter_old = self.recursion_counter;
+        if let Some(message_index) = self.increment_recursion_counter("expression") {
+            self.recursion_counter = recursion_counter_old;
+
+            return self.add(ExprError::new(start, Vec::new(), message_index));
+        }
+
+        let mut expr = if let Some(operator) = self.parse_unary_operator(self.get_lexeme()) {
+            self.next_lexeme();
+            let sub_expr = self.parse_expr(operator.priority());
+
+            self.add(ExprUnary::new(
+                LexLocation::new(
+                    start.get_begin(),
+                    self.arena[sub_expr].get_location().get_end(),
+                ),
+                operator,
+                sub_expr,
+            ))
+        } else {
+            self.parse_assertion_expr()
+        };
+
+        // expand while operators have priorities higher than `limit'
+        let mut operator = self.parse_binary_operator(self.get_lexeme());
+        while let Some(current_operator) = operator {
+            if current_operator.left_priority() <= limit {
+                break;
+            }
+
+            self.next_lexeme();
+
+            // read sub-expression with higher priority
+            let next = self.parse_expr(current_operator.right_priority());
+            expr = self.add(ExprBinary::new(
+                LexLocation::new(start.get_begin(), self.arena[next].get_location().get_end()),
+                current_operator,
+                expr,
+                next,
+            ));
+
+            operator = self.parse_binary_operator(self.get_lexeme());
+
+            // the loop isn't recursive but the trees it builds are nested without a bound
+            if let Some(message_index) = self.increment_recursion_counter("expression") {
+                let location = self.arena[expr].get_location();
+                expr = self.add(ExprError::new(location, vec![expr], message_index));
+                break;
+            }
+        }
+
+        self.recursion_counter = recursion_counter_old;
+
+        expr
+    }
+
+    fn parse_nil_type(&mut self) -> AstTypeId {
+        let location = self.get_location();
+
+        if self.get_lexeme().eq(&LexType::Nil) {
+            self.next_lexeme();
+
+            self.add(TypeReference::new(location, None, self.name_nil, None))
+        } else {
+            self.report_type_error(
+                location,
+                ParseErrorCode::UnexpectedToken,
+                Vec::new(),
+                true,
+                &format!("Expected nil, got {}", self.get_lexeme_text()),
+            )
+        }
+    }
+
+    fn parse_bool_type(&mut self) -> AstTypeId {
+        let location = self.get_location();
+
+        match self.get_lexeme() {
+            LexType::True => {
+                self.next_lexeme();
+
+                self.add(new_type_singleton_bool(location, true))
+            }
+            LexType::False => {
+                self.next_lexeme();
+
+                self.add(new_type_singleton_bool(location, false))
+            }
+            _ => self.report_type_error(
+                location,
+                ParseErrorCode::UnexpectedToken,
+                Vec::new(),
+                true,
+                &format!("Expected true or false, got {}", self.get_lexeme_text()),
+            ),
+        }
+    }
+
+    fn parse_string_type(&mut self) -> AstTypeId {
+        let location = self.get_location();
+
+        match self.get_lexeme() {
+            LexType::RawString(_) | LexType::QuotedString(_) => match self.parse_char_array() {
+                Ok(value) => self.add(new_type_singleton_string(location, value)),
+                Err(message_index) => {
+                    self.add(TypeError::new(location, Vec::new(), false, message_index))
+                }
+            },
+            LexType::BrokenString => {
+                let lexeme = self.get_current();
+                self.next_lexeme();
+
+                let message_index =
+                    self.report_lexeme_error(&lexeme, ParseErrorCode::MalformedString);
+                self.add(TypeError::new(location, Vec::new(), false, message_index))
+            }
+            _ => self.report_type_error(
+                location,
+                ParseErrorCode::UnexpectedToken,
+                Vec::new(),
+                true,
+                &format!("Expected string, got {}", self.get_lexeme_text()),
+            ),
+        }
+    }
+
+    fn parse_typeof_type(&mut self, begin: LexPosition) -> AstTypeId {
+        let typeof_begin = self.get_current();
+        self.expect_and_consume(LexType::LeftRoundBracket, "typeof type");
+
+        let expr = self.parse_expr(0);
+        let end = self.get_location().get_end();
+
+        self.expect_match_and_consume(LexType::RightRoundBracket, &typeof_begin);
+
+        self.add(new_type_typeof(LexLocation::new(begin, end), expr))
+    }
+
+    fn should_parse_type_pack_annotation(&self) -> bool {
+        match self.get_lexeme() {
+            LexType::Dot3 => true,
+            LexType::Name(_) => self.get_ahead_lexeme().eq(&LexType::Dot3),
+            _ => false,
+        }
+    }
+
+    fn parse_type_annotation(&mut self) -> AstTypeId {
+        let begin = self.get_location().get_begin();
+        let mut parts: Vec<AstTypeId> = Vec::new();
+
+        let recursion_counter_old = self.recursion_counter;
+        if let Some(message_index) = self.increment_recursion_counter("type annotation") {
+            self.recursion_counter = recursion_counter_old;
+
+            return self.add(TypeError::new(
+                self.get_location(),
+                Vec::new(),
+                false,
+                message_index,
+            ));
+        }
+
+        if let (Some(value), _) = self.parse_simple_type_annotation(false) {
+            parts.push(value);
+        }
+
+        let result = self.parse_type_annotation_parts(parts, begin);
 
-        Ok(match self.get_lexeme() {
-            LexType::Nil => (Some(self.parse_nil_type()?), None),
-            LexType::True | LexType::False => (Some(self.parse_bool_type()?), None),
+        self.recursion_counter = recursion_counter_old;
+
+        result
+    }
+
+    fn parse_type_annotation_parts(
+        &mut self,
+        mut parts: Vec<AstTypeId>,
+        begin: LexPosition,
+    ) -> AstTypeId {
+        let mut is_union = false;
+        let mut is_intersection = false;
+        loop {
+            match self.get_lexeme() {
+                LexType::SingletonOr => {
+                    self.next_lexeme();
+
+                    if let (Some(value), _) = self.parse_simple_type_annotation(false) {
+                        parts.push(value);
+                    }
+                    is_union = true;
+                }
+                LexType::QuestionMark => {
+                    let location = self.get_location();
+
+                    self.next_lexeme();
+                    parts.push(self.add(TypeReference::new(location, None, self.name_nil, None)));
+                    is_union = true;
+                }
+                LexType::SingletonAnd => {
+                    self.next_lexeme();
+
+                    if let (Some(value), _) = self.parse_simple_type_annotation(false) {
+                        parts.push(value);
+                    }
+                    is_intersection = true;
+                }
+                LexType::Dot3 => {
+                    self.report(
+                        self.get_location(),
+                        ParseErrorCode::UnexpectedTypePack,
+                        "Unexpected '...' after type annotation",
+                    );
+                    self.next_lexeme();
+                }
+                _ => break,
+            }
+        }
+
+        if parts.len().eq(&1) {
+            parts.pop().unwrap()
+        } else {
+            let end = self.arena[*parts.last().unwrap()].get_location().get_end();
+            let location = LexLocation::new(begin, end);
+
+            if is_union && is_intersection {
+                self.report_type_error(
+                    location, ParseErrorCode::MixedUnionIntersection,
+                    parts,
+                    false,
+                    "Mixing union and intersection types is not allowed; consider wrapping in parentheses.",
+                )
+            } else if is_union {
+                self.add(new_type_union(location, parts))
+            } else {
+                self.add(new_type_intersection(location, parts))
+            }
+        }
+    }
+
+    fn parse_type_pack_annotation(&mut self) -> Option<AstTypePackId> {
+        // variadic: ...T
+        if self.get_lexeme().eq(&LexType::Dot3) {
+            let begin = self.get_location().get_begin();
+            self.next_lexeme();
+
+            let vararg = self.parse_type_annotation();
+
+            return Some(self.add(new_type_pack_variadic(
+                LexLocation::new(begin, self.arena[vararg].get_location().get_end()),
+                vararg,
+            )));
+        }
+
+        // generic: a...
+        if let LexType::Name(_) = self.get_lexeme() {
+            if self.get_ahead_lexeme().eq(&LexType::Dot3) {
+                let (name, name_location) = self.parse_name("generic name");
+
+                let end = self.get_location();
+                // this will not fail because of the lookahead guard
+                self.expect_and_consume(LexType::Dot3, "generic type pack annotation");
+
+                return Some(self.add(new_type_pack_generic(
+                    LexLocation::new(name_location.get_begin(), end.get_end()),
+                    name,
+                )));
+            }
+        }
+
+        // no type pack annotation exists here
+        None
+    }
+
+    fn parse_type_or_pack_annotation(&mut self) -> AstTypeOrPack {
+        let begin = self.get_location().get_begin();
+
+        match self.parse_simple_type_annotation(true) {
+            (_, Some(type_pack)) => AstTypeOrPack::TypePack(type_pack),
+            (Some(type_), None) => {
+                AstTypeOrPack::Type(self.parse_type_annotation_parts(vec![type_], begin))
+            }
+            (None, None) => AstTypeOrPack::Type(self.report_type_error(
+                self.get_location(),
+                ParseErrorCode::ExpectedType,
+                Vec::new(),
+                true,
+                &format!("Expected type, got {}", self.get_lexeme_text()),
+            )),
+        }
+    }
+
+    fn parse_type_parameters(&mut self) -> Option<Vec<AstTypeOrPack>> {
+        let mut parameters: Vec<AstTypeOrPack> = Vec::new();
+
+        if self.get_lexeme().eq(&LexType::Less) {
+            let begin = self.get_current();
+            self.next_lexeme();
+
+            loop {
+                if self.should_parse_type_pack_annotation() {
+                    if let Some(type_pack) = self.parse_type_pack_annotation() {
+                        parameters.push(AstTypeOrPack::TypePack(type_pack));
+                    }
+                } else if self.get_lexeme().eq(&LexType::LeftRoundBracket) {
+                    parameters.push(self.parse_type_or_pack_annotation());
+                } else if self.get_lexeme().eq(&LexType::Greater) && parameters.is_empty() {
+                    break;
+                } else {
+                    let type_ = self.parse_type_annotation();
+                    parameters.push(AstTypeOrPack::Type(type_));
+                }
+
+                if self.get_lexeme().eq(&LexType::Comma) {
+                    self.next_lexeme();
+                } else {
+                    break;
+                }
+            }
+
+            self.expect_match_and_consume(LexType::Greater, &begin);
+
+            return Some(parameters);
+        }
+
+        None
+    }
+
+    fn parse_name_type(&mut self, begin: LexPosition, name: AstName) -> AstTypeId {
+        let mut real_name = name;
+        let mut prefix: Option<AstName> = None;
+
+        if self.get_lexeme().eq(&LexType::Dot) {
+            self.next_lexeme();
+
+            let (sub_name, _) = self.parse_name("field name");
+            prefix = Some(real_name);
+            real_name = sub_name;
+        } else if self.get_lexeme().eq(&LexType::Dot3) {
+            self.report(
+                self.get_location(),
+                ParseErrorCode::UnexpectedTypePack,
+                "Unexpected '...' after type name; type pack is not allowed in this context",
+            );
+            self.next_lexeme();
+        }
+
+        let parameters = self.parse_type_parameters();
+
+        let end = self.get_previous_location().get_end();
+        self.add(TypeReference::new(
+            LexLocation::new(begin, end),
+            prefix,
+            real_name,
+            parameters,
+        ))
+    }
+
+    fn parse_name_or_typeof_type(&mut self) -> AstTypeId {
+        let begin = self.get_location().get_begin();
+        let (name, _) = self.parse_name("type name");
+
+        if self.is_name(name, "typeof") && self.get_lexeme().ne(&LexType::Dot) {
+            self.parse_typeof_type(begin)
+        } else {
+            self.parse_name_type(begin, name)
+        }
+    }
+
+    fn parse_type_indexer_annotation(&mut self) -> TableIndexer {
+        let begin = self.get_current();
+        self.next_lexeme(); // skip [
+
+        let index = self.parse_type_annotation();
+
+        self.expect_match_and_consume(LexType::RightSquareBracket, &begin);
+        self.expect_and_consume(LexType::Colon, "table field");
+
+        let result = self.parse_type_annotation();
+        let location = LexLocation::new(
+            begin.get_location().get_begin(),
+            self.arena[result].get_location().get_end(),
+        );
+
+        TableIndexer::new(index, result, location)
+    }
+
+    fn parse_table_type_annotation(&mut self) -> AstTypeId {
+        let mut props: Vec<TableProp> = Vec::new();
+        let mut indexer: Option<TableIndexer> = None;
+
+        let begin = self.get_location().get_begin();
+
+        let match_brace = self.get_current();
+        self.expect_and_consume(LexType::LeftCurlyBracket, "table type");
+
+        while self.get_lexeme().ne(&LexType::RightCurlyBracket) {
+            let is_string_key = matches!(
+                self.get_ahead_lexeme(),
+                LexType::RawString(_) | LexType::QuotedString(_)
+            );
+
+            if self.get_lexeme().eq(&LexType::LeftSquareBracket) && is_string_key {
+                // [<string>]: <type>
+                let match_bracket = self.get_current();
+                self.next_lexeme(); // skip [
+
+                let name = self.parse_string_name();
+
+                self.expect_match_and_consume(LexType::RightSquareBracket, &match_bracket);
+                self.expect_and_consume(LexType::Colon, "table field");
+
+                let type_ = self.parse_type_annotation();
+
+                props.push(TableProp::new(name, match_bracket.get_location(), type_));
+            } else if self.get_lexeme().eq(&LexType::LeftSquareBracket) {
+                // [<type>]: <type>
+                if let Some(first_indexer) = &indexer {
+                    let first_location = first_indexer.get_location();
+                    let bad_indexer = self.parse_type_indexer_annotation();
+
+                    self.report_error(
+                        ParseError::new(
+                            ParseErrorCode::DuplicateTableIndexer,
+                            bad_indexer.get_location(),
+                            "Cannot have more than one table indexer",
+                        )
+                        .with_label(first_location, "first indexer declared here"),
+                    );
+                } else {
+                    indexer = Some(self.parse_type_indexer_annotation());
+                }
+            } else if props.is_empty()
+                && indexer.is_none()
+                && !(matches!(self.get_lexeme(), LexType::Name(_))
+                    && self.get_ahead_lexeme().eq(&LexType::Colon))
+            {
+                // array-like table type: {T} desugars into {[number]: T}
+                let type_ = self.parse_type_annotation();
+                let location = self.arena[type_].get_location();
+
+                indexer = Some(TableIndexer::new(
+                    self.add(TypeReference::new(location, None, self.name_number, None)),
+                    type_,
+                    location,
+                ));
+
+                break;
+            } else {
+                // <name>: <type>
+                let (name, name_location) = self.parse_name("table field");
+
+                self.expect_and_consume(LexType::Colon, "table field");
+
+                let type_ = self.parse_type_annotation();
+
+                props.push(TableProp::new(name, name_location, type_));
+            }
+
+            if self.get_lexeme().eq(&LexType::Comma) || self.get_lexeme().eq(&LexType::Semicolon) {
+                self.next_lexeme();
+            } else if self.get_lexeme().ne(&LexType::RightCurlyBracket) {
+                break;
+            }
+        }
+
+        let mut end = self.get_location();
+
+        if !self.expect_match_and_consume_search(LexType::RightCurlyBracket, &match_brace) {
+            end = self.get_previous_location();
+        }
+
+        self.add(TypeTable::new(
+            LexLocation::new(begin, end.get_end()),
+            props,
+            indexer,
+        ))
+    }
+
+    // GenericTypeList ::= `<' Name [`=' TypeAnnotation] {`,' ...} {`,' Name `...' [`=' TypePack]} `>'
+    fn parse_generic_type_list(
+        &mut self,
+        with_default_values: bool,
+    ) -> (Vec<AstGenericType>, Vec<AstGenericTypePack>) {
+        let mut names: Vec<AstGenericType> = Vec::new();
+        let mut name_packs: Vec<AstGenericTypePack> = Vec::new();
+
+        if self.get_lexeme().eq(&LexType::Less) {
+            let begin = self.get_current();
+            self.next_lexeme();
+
+            let mut seen_pack = false;
+            let mut seen_default = false;
+
+            loop {
+                let (name, name_location) = self.parse_name("generic name");
+
+                if self.get_lexeme() == LexType::Dot3 || seen_pack {
+                    seen_pack = true;
+
+                    if self.get_lexeme() == LexType::Dot3 {
+                        self.next_lexeme();
+                    } else {
+                        self.report(
+                            self.get_location(),
+                            ParseErrorCode::GenericOrder,
+                            "Generic types come before generic type packs",
+                        );
+                    }
+
+                    if with_default_values && self.get_lexeme() == LexType::Assign {
+                        seen_default = true;
+                        self.next_lexeme();
+
+                        let type_pack = if self.should_parse_type_pack_annotation() {
+                            self.parse_type_pack_annotation()
+                        } else {
+                            match self.parse_simple_type_annotation(true) {
+                                (_, Some(type_pack)) => Some(type_pack),
+                                (Some(type_), None) => {
+                                    self.report(
+                                        self.arena[type_].get_location(),
+                                        ParseErrorCode::ExpectedTypePack,
+                                        "Expected type pack after '=', got type",
+                                    );
+
+                                    None
+                                }
+                                (None, None) => None,
+                            }
+                        };
+
+                        name_packs.push(AstGenericTypePack::new(name, name_location, type_pack));
+                    } else {
+                        if seen_default {
+                            self.report(
+                                self.get_location(),
+                                ParseErrorCode::MissingGenericDefault,
+                                "Expected default type pack after type pack name",
+                            );
+                        }
+
+                        name_packs.push(AstGenericTypePack::new(name, name_location, None));
+                    }
+                } else if with_default_values && self.get_lexeme().eq(&LexType::Assign) {
+                    seen_default = true;
+                    self.next_lexeme();
+
+                    let default_value = self.parse_type_annotation();
+
+                    names.push(AstGenericType::new(
+                        name,
+                        name_location,
+                        Some(default_value),
+                    ));
+                } else {
+                    if seen_default {
+                        self.report(
+                            self.get_location(),
+                            ParseErrorCode::MissingGenericDefault,
+                            "Expected default type after type name",
+                        );
+                    }
+
+                    names.push(AstGenericType::new(name, name_location, None));
+                }
+
+                if self.get_lexeme() == LexType::Comma {
+                    self.next_lexeme();
+                } else {
+                    break;
+                }
+            }
+
+            self.expect_match_and_consume(LexType::Greater, &begin);
+        }
+
+        (names, name_packs)
+    }
+
+    // FunctionType ::= [`<' varlist `>'] `(' [TypeList] `)' `->` ReturnType
+    fn parse_function_type_annotation(
+        &mut self,
+        allow_pack: bool,
+    ) -> (Option<AstTypeId>, Option<AstTypePackId>) {
+        let monomorphic = self.get_lexeme().ne(&LexType::Less);
+
+        let begin = self.get_current();
+
+        let (generics, generic_packs) = self.parse_generic_type_list(false);
+
+        let parameter_start = self.get_current();
+        self.expect_and_consume(LexType::LeftRoundBracket, "function parameters");
+
+        let mut params: Vec<AstTypeId> = Vec::new();
+        let mut names: Vec<Option<AstArgumentName>> = Vec::new();
+        let mut vararg_annotation: Option<AstTypePackId> = None;
+
+        if self.get_lexeme().ne(&LexType::RightRoundBracket) {
+            vararg_annotation = self.parse_type_list(&mut params, &mut names);
+        }
+
+        self.expect_match_and_consume(LexType::RightRoundBracket, &parameter_start);
+
+        let force_function_type = !names.is_empty();
+        let return_type_introducer =
+            self.get_lexeme().eq(&LexType::SkinnyArrow) || self.get_lexeme().eq(&LexType::Colon);
+
+        // not a function at all, just a parenthesized type or maybe a type pack with a single element
+        if params.len() == 1
+            && vararg_annotation.is_none()
+            && monomorphic
+            && !return_type_introducer
+        {
+            return if allow_pack {
+                (
+                    None,
+                    Some(self.add(new_type_pack_explicit(
+                        begin.get_location(),
+                        Box::new(AstTypeList::new(params, None)),
+                    ))),
+                )
+            } else {
+                (params.pop(), None)
+            };
+        }
+
+        if !force_function_type && !return_type_introducer && allow_pack {
+            return (
+                None,
+                Some(self.add(new_type_pack_explicit(
+                    begin.get_location(),
+                    Box::new(AstTypeList::new(params, vararg_annotation)),
+                ))),
+            );
+        }
+
+        (
+            Some(
+                self.parse_function_type_annotation_tail(
+                    &begin,
+                    generics.iter().map(|generic| generic.get_name()).collect(),
+                    generic_packs
+                        .iter()
+                        .map(|generic| generic.get_name())
+                        .collect(),
+                    AstTypeList::new(params, vararg_annotation),
+                    names,
+                ),
+            ),
+            None,
+        )
+    }
+
+    fn parse_function_type_annotation_tail(
+        &mut self,
+        begin: &Lexeme,
+        generics: Vec<AstName>,
+        generic_packs: Vec<AstName>,
+        params: AstTypeList,
+        param_names: Vec<Option<AstArgumentName>>,
+    ) -> AstTypeId {
+        // users occasionally write '()' as the 'unit' type when they actually want to use 'nil'
+        if self.get_lexeme().ne(&LexType::SkinnyArrow)
+            && generics.is_empty()
+            && generic_packs.is_empty()
+            && params.get_types().is_empty()
+            && params.get_tail_type().is_none()
+        {
+            self.report_error(
+                ParseError::new(
+                    ParseErrorCode::UnitType,
+                    LexLocation::new(
+                        begin.get_location().get_begin(),
+                        self.get_previous_location().get_end(),
+                    ),
+                    "Expected '->' after '()' when parsing function type; did you mean 'nil'?",
+                )
+                .with_suggestion("nil"),
+            );
+
+            return self.add(TypeReference::new(
+                begin.get_location(),
+                None,
+                self.name_nil,
+                None,
+            ));
+        }
+
+        self.expect_and_consume(LexType::SkinnyArrow, "function type");
+
+        let (end, return_types) = self.parse_return_type_annotation();
+
+        self.add(TypeFunction::new(
+            LexLocation::new(begin.get_location().get_begin(), end.get_end()),
+            generics,
+            generic_packs,
+            params,
+            param_names,
+            return_types,
+        ))
+    }
+
+    fn parse_simple_type_annotation(
+        &mut self,
+        allow_pack: bool,
+    ) -> (Option<AstTypeId>, Option<AstTypePackId>) {
+        match self.get_lexeme() {
+            LexType::Nil => (Some(self.parse_nil_type()), None),
+            LexType::True | LexType::False => (Some(self.parse_bool_type()), None),
             LexType::QuotedString(_) | LexType::RawString(_) | LexType::BrokenString => {
-                (Some(self.parse_string_type()?), None)
+                (Some(self.parse_string_type()), None)
             }
-            LexType::Name(_) => (Some(self.parse_name_or_typeof_type()?), None),
-            LexType::LeftCurlyBracket => (Some(self.parse_table_type_annotation()?), None),
-            // TODO function type annotation
-            _ => return Err(self.report_type_error(true, "unexpected type")),
-        })
+            LexType::InterpStringBegin(_) | LexType::InterpStringSimple(_) => {
+                let location = self.get_location();
+                self.parse_interp_string();
+
+                (
+                    Some(self.report_type_error(
+                        location,
+                        ParseErrorCode::UnexpectedToken,
+                        Vec::new(),
+                        false,
+                        "Interpolated string literals cannot be used as types",
+                    )),
+                    None,
+                )
+            }
+            LexType::Name(_) => (Some(self.parse_name_or_typeof_type()), None),
+            LexType::LeftCurlyBracket => (Some(self.parse_table_type_annotation()), None),
+            LexType::LeftRoundBracket | LexType::Less => {
+                self.parse_function_type_annotation(allow_pack)
+            }
+            _ => {
+                // for a missing type annotation, capture 'space' between last token and the next one
+                let location = LexLocation::new(
+                    self.get_previous_location().get_end(),
+                    self.get_location().get_begin(),
+                );
+
+                (
+                    Some(self.report_type_error(
+                        location,
+                        ParseErrorCode::ExpectedType,
+                        Vec::new(),
+                        true,
+                        &format!("Expected type, got {}", self.get_lexeme_text()),
+                    )),
+                    None,
+                )
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::super::test_util::{block_body, expr_shape, return_list};
     use super::*;
 
     #[test]
     fn parse_name() {
-        let expect = vec![String::from("name_1"), String::from("name_2")];
+        let expect = ["name_1", "name_2"];
         let mut parser = Parser::new("name_1 name_2");
 
-        for i in 0..2 {
-            if let Ok(result) = parser.parse_name() {
-                assert_eq!(result.0, AstName::new(expect.get(i).unwrap().clone()));
-
-                parser.next_lexeme();
-            } else {
-                panic!("failed");
-            }
+        for expect_name in expect.iter() {
+            let result = parser.parse_name("test");
+            assert_eq!(parser.lexer.get_names().get_value(result.0), *expect_name);
         }
     }
 
     #[test]
     fn parse_string() {
-        let expect = vec![String::from("foo"), String::from("bar")];
+        let expect = [b"foo".to_vec(), b"bar".to_vec()];
 
         let mut parser = Parser::new("\"foo\"   'bar'");
         for i in 0..2 {
-            if let Ok(result) = parser.parse_string_expr() {
-                if let AstNodePayload::ExprConstantString(value) = result.get_payload() {
-                    assert_eq!(value, *expect.get(i).unwrap());
-                } else {
-                    panic!("failed");
-                }
+            let result = parser.parse_string_expr();
+            if let Expr::ConstantString(value) = parser.arena[result].get_payload() {
+                assert_eq!(value, expect.get(i).unwrap());
             } else {
                 panic!("failed");
             }
@@ -1200,15 +3269,12 @@ mod tests {
         let test_fn = |t: &str, expect_value: f64| {
             let mut parser = Parser::new(t);
 
-            if let Ok(result) = parser.parse_number_expr() {
-                if let AstNodePayload::ExprConstantNumber(value) = result.get_payload() {
-                    assert_eq!(value, expect_value);
-                } else {
-                    panic!("failed");
-                }
+            let result = parser.parse_number_expr();
+            if let Expr::ConstantNumber(value) = parser.arena[result].get_payload() {
+                assert_eq!(*value, expect_value);
             } else {
                 panic!("failed");
-            };
+            }
         };
 
         test_fn("0b11001110", 0xCE as f64);
@@ -1219,20 +3285,101 @@ mod tests {
         test_fn(".32", 0.32_f64);
         test_fn("0.32_33", 0.3233_f64);
         test_fn("103_4.32_33", 1034.3233_f64);
+        test_fn("1.", 1.0);
+        test_fn("1e3", 1000.0);
+        test_fn("2.5E-1", 0.25);
+        test_fn("1e400", f64::INFINITY);
+        test_fn("0xffffffffffffffff", u64::MAX as f64);
+        // integers wider than 64 bits saturate like strtoull
+        test_fn("0x1_0000_0000_0000_0000", u64::MAX as f64);
+        test_fn(
+            "0b11111111111111111111111111111111111111111111111111111111111111111",
+            u64::MAX as f64,
+        );
+    }
+
+    #[test]
+    fn parse_number_errors() {
+        for src in [
+            "1..2", "0x", "0b", "1e", "1e+", "0x1p4", "0b102", "1.2.3", "12abc", "0xg",
+        ]
+        .iter()
+        {
+            let result = Parser::parse(&format!("local a = {}", src), ParseOptions::default());
+            let errors = result.get_errors();
+
+            assert_eq!(errors.len(), 1, "{}", src);
+            assert_eq!(errors[0].get_code(), ParseErrorCode::MalformedNumber);
+            assert_eq!(errors[0].get_message(), "Malformed number");
+            assert_eq!(
+                errors[0].get_location(),
+                LexLocation::new(
+                    LexPosition::new(0, 10),
+                    LexPosition::new(0, 10 + src.len() as u32)
+                ),
+                "{}",
+                src
+            );
+        }
+
+        assert_eq!(
+            to_number("9007199254740993"),
+            (9007199254740992.0, NumberParseResult::Imprecise)
+        );
+        assert_eq!(
+            to_number("9007199254740992"),
+            (9007199254740992.0, NumberParseResult::Ok)
+        );
+        assert_eq!(
+            to_number("0x10000000000000000").1,
+            NumberParseResult::HexOverflow
+        );
+        assert_eq!(to_number("1_000").1, NumberParseResult::Malformed);
+
+        let warning = |src: &str| {
+            let result = Parser::parse(&format!("local a = {}", src), ParseOptions::default());
+            let errors = result.get_errors();
+            assert_eq!(errors.len(), 1, "{}", src);
+            assert_eq!(errors[0].get_code(), ParseErrorCode::ImpreciseNumber);
+            assert_eq!(errors[0].get_severity(), Severity::Warning);
+
+            String::from(errors[0].get_message())
+        };
+        assert_eq!(
+            warning("9007199254740993"),
+            "Number literal exceeded available precision and was truncated to closest representable number"
+        );
+        assert_eq!(
+            warning("0x1ffffffffffffffff"),
+            "Hexadecimal number literal exceeded available precision and was truncated to 2^64"
+        );
+        assert_eq!(
+            warning("0b1_0000000000000000000000000000000000000000000000000000000000000000"),
+            "Binary number literal exceeded available precision and was truncated to 2^64"
+        );
+
+        // integers that convert back to the same text are exact
+        for src in [
+            "9007199254740992",
+            "18446744073709551616",
+            "0xffffffffffffffff",
+        ]
+        .iter()
+        {
+            let result = Parser::parse(&format!("local a = {}", src), ParseOptions::default());
+            assert!(result.get_errors().is_empty(), "{}", src);
+        }
     }
 
     #[test]
     fn parse_expr_bool() {
-        let expect = vec![true, false];
+        let expect = [true, false];
         let mut parser = Parser::new("true false");
 
         for i in 0..2 {
-            if let Ok(result) = parser.parse_bool_expr() {
-                if let AstNodePayload::ExprConstantBool(value) = result.get_payload() {
-                    assert_eq!(value, *expect.get(i).unwrap());
-                } else {
-                    panic!("failed");
-                }
+            let result = parser.parse_bool_expr();
+            if let Expr::ConstantBool(value) = parser.arena[result].get_payload() {
+                assert_eq!(value, expect.get(i).unwrap());
             } else {
                 panic!("failed");
             }
@@ -1242,13 +3389,1372 @@ mod tests {
     #[test]
     fn parse_expr_nil() {
         let mut parser = Parser::new("nil");
-        if let Ok(result) = parser.parse_nil_expr() {
+        let result = parser.parse_nil_expr();
+        assert_eq!(
+            Expr::ConstantNil.get_type(),
+            parser.arena[result].get_payload().get_type()
+        );
+    }
+
+    fn parse_block_types(src: &str) -> (Vec<AstNodePayloadType>, Vec<String>) {
+        let result = Parser::parse(src, ParseOptions::default());
+
+        let types = if let Stat::Block(body) = result.get_root().get_payload() {
+            body.iter()
+                .map(|&stat| result.get_arena()[stat].get_payload_type())
+                .collect()
+        } else {
+            panic!("failed");
+        };
+        let errors = result
+            .get_errors()
+            .iter()
+            .map(|error| String::from(error.get_message()))
+            .collect();
+
+        (types, errors)
+    }
+
+    #[test]
+    fn parse_stat() {
+        let (types, errors) = parse_block_types(
+            "local a, b = 1, 2
+            a, b = b, a
+            print(a)
+            if a then elseif b then else end
+            while true do break end
+            repeat local c = 1 until c
+            for i = 1, 10, 2 do end
+            for k, v in pairs(t) do end
+            do end
+            function t.foo:bar(x, ...) return x, ... end
+            local function baz() end
+            type Foo<T> = { T }
+            export type Bar = string
+            return a;",
+        );
+
+        assert_eq!(errors, Vec::<String>::new());
+        assert_eq!(
+            types,
+            vec![
+                AstNodePayloadType::StatLocal,
+                AstNodePayloadType::StatAssign,
+                AstNodePayloadType::StatExpr,
+                AstNodePayloadType::StatIf,
+                AstNodePayloadType::StatWhile,
+                AstNodePayloadType::StatRepeat,
+                AstNodePayloadType::StatFor,
+                AstNodePayloadType::StatForIn,
+                AstNodePayloadType::StatBlock,
+                AstNodePayloadType::StatFunction,
+                AstNodePayloadType::StatLocalFunction,
+                AstNodePayloadType::StatTypeAlias,
+                AstNodePayloadType::StatTypeAlias,
+                AstNodePayloadType::StatReturn,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_stat_errors() {
+        let (types, errors) = parse_block_types("break");
+        assert_eq!(types, vec![AstNodePayloadType::StatError]);
+        assert_eq!(errors, vec!["break statement must be inside a loop"]);
+
+        let (_, errors) = parse_block_types("(a) = 1");
+        assert_eq!(
+            errors,
+            vec!["Assigned expression must be a variable or a field"]
+        );
+
+        let (_, errors) = parse_block_types("a");
+        assert_eq!(
+            errors,
+            vec!["Incomplete statement: expected assignment or a function call"]
+        );
+
+        let (_, errors) = parse_block_types("while true do\nlocal a = 1");
+        assert_eq!(
+            errors,
+            vec!["Expected 'end' (to close 'do' at line 1), got <eof>"]
+        );
+
+        let (_, errors) = parse_block_types("return 1 2");
+        assert_eq!(errors, vec!["Expected <eof>, got '2'"]);
+    }
+
+    #[test]
+    fn parse_hot_comments() {
+        let result = Parser::parse(
+            "--!strict\n-- comment\nlocal a = 1 --!optimize 2\n",
+            ParseOptions::default(),
+        );
+
+        let hot_comments = result.get_hot_comments();
+        assert_eq!(hot_comments.len(), 2);
+        assert!(hot_comments[0].is_header());
+        assert_eq!(hot_comments[0].get_content(), "strict");
+        assert!(!hot_comments[1].is_header());
+        assert_eq!(hot_comments[1].get_content(), "optimize 2");
+        assert!(result.get_comments().is_empty());
+    }
+
+    #[test]
+    fn parse_hot_comment_directives() {
+        let mode = |src: &str| Parser::parse(src, ParseOptions::default()).get_mode();
+
+        assert_eq!(mode("--!strict\nlocal a = 1"), Some(Mode::Strict));
+        assert_eq!(mode("-- header\n--!nocheck\n"), Some(Mode::NoCheck));
+        assert_eq!(
+            mode("--!native\n--!nonstrict\n--!strict"),
+            Some(Mode::Nonstrict)
+        );
+        assert_eq!(mode("local a = 1 --!strict"), None);
+        assert_eq!(mode("--!strict extra"), None);
+
+        // long comments are never directives
+        let result = Parser::parse("--[[!strict]]\n--[==[!nocheck]==]", ParseOptions::default());
+        assert_eq!(result.get_mode(), None);
+        assert!(result.get_hot_comments().is_empty());
+
+        let result = Parser::parse("print(1)", ParseOptions::default());
+        assert_eq!(result.get_mode(), None);
+        assert_eq!(result.get_effective_mode(), Mode::Nonstrict);
+
+        let result = Parser::parse(
+            "--! informational\n--!strict\n--!optimize 2\n--!native\n--!nolint UnknownGlobal\nlocal a = 1",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+        assert_eq!(result.get_effective_mode(), Mode::Strict);
+
+        let result = Parser::parse(
+            "--!stirct\n--!optimize 3\n--!optimize\n--!native x\n--!strict x\n--!foobarbaz\nlocal a = 1\n--!strict",
+            ParseOptions::default(),
+        );
+        let warnings: Vec<(u32, &str)> = result
+            .get_errors()
+            .iter()
+            .map(|error| {
+                assert_eq!(error.get_severity(), Severity::Warning);
+                assert_eq!(error.get_code(), ParseErrorCode::CommentDirective);
+
+                (
+                    error.get_location().get_begin().get_line(),
+                    error.get_message(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (0, "Unknown comment directive 'stirct'; did you mean 'strict'?"),
+                (1, "optimize directive uses unknown optimization level '3', 0..2 expected"),
+                (2, "optimize directive requires an optimization level"),
+                (3, "native directive has extra symbols at the end of the line"),
+                (
+                    4,
+                    "Comment directive with the type checking mode has extra symbols at the end of the line"
+                ),
+                (5, "Unknown comment directive 'foobarbaz'"),
+                (
+                    7,
+                    "Comment directive is ignored because it is placed after the first non-comment token"
+                ),
+            ]
+        );
+        assert_eq!(result.get_errors()[0].get_suggestion(), Some("--!strict"));
+        // the location covers the whole comment so the suggestion can replace it
+        assert_eq!(
+            result.get_errors()[0].get_location(),
+            LexLocation::new(LexPosition::new(0, 0), LexPosition::new(0, 9))
+        );
+    }
+
+    #[test]
+    fn parse_function_expr() {
+        let result = Parser::parse(
+            "local f = function<T, U...>(a: T, b, ...: number): (T, U...) return a end",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+
+        let arena = result.get_arena();
+        let function = match result.get_root().get_payload() {
+            Stat::Block(body) => match arena[body[0]].get_payload() {
+                Stat::Local(local) => match arena[local.get_values()[0]].get_payload() {
+                    Expr::Function(function) => function,
+                    _ => panic!("failed"),
+                },
+                _ => panic!("failed"),
+            },
+            _ => panic!("failed"),
+        };
+
+        assert_eq!(function.get_generics().len(), 1);
+        assert_eq!(function.get_generic_packs().len(), 1);
+        assert_eq!(function.get_args().len(), 2);
+        assert!(arena[function.get_args()[0]].get_annotation().is_some());
+        assert!(arena[function.get_args()[1]].get_annotation().is_none());
+        assert!(function.is_vararg());
+        assert_eq!(
+            arena[function.get_vararg_annotation().unwrap()].get_payload_type(),
+            AstNodePayloadType::TypePackVariadic
+        );
+
+        let return_annotation = function.get_return_annotation().unwrap();
+        assert_eq!(return_annotation.get_types().len(), 1);
+        assert_eq!(
+            arena[return_annotation.get_tail_type().unwrap()].get_payload_type(),
+            AstNodePayloadType::TypePackGeneric
+        );
+        assert_eq!(function.get_function_depth(), 1);
+        assert!(function.has_end());
+    }
+
+    #[test]
+    fn parse_function_upvalue() {
+        let result = Parser::parse(
+            "local x = 1 local f = function() return x end",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+
+        let arena = result.get_arena();
+        let return_value = match result.get_root().get_payload() {
+            Stat::Block(body) => match arena[body[1]].get_payload() {
+                Stat::Local(local) => match arena[local.get_values()[0]].get_payload() {
+                    Expr::Function(function) => match arena[function.get_body()].get_payload() {
+                        Stat::Block(body) => match arena[body[0]].get_payload() {
+                            Stat::Return(return_) => arena[return_.get_list()[0]].get_payload(),
+                            _ => panic!("failed"),
+                        },
+                        _ => panic!("failed"),
+                    },
+                    _ => panic!("failed"),
+                },
+                _ => panic!("failed"),
+            },
+            _ => panic!("failed"),
+        };
+
+        if let Expr::Local(local) = return_value {
+            assert!(local.is_upvalue());
+        } else {
+            panic!("failed");
+        }
+    }
+
+    #[test]
+    fn parse_function_vararg() {
+        let (_, errors) = parse_block_types("local f = function(...) return ... end");
+        assert_eq!(errors, Vec::<String>::new());
+
+        let (_, errors) = parse_block_types("local f = function() return ... end");
+        assert_eq!(
+            errors,
+            vec!["Cannot use '...' outside of a vararg function"]
+        );
+    }
+
+    fn parse_alias_type(src: &str) -> (AstTypeId, Vec<String>, ParseResult) {
+        let result = Parser::parse(src, ParseOptions::default());
+
+        let type_ = match result.get_root().get_payload() {
+            Stat::Block(body) => match result.get_arena()[body[0]].get_payload() {
+                Stat::TypeAlias(alias) => alias.get_type(),
+                _ => panic!("failed"),
+            },
+            _ => panic!("failed"),
+        };
+        let errors = result
+            .get_errors()
+            .iter()
+            .map(|error| String::from(error.get_message()))
+            .collect();
+
+        (type_, errors, result)
+    }
+
+    fn type_function(arena: &AstArena, type_: AstTypeId) -> &TypeFunction {
+        if let Type::Function(function) = arena[type_].get_payload() {
+            function
+        } else {
+            panic!("failed");
+        }
+    }
+
+    #[test]
+    fn parse_function_type() {
+        let function_type = |src: &str| {
+            let (type_, errors, result) = parse_alias_type(src);
+            assert_eq!(errors, Vec::<String>::new());
+
+            (type_, result)
+        };
+
+        let (type_, result) = function_type("type F = (number, string) -> boolean");
+        let function = type_function(result.get_arena(), type_);
+        assert_eq!(function.get_arg_types().get_types().len(), 2);
+        assert_eq!(function.get_return_types().get_types().len(), 1);
+
+        let (type_, result) = function_type("type F = <T>(T) -> T");
+        let function = type_function(result.get_arena(), type_);
+        assert_eq!(function.get_generics().len(), 1);
+        assert_eq!(function.get_arg_types().get_types().len(), 1);
+
+        let (type_, result) = function_type("type F = () -> ()");
+        let function = type_function(result.get_arena(), type_);
+        assert!(function.get_arg_types().get_types().is_empty());
+        assert!(function.get_return_types().get_types().is_empty());
+
+        let (type_, result) = function_type("type F = (...number) -> ...string");
+        let arena = result.get_arena();
+        let function = type_function(arena, type_);
+        assert_eq!(
+            arena[function.get_arg_types().get_tail_type().unwrap()].get_payload_type(),
+            AstNodePayloadType::TypePackVariadic
+        );
+        assert_eq!(
+            arena[function.get_return_types().get_tail_type().unwrap()].get_payload_type(),
+            AstNodePayloadType::TypePackVariadic
+        );
+
+        let (type_, _, result) =
+            parse_alias_type("type F = (x: number, string) -> (number) -> string");
+        let (arena, names) = (result.get_arena(), result.get_names());
+        if let Type::Function(function) = arena[type_].get_payload() {
+            let arg_names = function.get_arg_names();
+            assert_eq!(arg_names.len(), 2);
+            assert_eq!(
+                names.get_value(arg_names[0].as_ref().unwrap().get_name()),
+                "x"
+            );
+            assert!(arg_names[1].is_none());
+            assert_eq!(
+                arena[function.get_return_types().get_types()[0]].get_payload_type(),
+                AstNodePayloadType::TypeFunction
+            );
+        } else {
+            panic!("failed");
+        }
+    }
+
+    #[test]
+    fn parse_parenthesized_type() {
+        let (type_, errors, result) = parse_alias_type("type F = (number)");
+        assert!(errors.is_empty());
+        assert_eq!(
+            result.get_arena()[type_].get_payload_type(),
+            AstNodePayloadType::TypeReference
+        );
+
+        let (type_, errors, result) = parse_alias_type("type F = (number) | string");
+        assert!(errors.is_empty());
+        assert_eq!(
+            result.get_arena()[type_].get_payload_type(),
+            AstNodePayloadType::TypeUnion
+        );
+
+        let (type_, errors, result) = parse_alias_type("type F = ((number) -> string)?");
+        assert!(errors.is_empty());
+        assert_eq!(
+            result.get_arena()[type_].get_payload_type(),
+            AstNodePayloadType::TypeUnion
+        );
+
+        let (type_, errors, result) = parse_alias_type("type F = Foo<(number, string), ()>");
+        assert!(errors.is_empty());
+        let arena = result.get_arena();
+        if let Type::Reference(reference) = arena[type_].get_payload() {
+            let parameters = reference.get_parameters().unwrap();
+            assert_eq!(parameters.len(), 2);
+            match parameters[0] {
+                AstTypeOrPack::TypePack(pack) => assert_eq!(
+                    arena[pack].get_payload_type(),
+                    AstNodePayloadType::TypePackExplicit
+                ),
+                AstTypeOrPack::Type(_) => panic!("failed"),
+            }
+        } else {
+            panic!("failed");
+        }
+
+        let (_, errors, _) = parse_alias_type("type F = ()");
+        assert_eq!(
+            errors,
+            vec!["Expected '->' after '()' when parsing function type; did you mean 'nil'?"]
+        );
+    }
+
+    #[test]
+    fn parse_expr_precedence() {
+        let test_fn = |src: &str, expect: &str| {
+            let mut parser = Parser::new(src);
+
+            let result = parser.parse_expr(0);
+            assert_eq!(
+                expr_shape(&parser.arena, result, parser.lexer.get_names()),
+                expect
+            );
+        };
+
+        test_fn("1 + 2 * 3", "(Add 1 (Mul 2 3))");
+        test_fn("1 * 2 + 3", "(Add (Mul 1 2) 3)");
+        test_fn("a // b * c", "(Mul (FloorDiv a b) c)");
+        test_fn("1 + a // 2", "(Add 1 (FloorDiv a 2))");
+        test_fn("a + b // c", "(Add a (FloorDiv b c))");
+        test_fn("a // b + c", "(Add (FloorDiv a b) c)");
+        test_fn("a * b // c", "(FloorDiv (Mul a b) c)");
+        test_fn("a / b // c", "(FloorDiv (Div a b) c)");
+        test_fn("a // b / c % d", "(Mod (Div (FloorDiv a b) c) d)");
+        test_fn("a // b // c", "(FloorDiv (FloorDiv a b) c)");
+        test_fn("-a // b", "(FloorDiv (Minus a) b)");
+        test_fn("a // b ^ c", "(FloorDiv a (Pow b c))");
+        test_fn("1 - 2 - 3", "(Sub (Sub 1 2) 3)");
+        test_fn("2 ^ 3 ^ 2", "(Pow 2 (Pow 3 2))");
+        test_fn("a .. b .. c", "(Concat a (Concat b c))");
+        test_fn("a .. b == c", "(Equal (Concat a b) c)");
+        test_fn("1 + 2 .. 3", "(Concat (Add 1 2) 3)");
+        test_fn("a < b and b < c or d", "(Or (And (Less a b) (Less b c)) d)");
+        test_fn("not a == b", "(Equal (Not a) b)");
+        test_fn("-x ^ 2", "(Minus (Pow x 2))");
+        test_fn("-x * 2", "(Mul (Minus x) 2)");
+        test_fn("#t + 1", "(Add (Len t) 1)");
+        test_fn("(1 + 2) * 3", "(Mul [(Add 1 2)] 3)");
+        test_fn("a :: number + 1", "(Add (:: a) 1)");
+        test_fn("-a :: number", "(Minus (:: a))");
+    }
+
+    fn local_ref(arena: &AstArena, expr: AstExprId) -> Option<(u32, bool)> {
+        match arena[expr].get_payload() {
+            Expr::Local(local) => Some((local.get_local().get_index(), local.is_upvalue())),
+            Expr::Global(_) => None,
+            _ => panic!("failed"),
+        }
+    }
+
+    #[test]
+    fn parse_local_shadowing() {
+        let result = Parser::parse("local a = 1 local a = a return a", ParseOptions::default());
+        let arena = result.get_arena();
+        let body = block_body(arena, result.get_root_id());
+
+        let (first, second) = match (arena[body[0]].get_payload(), arena[body[1]].get_payload()) {
+            (Stat::Local(first), Stat::Local(second)) => (first, second),
+            _ => panic!("failed"),
+        };
+
+        let first_id = first.get_vars()[0];
+        let second_id = second.get_vars()[0];
+        assert_ne!(first_id, second_id);
+        assert_eq!(arena[first_id].get_shadow(), None);
+        assert_eq!(arena[second_id].get_shadow(), Some(first_id));
+
+        assert_eq!(
+            local_ref(arena, second.get_values()[0]),
+            Some((first_id.get_index(), false))
+        );
+        assert_eq!(
+            local_ref(arena, return_list(arena, body[2])[0]),
+            Some((second_id.get_index(), false))
+        );
+    }
+
+    #[test]
+    fn parse_local_begin() {
+        // begin isn't a luau keyword
+        let result = Parser::parse("local begin = 1 return begin", ParseOptions::default());
+        assert!(result.get_errors().is_empty());
+
+        let (arena, names) = (result.get_arena(), result.get_names());
+        let body = block_body(arena, result.get_root_id());
+        let local = match arena[body[0]].get_payload() {
+            Stat::Local(local) => local.get_vars()[0],
+            _ => panic!("failed"),
+        };
+        assert_eq!(names.get_value(arena[local].get_name()), "begin");
+        assert_eq!(
+            local_ref(arena, return_list(arena, body[1])[0]),
+            Some((local.get_index(), false))
+        );
+    }
+
+    #[test]
+    fn parse_local_scopes() {
+        let last_return = |src: &str| {
+            let result = Parser::parse(src, ParseOptions::default());
+            assert!(result.get_errors().is_empty());
+
+            let arena = result.get_arena();
+            let body = block_body(arena, result.get_root_id());
+            local_ref(arena, return_list(arena, *body.last().unwrap())[0])
+        };
+
+        assert_eq!(last_return("do local x = 1 end return x"), None);
+        assert_eq!(last_return("for i = 1, 2 do end return i"), None);
+        assert_eq!(last_return("for k, v in t do end return k"), None);
+        assert_eq!(last_return("local function f(a) end return a"), None);
+        assert_eq!(
+            last_return("local function f(a) end return f"),
+            Some((0, false))
+        );
+        assert_eq!(
+            last_return("local x = 1 do local x = 2 end return x"),
+            Some((0, false))
+        );
+
+        let result = Parser::parse("repeat local x = 1 until x", ParseOptions::default());
+        let arena = result.get_arena();
+        let body = block_body(arena, result.get_root_id());
+        if let Stat::Repeat(repeat) = arena[body[0]].get_payload() {
+            assert_eq!(local_ref(arena, repeat.get_condition()), Some((0, false)));
+        } else {
+            panic!("failed");
+        }
+    }
+
+    #[test]
+    fn parse_local_upvalue() {
+        let result = Parser::parse(
+            "local x = 1 local function f(a) local y = 2 return x, y, a, f end",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+
+        let arena = result.get_arena();
+        let body = block_body(arena, result.get_root_id());
+        let function = if let Stat::LocalFunction(function) = arena[body[1]].get_payload() {
+            function.get_function()
+        } else {
+            panic!("failed");
+        };
+
+        let function_body = block_body(arena, function.get_body());
+        let list = return_list(arena, function_body[1]);
+        assert_eq!(local_ref(arena, list[0]), Some((0, true)));
+        assert_eq!(local_ref(arena, list[1]), Some((3, false)));
+        assert_eq!(local_ref(arena, list[2]), Some((2, false)));
+        assert_eq!(local_ref(arena, list[3]), Some((1, true)));
+    }
+    #[test]
+    fn parse_arena() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ParseResult>();
+
+        let result = Parser::parse("local a = 1 return a, a", ParseOptions::default());
+        let arena = result.get_arena();
+        let body = block_body(arena, result.get_root_id());
+        assert_eq!(arena.get_locals_len(), 1);
+
+        // both references point at the local declared by the first statement
+        let declared = match arena[body[0]].get_payload() {
+            Stat::Local(local) => local.get_vars()[0],
+            _ => panic!("failed"),
+        };
+        let list = return_list(arena, body[1]);
+        for &expr in list.iter() {
+            match arena[expr].get_payload() {
+                Expr::Local(local) => assert_eq!(local.get_local(), declared),
+                _ => panic!("failed"),
+            }
+        }
+        assert_ne!(list[0], list[1]);
+        assert_eq!(
+            result.get_names().get_value(arena[declared].get_name()),
+            "a"
+        );
+    }
+
+    #[test]
+    fn parse_node_kinds() {
+        let result = Parser::parse(
+            "local a = 'x' type F = Foo<number, ...string, U...>",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+
+        let arena = result.get_arena();
+        let body = block_body(arena, result.get_root_id());
+        match arena[body[0]].get_payload() {
+            Stat::Local(local) => assert_eq!(
+                arena[local.get_values()[0]].get_payload_type(),
+                AstNodePayloadType::ExprConstantString
+            ),
+            _ => panic!("failed"),
+        }
+
+        let parameters = match arena[body[1]].get_payload() {
+            Stat::TypeAlias(alias) => match arena[alias.get_type()].get_payload() {
+                Type::Reference(reference) => reference.get_parameters().unwrap().to_vec(),
+                _ => panic!("failed"),
+            },
+            _ => panic!("failed"),
+        };
+        let kinds: Vec<AstNodePayloadType> = parameters
+            .iter()
+            .map(|parameter| match *parameter {
+                AstTypeOrPack::Type(type_) => arena[type_].get_payload_type(),
+                AstTypeOrPack::TypePack(pack) => arena[pack].get_payload_type(),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AstNodePayloadType::TypeReference,
+                AstNodePayloadType::TypePackVariadic,
+                AstNodePayloadType::TypePackGeneric,
+            ]
+        );
+
+        // every kind of node has its own storage
+        assert_eq!(arena.ids::<AstTypePack>().count(), 2);
+        assert_eq!(arena.ids::<AstStat>().count(), 3);
+    }
+
+    #[test]
+    fn parse_recursion_limit() {
+        let find = |src: String, context: &str| {
+            // a level of nesting takes several times more stack in debug builds than in
+            // release builds, the default test thread is too small for the limit there
+            let (sender, receiver) = std::sync::mpsc::channel();
+            std::thread::Builder::new()
+                .stack_size(64 * 1024 * 1024)
+                .spawn(move || {
+                    let result = Parser::parse(&src, ParseOptions::default());
+                    let messages: Vec<String> = result
+                        .get_errors()
+                        .iter()
+                        .filter(|error| error.get_code() == ParseErrorCode::RecursionLimit)
+                        .map(|error| String::from(error.get_message()))
+                        .collect();
+                    sender.send(messages).unwrap();
+                })
+                .unwrap()
+                .join()
+                .unwrap();
+
+            let messages = receiver.recv().unwrap();
+            assert!(!messages.is_empty());
+            assert_eq!(
+                messages[0],
+                format!(
+                    "Exceeded allowed recursion depth; simplify your {} to make the code compile",
+                    context
+                )
+            );
+        };
+
+        find(
+            format!("local a = {}1{}", "(".repeat(10000), ")".repeat(10000)),
+            "expression",
+        );
+        find(format!("local a = {}a", "not ".repeat(10000)), "expression");
+        find(format!("local a = a{}", " + a".repeat(10000)), "expression");
+        find(format!("a{} = 1", ".a".repeat(10000)), "expression");
+        find(
+            format!("{}{}", "do ".repeat(10000), "end ".repeat(10000)),
+            "block",
+        );
+        find(
+            format!(
+                "type T = {}number{}",
+                "{ a: ".repeat(10000),
+                " }".repeat(10000)
+            ),
+            "type annotation",
+        );
+
+        // nesting below the limit is fine
+        let src = format!("local a = {}1{}", "(".repeat(100), ")".repeat(100));
+        assert!(Parser::parse(&src, ParseOptions::default())
+            .get_errors()
+            .is_empty());
+    }
+
+    #[test]
+    fn parse_recovery() {
+        let (types, errors) = parse_block_types(") local x = 1");
+        assert_eq!(
+            types,
+            vec![AstNodePayloadType::StatError, AstNodePayloadType::StatLocal]
+        );
+        assert_eq!(
+            errors,
+            vec!["Expected identifier when parsing expression, got ')'"]
+        );
+
+        let (types, errors) = parse_block_types("local a = 1 +\nlocal b = (((c +");
+        assert_eq!(
+            types,
+            vec![AstNodePayloadType::StatLocal, AstNodePayloadType::StatLocal]
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Expected identifier when parsing expression, got 'local'",
+                "Expected identifier when parsing expression, got <eof>",
+            ]
+        );
+
+        let (types, errors) = parse_block_types("foo(1, 2\nbar()");
+        assert_eq!(
+            types,
+            vec![AstNodePayloadType::StatExpr, AstNodePayloadType::StatExpr]
+        );
+        assert_eq!(
+            errors,
+            vec!["Expected ')' (to close '(' at line 1), got 'bar'"]
+        );
+
+        // the missing token is searched for until the end of the line
+        let (types, errors) = parse_block_types("local function f(a, b c) return a end\nf()");
+        assert_eq!(
+            types,
+            vec![
+                AstNodePayloadType::StatLocalFunction,
+                AstNodePayloadType::StatExpr
+            ]
+        );
+        assert_eq!(
+            errors,
+            vec!["Expected ')' (to close '(' at column 17), got 'c'"]
+        );
+    }
+
+    #[test]
+    fn parse_recovery_partial() {
+        let result = Parser::parse("local a = (1 + 2\nx = 1", ParseOptions::default());
+        let errors = result.get_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].get_location().get_begin().get_line(), 1);
+
+        let arena = result.get_arena();
+        let body = block_body(arena, result.get_root_id());
+        assert_eq!(body.len(), 2);
+        assert_eq!(
+            arena[body[1]].get_payload_type(),
+            AstNodePayloadType::StatAssign
+        );
+
+        if let Stat::Local(local) = arena[body[0]].get_payload() {
+            if let Expr::Error(error) = arena[local.get_values()[0]].get_payload() {
+                assert_eq!(error.get_message_index(), 0);
+                assert_eq!(
+                    expr_shape(arena, error.get_expressions()[0], result.get_names()),
+                    "(Add 1 2)"
+                );
+            } else {
+                panic!("failed");
+            }
+        } else {
+            panic!("failed");
+        }
+
+        let result = Parser::parse("a.b\nreturn", ParseOptions::default());
+        let arena = result.get_arena();
+        let body = block_body(arena, result.get_root_id());
+        if let Stat::Error(error) = arena[body[0]].get_payload() {
+            assert_eq!(
+                arena[error.get_expressions()[0]].get_payload_type(),
+                AstNodePayloadType::ExprIndexName
+            );
+        } else {
+            panic!("failed");
+        }
+        assert_eq!(
+            arena[body[1]].get_payload_type(),
+            AstNodePayloadType::StatReturn
+        );
+    }
+    #[test]
+    fn parse_error_diagnostics() {
+        let result = Parser::parse("while true do\nlocal a = 1", ParseOptions::default());
+        let error = &result.get_errors()[0];
+        assert_eq!(error.get_code(), ParseErrorCode::UnmatchedDelimiter);
+        assert_eq!(error.get_severity(), Severity::Error);
+        assert_eq!(error.get_labels().len(), 1);
+        assert_eq!(error.get_labels()[0].get_message(), "'do' opened here");
+        assert_eq!(
+            error.get_labels()[0]
+                .get_location()
+                .get_begin()
+                .get_column(),
+            11
+        );
+        assert_eq!(
+            error.to_string(),
+            "2:12: error[P0002]: Expected 'end' (to close 'do' at line 1), got <eof>"
+        );
+
+        let result = Parser::parse("type F = ()", ParseOptions::default());
+        let error = &result.get_errors()[0];
+        assert_eq!(error.get_code(), ParseErrorCode::UnitType);
+        assert_eq!(error.get_suggestion(), Some("nil"));
+
+        let result = Parser::parse(
+            "type T = { [string]: number, [number]: string }",
+            ParseOptions::default(),
+        );
+        let error = &result.get_errors()[0];
+        assert_eq!(error.get_code(), ParseErrorCode::DuplicateTableIndexer);
+        assert_eq!(
+            error.get_labels()[0].get_message(),
+            "first indexer declared here"
+        );
+        assert_eq!(error.get_code().as_str(), "P0013");
+
+        let boxed: Box<dyn std::error::Error> = Box::new(error.clone());
+        assert!(boxed
+            .to_string()
+            .contains("Cannot have more than one table indexer"));
+    }
+    fn parse_definitions(src: &str) -> (Vec<AstStatId>, Vec<String>, ParseResult) {
+        let mut options = ParseOptions::default();
+        options.set_allow_declaration_syntax(true);
+
+        let result = Parser::parse(src, options);
+        let errors = result
+            .get_errors()
+            .iter()
+            .map(|error| String::from(error.get_message()))
+            .collect();
+
+        (
+            block_body(result.get_arena(), result.get_root_id()),
+            errors,
+            result,
+        )
+    }
+
+    #[test]
+    fn parse_declarations() {
+        let (body, errors, result) = parse_definitions(
+            "declare game: Instance
+            declare function print<T...>(message: string, ...: T...): ()
+            declare class Part extends Instance
+                Name: string
+                [\"Size\"]: Vector3
+                [string]: any
+                function Clone(self): Part
+                function FindFirstChild<T>(self, name: string, recursive: boolean?): T?
+            end",
+        );
+        assert_eq!(errors, Vec::<String>::new());
+
+        let (arena, names) = (result.get_arena(), result.get_names());
+        if let Stat::DeclareGlobal(global) = arena[body[0]].get_payload() {
+            assert_eq!(names.get_value(global.get_name()), "game");
             assert_eq!(
-                AstNodePayload::ExprConstantNil.get_type(),
-                result.get_payload().get_type()
+                arena[global.get_type()].get_payload_type(),
+                AstNodePayloadType::TypeReference
+            );
+        } else {
+            panic!("failed");
+        }
+
+        if let Stat::DeclareFunction(function) = arena[body[1]].get_payload() {
+            assert_eq!(names.get_value(function.get_name()), "print");
+            assert_eq!(function.get_generic_packs().len(), 1);
+            assert_eq!(function.get_params().get_types().len(), 1);
+            assert!(function.get_params().get_tail_type().is_some());
+            assert_eq!(
+                names.get_value(function.get_param_names()[0].as_ref().unwrap().get_name()),
+                "message"
+            );
+            assert!(function.get_return_types().get_types().is_empty());
+        } else {
+            panic!("failed");
+        }
+
+        if let Stat::DeclareClass(class) = arena[body[2]].get_payload() {
+            assert_eq!(names.get_value(class.get_name()), "Part");
+            assert_eq!(names.get_value(class.get_super_name().unwrap()), "Instance");
+            assert!(class.get_indexer().is_some());
+
+            let props = class.get_props();
+            assert_eq!(props.len(), 4);
+            assert_eq!(names.get_value(props[1].get_name()), "Size");
+            assert!(!props[1].is_method());
+            assert!(props[3].is_method());
+            if let Type::Function(method) = arena[props[3].get_type()].get_payload() {
+                assert_eq!(method.get_generics().len(), 1);
+                assert_eq!(method.get_arg_types().get_types().len(), 2);
+            } else {
+                panic!("failed");
+            }
+        } else {
+            panic!("failed");
+        }
+    }
+
+    #[test]
+    fn parse_declaration_errors() {
+        let (_, errors, _) = parse_definitions("declare function f(a, b: number)");
+        assert_eq!(errors, vec!["All declaration parameters must be annotated"]);
+
+        let (_, errors, _) = parse_definitions("declare class A function f(x: number) end");
+        assert_eq!(
+            errors,
+            vec!["'self' must be present as the unannotated first parameter"]
+        );
+
+        let (_, errors, _) = parse_definitions("declare 1");
+        assert_eq!(
+            errors[0],
+            "declare must be followed by an identifier, 'function', or 'class'"
+        );
+
+        // declarations are only allowed in definition files
+        let (types, errors) = parse_block_types("declare game: Instance");
+        assert_eq!(types[0], AstNodePayloadType::StatError);
+        assert_eq!(
+            errors[0],
+            "Incomplete statement: expected assignment or a function call"
+        );
+    }
+    fn parse_alias(src: &str) -> (StatTypeAlias, Vec<String>, ParseResult) {
+        let result = Parser::parse(src, ParseOptions::default());
+
+        let arena = result.get_arena();
+        let alias = match arena[block_body(arena, result.get_root_id())[0]].get_payload() {
+            Stat::TypeAlias(alias) => alias.as_ref().clone(),
+            _ => panic!("failed"),
+        };
+        let errors = result
+            .get_errors()
+            .iter()
+            .map(|error| String::from(error.get_message()))
+            .collect();
+
+        (alias, errors, result)
+    }
+
+    #[test]
+    fn parse_type_alias_generics() {
+        let (alias, errors, result) =
+            parse_alias("export type Map<K, V = string, U... = ...number> = { [K]: V }");
+        assert_eq!(errors, Vec::<String>::new());
+        assert!(alias.is_exported());
+
+        let (arena, names) = (result.get_arena(), result.get_names());
+        let generics = alias.get_generics();
+        assert_eq!(generics.len(), 2);
+        assert_eq!(names.get_value(generics[0].get_name()), "K");
+        assert!(generics[0].get_default_value().is_none());
+        assert_eq!(
+            arena[generics[1].get_default_value().unwrap()].get_payload_type(),
+            AstNodePayloadType::TypeReference
+        );
+
+        let packs = alias.get_generic_packs();
+        assert_eq!(packs.len(), 1);
+        assert_eq!(names.get_value(packs[0].get_name()), "U");
+        assert_eq!(
+            arena[packs[0].get_default_value().unwrap()].get_payload_type(),
+            AstNodePayloadType::TypePackVariadic
+        );
+
+        let (alias, errors, result) = parse_alias("type F<T... = (string, number)> = () -> T...");
+        assert_eq!(errors, Vec::<String>::new());
+        assert_eq!(
+            result.get_arena()[alias.get_generic_packs()[0].get_default_value().unwrap()]
+                .get_payload_type(),
+            AstNodePayloadType::TypePackExplicit
+        );
+
+        // `type` is still a regular identifier outside of type alias statements
+        let (types, errors) = parse_block_types("local type = type(x) type = 1");
+        assert_eq!(errors, Vec::<String>::new());
+        assert_eq!(
+            types,
+            vec![
+                AstNodePayloadType::StatLocal,
+                AstNodePayloadType::StatAssign
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_type_alias_generic_errors() {
+        let (_, errors, _) = parse_alias("type A<T = number, U> = T");
+        assert_eq!(errors, vec!["Expected default type after type name"]);
+
+        let (_, errors, _) = parse_alias("type A<T... = ...number, U...> = T");
+        assert_eq!(
+            errors,
+            vec!["Expected default type pack after type pack name"]
+        );
+
+        let (alias, errors, _) = parse_alias("type A<T..., U> = T");
+        assert_eq!(errors, vec!["Generic types come before generic type packs"]);
+        assert_eq!(alias.get_generic_packs().len(), 2);
+
+        let (_, errors, _) = parse_alias("type A<T... = number> = T");
+        assert_eq!(errors, vec!["Expected type pack after '=', got type"]);
+    }
+
+    #[test]
+    fn parse_compound_assignment() {
+        let result = Parser::parse(
+            "local x = 1 x += 1 x -= 2 x *= 3 x /= 4 x //= 4 x %= 5 x ^= 6 t.a ..= \"s\"",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+
+        let arena = result.get_arena();
+        let operators: Vec<BinaryOperator> = block_body(arena, result.get_root_id())
+            .iter()
+            .skip(1)
+            .map(|&stat| match arena[stat].get_payload() {
+                Stat::CompoundAssign(assign) => assign.get_operator(),
+                _ => panic!("failed"),
+            })
+            .collect();
+        assert_eq!(
+            operators,
+            vec![
+                BinaryOperator::Add,
+                BinaryOperator::Sub,
+                BinaryOperator::Mul,
+                BinaryOperator::Div,
+                BinaryOperator::FloorDiv,
+                BinaryOperator::Mod,
+                BinaryOperator::Pow,
+                BinaryOperator::Concat,
+            ]
+        );
+
+        // the value of '//=' is a whole expression, it doesn't bind to the operator
+        let result = Parser::parse("x //= a + b // c", ParseOptions::default());
+        assert!(result.get_errors().is_empty());
+
+        let arena = result.get_arena();
+        match arena[block_body(arena, result.get_root_id())[0]].get_payload() {
+            Stat::CompoundAssign(assign) => {
+                assert_eq!(assign.get_operator(), BinaryOperator::FloorDiv);
+                assert_eq!(
+                    expr_shape(arena, assign.get_value(), result.get_names()),
+                    "(Add a (FloorDiv b c))"
+                );
+            }
+            _ => panic!("failed"),
+        }
+
+        let (_, errors) = parse_block_types("(a) += 1");
+        assert_eq!(
+            errors,
+            vec!["Assigned expression must be a variable or a field"]
+        );
+    }
+
+    #[test]
+    fn parse_continue() {
+        let (types, errors) = parse_block_types("while true do continue end");
+        assert_eq!(errors, Vec::<String>::new());
+        assert_eq!(types, vec![AstNodePayloadType::StatWhile]);
+
+        let result = Parser::parse(
+            "for i = 1, 10 do if i then continue; end end",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+
+        let (types, errors) = parse_block_types("continue");
+        assert_eq!(types, vec![AstNodePayloadType::StatError]);
+        assert_eq!(errors, vec!["continue statement must be inside a loop"]);
+
+        let (_, errors) = parse_block_types("while true do local f = function() continue end end");
+        assert_eq!(errors, vec!["continue statement must be inside a loop"]);
+
+        // continue is an ordinary identifier when it does not end the statement
+        let (types, errors) =
+            parse_block_types("local continue = 1 continue = 2 continue() continue += 1");
+        assert_eq!(errors, Vec::<String>::new());
+        assert_eq!(
+            types,
+            vec![
+                AstNodePayloadType::StatLocal,
+                AstNodePayloadType::StatAssign,
+                AstNodePayloadType::StatExpr,
+                AstNodePayloadType::StatCompoundAssign,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_string_escapes() {
+        let test_fn = |src: &str, expect_value: &[u8]| {
+            let mut parser = Parser::new(src);
+            let result = parser.parse_string_expr();
+            if let Expr::ConstantString(value) = parser.arena[result].get_payload() {
+                assert_eq!(value, expect_value, "{}", src);
+            } else {
+                panic!("failed: {}", src);
+            }
+        };
+
+        test_fn(r#""\"\'\\""#, b"\"'\\");
+        test_fn(r#"'\a\b\f\n\r\t\v'"#, b"\x07\x08\x0c\n\r\t\x0b");
+        test_fn(r#""\x41\xff\xFe""#, b"A\xff\xfe");
+        test_fn(r#""\0\65\0651\255""#, b"\0AA1\xff");
+        test_fn(
+            r#""\u{41}\u{7ff}\u{20AC}\u{10FFFF}""#,
+            "A\u{7ff}\u{20ac}\u{10ffff}".as_bytes(),
+        );
+        test_fn(r#""\u{D800}""#, b"\xed\xa0\x80");
+        test_fn("\"a\\z  \n\t  b\"", b"ab");
+        test_fn("\"a\\\nb\"", b"a\nb");
+        test_fn("\"a\\\r\nb\"", b"a\nb");
+        test_fn("\"\u{e9}\"", "\u{e9}".as_bytes());
+        test_fn("[[\\x41]]", b"\\x41");
+        test_fn("[[\nx]]", b"x");
+        test_fn("[[\r\nx\r\ny\n]]", b"x\ny\n");
+        test_fn("[[\n\nx]]", b"\nx");
+        test_fn("[[x\ry\n\rz]]", b"x\ry\n\rz");
+    }
+
+    #[test]
+    fn parse_string_escape_errors() {
+        let result = Parser::parse(
+            r#"local a = "ok \xZZ \256 \u41 \u{110000} \u{41" .. "\x4""#,
+            ParseOptions::default(),
+        );
+        let errors: Vec<(String, (u32, u32))> = result
+            .get_errors()
+            .iter()
+            .map(|error| {
+                let location = error.get_location();
+                assert_eq!(error.get_code(), ParseErrorCode::MalformedString);
+                assert_eq!(
+                    location.get_begin().get_line(),
+                    location.get_end().get_line()
+                );
+                (
+                    String::from(error.get_message()),
+                    (
+                        location.get_begin().get_column(),
+                        location.get_end().get_column(),
+                    ),
+                )
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    String::from("Invalid hexadecimal escape sequence, expected two hexadecimal digits after '\\x'"),
+                    (14, 16)
+                ),
+                (
+                    String::from("Decimal escape sequence is out of range, expected at most 255"),
+                    (19, 23)
+                ),
+                (
+                    String::from("Invalid unicode escape sequence, expected '{' after '\\u'"),
+                    (24, 26)
+                ),
+                (
+                    String::from("Unicode escape sequence is out of range, expected at most 10FFFF"),
+                    (29, 39)
+                ),
+                (
+                    String::from("Invalid unicode escape sequence, expected hexadecimal digits followed by '}'"),
+                    (40, 45)
+                ),
+                (
+                    String::from("Invalid hexadecimal escape sequence, expected two hexadecimal digits after '\\x'"),
+                    (51, 54)
+                ),
+            ]
+        );
+
+        // escapes after a line continuation are reported on their own line
+        let result = Parser::parse("local a = 'x\\\n  \\q \\xG'", ParseOptions::default());
+        let location = result.get_errors()[0].get_location();
+        assert_eq!(location.get_begin(), LexPosition::new(1, 5));
+        assert_eq!(location.get_end(), LexPosition::new(1, 7));
+
+        let (_, errors) = parse_block_types("type A = { [\"\\xff\"]: number }");
+        assert_eq!(errors, vec!["Property name must be a valid UTF-8 string"]);
+    }
+
+    fn interp_parts(
+        arena: &AstArena,
+        expr: AstExprId,
+        names: &NameTable,
+    ) -> (Vec<Vec<u8>>, Vec<String>) {
+        if let Expr::InterpString(interp) = arena[expr].get_payload() {
+            (
+                interp.get_strings().to_vec(),
+                interp
+                    .get_expressions()
+                    .iter()
+                    .map(|&expr| expr_shape(arena, expr, names))
+                    .collect(),
             )
         } else {
             panic!("failed");
         }
     }
+
+    #[test]
+    fn parse_interp_string() {
+        let result = Parser::parse(
+            "return `Hello {name}, you have {#items} items`, `plain\\{`, `{`nested {1}`}\\u{41}`",
+            ParseOptions::default(),
+        );
+        assert!(result.get_errors().is_empty());
+
+        let arena = result.get_arena();
+        let list = return_list(arena, block_body(arena, result.get_root_id())[0]);
+        assert_eq!(
+            interp_parts(arena, list[0], result.get_names()),
+            (
+                vec![
+                    b"Hello ".to_vec(),
+                    b", you have ".to_vec(),
+                    b" items".to_vec()
+                ],
+                vec![String::from("name"), String::from("(Len items)")]
+            )
+        );
+        assert_eq!(
+            arena[list[0]].get_location(),
+            LexLocation::new(LexPosition::new(0, 7), LexPosition::new(0, 46))
+        );
+        assert_eq!(
+            interp_parts(arena, list[1], result.get_names()),
+            (vec![b"plain{".to_vec()], vec![])
+        );
+
+        let (strings, expressions) = interp_parts(arena, list[2], result.get_names());
+        assert_eq!(strings, vec![b"".to_vec(), b"A".to_vec()]);
+        assert_eq!(expressions.len(), 1);
+    }
+
+    #[test]
+    fn parse_interp_string_errors() {
+        let (_, errors) = parse_block_types("local a = `x{}y`");
+        assert_eq!(
+            errors,
+            vec!["Malformed interpolated string, expected expression inside '{}'"]
+        );
+
+        let result = Parser::parse("local a = `x{{y}}`", ParseOptions::default());
+        let error = &result.get_errors()[0];
+        assert_eq!(
+            error.get_code(),
+            ParseErrorCode::MalformedInterpolatedString
+        );
+        assert_eq!(
+            error.get_message(),
+            "Double braces are not permitted within interpolated strings, did you mean '\\{'?"
+        );
+        assert_eq!(error.get_suggestion(), Some("\\{"));
+
+        let (_, errors) = parse_block_types("local a = `x{y}z\nprint(a)");
+        assert_eq!(
+            errors[0],
+            "Malformed interpolated string, did you forget to add a '`'?"
+        );
+
+        let (_, errors) = parse_block_types("local a = `x{y z}`");
+        assert_eq!(
+            errors[0],
+            "Malformed interpolated string, expected '}', got 'z'"
+        );
+
+        let (_, errors) = parse_block_types("local a = `x\nprint(a)");
+        assert_eq!(
+            errors[0],
+            "Unfinished interpolated string, did you forget a closing '`'?"
+        );
+
+        let (_, errors) = parse_block_types("local a = `\\xZZ{b}`");
+        assert_eq!(
+            errors,
+            vec![
+                "Invalid hexadecimal escape sequence, expected two hexadecimal digits after '\\x'"
+            ]
+        );
+
+        let (_, errors) = parse_block_types("type A = `x`");
+        assert_eq!(
+            errors,
+            vec!["Interpolated string literals cannot be used as types"]
+        );
+    }
+
+    #[test]
+    fn parse_lexeme_errors() {
+        let (types, errors) = parse_block_types("local a = 1 $ local b = @ 2");
+        assert_eq!(
+            types,
+            vec![AstNodePayloadType::StatLocal, AstNodePayloadType::StatLocal]
+        );
+        assert_eq!(
+            errors,
+            vec!["Unexpected character '$'", "Unexpected character '@'"]
+        );
+
+        let result = Parser::parse("local a = 1 --[==[ x", ParseOptions::default());
+        let error = &result.get_errors()[0];
+        assert_eq!(error.get_code(), ParseErrorCode::MalformedComment);
+        assert_eq!(
+            error.get_message(),
+            "Unfinished long comment, expected ']==]'"
+        );
+        assert_eq!(
+            error.get_location(),
+            LexLocation::new(LexPosition::new(0, 12), LexPosition::new(0, 20))
+        );
+
+        let (_, errors) = parse_block_types("local s = [==[ x ]=]");
+        assert_eq!(
+            errors,
+            vec!["Unfinished long string, expected ']==]' (the ']=]' at line 1 has a different level)"]
+        );
+
+        let result = Parser::parse_bytes(
+            b"local a = 1\n\xff local s = '\xfe'",
+            ParseOptions::default(),
+        );
+        assert_eq!(result.get_errors().len(), 1);
+        assert_eq!(
+            result.get_errors()[0].get_code(),
+            ParseErrorCode::InvalidCharacter
+        );
+        assert_eq!(
+            result.get_errors()[0].get_message(),
+            "Invalid UTF-8 sequence"
+        );
+        assert_eq!(
+            result.get_errors()[0].get_location(),
+            LexLocation::new(LexPosition::new(1, 0), LexPosition::new(1, 1))
+        );
+        assert_eq!(
+            block_body(result.get_arena(), result.get_root_id()).len(),
+            2
+        );
+
+        // locations after a replaced sequence still slice the original bytes
+        let src = b"local s = '\xe2\x82\xff'\nlocal t = s .. 'x'";
+        let result = Parser::parse_bytes(src, ParseOptions::default());
+        let arena = result.get_arena();
+        let body = block_body(arena, result.get_root_id());
+        let values = match arena[body[1]].get_payload() {
+            Stat::Local(local) => local.get_values(),
+            _ => panic!("failed"),
+        };
+        assert_eq!(
+            &src[arena[values[0]].get_location().get_byte_range()],
+            b"s .. 'x'"
+        );
+        assert_eq!(
+            &src[arena[body[0]].get_location().get_byte_range()],
+            b"local s = '\xe2\x82\xff'"
+        );
+    }
 }
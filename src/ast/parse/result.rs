@@ -0,0 +1,77 @@
+use super::super::{AstArena, AstStat, AstStatId, NameTable};
+use super::{Comment, HotComment, Mode, ParseError};
+
+pub struct ParseResult {
+    root: AstStatId,
+    // owns every node of the tree
+    arena: AstArena,
+    errors: Vec<ParseError>,
+    comments: Vec<Comment>,
+    hot_comments: Vec<HotComment>,
+    // the names of the tree are interned here
+    names: NameTable,
+}
+
+impl ParseResult {
+    pub fn new(
+        root: AstStatId,
+        arena: AstArena,
+        errors: Vec<ParseError>,
+        comments: Vec<Comment>,
+        hot_comments: Vec<HotComment>,
+        names: NameTable,
+    ) -> Self {
+        ParseResult {
+            root,
+            arena,
+            errors,
+            comments,
+            hot_comments,
+            names,
+        }
+    }
+
+    pub fn get_root(&self) -> &AstStat {
+        &self.arena[self.root]
+    }
+
+    pub fn get_root_id(&self) -> AstStatId {
+        self.root
+    }
+
+    pub fn get_arena(&self) -> &AstArena {
+        &self.arena
+    }
+
+    pub fn get_arena_mut(&mut self) -> &mut AstArena {
+        &mut self.arena
+    }
+
+    pub fn get_errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    pub fn get_comments(&self) -> &[Comment] {
+        &self.comments
+    }
+
+    pub fn get_hot_comments(&self) -> &[HotComment] {
+        &self.hot_comments
+    }
+
+    pub fn get_names(&self) -> &NameTable {
+        &self.names
+    }
+
+    // the mode requested by the first mode directive at the top of the chunk
+    pub fn get_mode(&self) -> Option<Mode> {
+        self.hot_comments
+            .iter()
+            .find_map(|hot_comment| hot_comment.get_mode())
+    }
+
+    // chunks without a mode directive are checked in nonstrict mode
+    pub fn get_effective_mode(&self) -> Mode {
+        self.get_mode().unwrap_or(Mode::Nonstrict)
+    }
+}
@@ -0,0 +1,388 @@
+use super::{
+    get_children, AstArena, AstExprId, AstNodeId, AstStatId, AstTypeId, Expr, ExprFunction,
+    LexLocation, LexPosition, LocalId, Stat,
+};
+use std::collections::HashMap;
+
+// finds the nodes of a parsed tree at a source position and the ancestors of a node
+pub struct PositionIndex {
+    root: AstStatId,
+    parents: HashMap<AstNodeId, AstNodeId>,
+    // the location of a node grown to include all of its descendants, e.g. a local declaration
+    // and its annotation
+    extents: HashMap<AstNodeId, LexLocation>,
+}
+
+impl PositionIndex {
+    pub fn new(arena: &AstArena, root: AstStatId) -> Self {
+        let mut result = PositionIndex {
+            root,
+            parents: HashMap::new(),
+            extents: HashMap::new(),
+        };
+        result.add_node(arena, AstNodeId::Stat(root));
+
+        result
+    }
+
+    fn add_node(&mut self, arena: &AstArena, node: AstNodeId) -> LexLocation {
+        let mut extent = arena.get_location(node);
+
+        for child in get_children(arena, node) {
+            self.parents.insert(child, node);
+
+            let child_extent = self.add_node(arena, child);
+            extent = LexLocation::new(
+                min_position(extent.get_begin(), child_extent.get_begin()),
+                max_position(extent.get_end(), child_extent.get_end()),
+            );
+        }
+
+        self.extents.insert(node, extent);
+
+        extent
+    }
+
+    pub fn get_root(&self) -> AstStatId {
+        self.root
+    }
+
+    pub fn get_parent(&self, node: AstNodeId) -> Option<AstNodeId> {
+        self.parents.get(&node).copied()
+    }
+
+    // from the parent of the node to the root block
+    pub fn get_ancestors(&self, node: AstNodeId) -> Vec<AstNodeId> {
+        let mut ancestors: Vec<AstNodeId> = Vec::new();
+        let mut current = node;
+
+        while let Some(parent) = self.get_parent(current) {
+            ancestors.push(parent);
+            current = parent;
+        }
+
+        ancestors
+    }
+
+    // the nodes at the position from the root block to the innermost node, empty when the
+    // position is outside of the tree
+    pub fn find_path(&self, arena: &AstArena, position: LexPosition) -> Vec<AstNodeId> {
+        let root = AstNodeId::Stat(self.root);
+        if !self.extents[&root].contains_closed(&position) {
+            return Vec::new();
+        }
+
+        let mut path = vec![root];
+        while let Some(child) = self.find_child(arena, path[path.len() - 1], position) {
+            path.push(child);
+        }
+
+        path
+    }
+
+    // a child spanning the position is preferred over an empty child at it, which is preferred
+    // over a child ending at it, so missing expressions of error nodes can still be found
+    fn find_child(
+        &self,
+        arena: &AstArena,
+        node: AstNodeId,
+        position: LexPosition,
+    ) -> Option<AstNodeId> {
+        let mut result: Option<(u32, AstNodeId)> = None;
+
+        for child in get_children(arena, node) {
+            let extent = self.extents[&child];
+
+            let rank = if extent.contains(&position) {
+                0
+            } else if extent.get_begin() == position && extent.get_end() == position {
+                1
+            } else if extent.get_end() == position {
+                2
+            } else {
+                continue;
+            };
+
+            if result.is_none_or(|(best, _)| rank < best) {
+                result = Some((rank, child));
+            }
+        }
+
+        result.map(|(_, child)| child)
+    }
+
+    pub fn find(&self, arena: &AstArena, position: LexPosition) -> Option<AstNodeId> {
+        self.find_path(arena, position).last().copied()
+    }
+
+    pub fn find_expr(&self, arena: &AstArena, position: LexPosition) -> Option<AstExprId> {
+        self.find_path(arena, position)
+            .iter()
+            .rev()
+            .find_map(|node| match *node {
+                AstNodeId::Expr(id) => Some(id),
+                _ => None,
+            })
+    }
+
+    pub fn find_stat(&self, arena: &AstArena, position: LexPosition) -> Option<AstStatId> {
+        self.find_path(arena, position)
+            .iter()
+            .rev()
+            .find_map(|node| match *node {
+                AstNodeId::Stat(id) => Some(id),
+                _ => None,
+            })
+    }
+
+    pub fn find_type(&self, arena: &AstArena, position: LexPosition) -> Option<AstTypeId> {
+        self.find_path(arena, position)
+            .iter()
+            .rev()
+            .find_map(|node| match *node {
+                AstNodeId::Type(id) => Some(id),
+                _ => None,
+            })
+    }
+
+    // the local declared or referenced at the position
+    pub fn find_local(&self, arena: &AstArena, position: LexPosition) -> Option<LocalId> {
+        self.find_path(arena, position)
+            .iter()
+            .rev()
+            .find_map(|node| match *node {
+                AstNodeId::Local(id) => Some(id),
+                AstNodeId::Expr(id) => match arena[id].get_payload() {
+                    Expr::Local(local) => Some(local.get_local()),
+                    _ => None,
+                },
+                _ => None,
+            })
+    }
+
+    pub fn get_enclosing_stat(&self, node: AstNodeId) -> Option<AstStatId> {
+        self.get_ancestors(node)
+            .iter()
+            .find_map(|ancestor| match *ancestor {
+                AstNodeId::Stat(id) => Some(id),
+                _ => None,
+            })
+    }
+
+    // the function expression or function statement the node is part of
+    pub fn get_enclosing_function<'a>(
+        &self,
+        arena: &'a AstArena,
+        node: AstNodeId,
+    ) -> Option<(AstNodeId, &'a ExprFunction)> {
+        self.get_ancestors(node)
+            .iter()
+            .find_map(|ancestor| match *ancestor {
+                AstNodeId::Expr(id) => match arena[id].get_payload() {
+                    Expr::Function(function) => Some((*ancestor, function.as_ref())),
+                    _ => None,
+                },
+                AstNodeId::Stat(id) => match arena[id].get_payload() {
+                    Stat::Function(function) => Some((*ancestor, function.get_function())),
+                    Stat::LocalFunction(function) => Some((*ancestor, function.get_function())),
+                    _ => None,
+                },
+                _ => None,
+            })
+    }
+}
+
+fn min_position(a: LexPosition, b: LexPosition) -> LexPosition {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max_position(a: LexPosition, b: LexPosition) -> LexPosition {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::expr_shape;
+    use super::super::{AstNodePayloadType, ParseOptions, ParseResult, Parser};
+    use super::*;
+
+    fn index_of(result: &ParseResult) -> PositionIndex {
+        PositionIndex::new(result.get_arena(), result.get_root_id())
+    }
+
+    fn payload_type(arena: &AstArena, node: AstNodeId) -> AstNodePayloadType {
+        match node {
+            AstNodeId::Expr(id) => arena[id].get_payload_type(),
+            AstNodeId::Stat(id) => arena[id].get_payload_type(),
+            AstNodeId::Type(id) => arena[id].get_payload_type(),
+            AstNodeId::TypePack(id) => arena[id].get_payload_type(),
+            AstNodeId::Local(_) => panic!("failed"),
+        }
+    }
+
+    #[test]
+    fn test_find_innermost() {
+        let src =
+            "local value = foo.bar(1, baz)\nlocal x: number = 1\nlocal function f(a) return a end";
+        let result = Parser::parse(src, ParseOptions::default());
+        let arena = result.get_arena();
+        let names = result.get_names();
+        let index = index_of(&result);
+
+        let baz = index.find_expr(arena, LexPosition::new(0, 26)).unwrap();
+        assert_eq!(expr_shape(arena, baz, names), "baz");
+        assert_eq!(
+            index.find(arena, LexPosition::new(0, 26)),
+            Some(AstNodeId::Expr(baz))
+        );
+
+        // the path goes from the root block to the innermost node
+        let path = index.find_path(arena, LexPosition::new(0, 26));
+        assert_eq!(path.len(), 4);
+        assert_eq!(path[0], AstNodeId::Stat(result.get_root_id()));
+        assert_eq!(path[3], AstNodeId::Expr(baz));
+
+        // the annotation of a local is found through the local, and the local at its name
+        let type_ = index.find_type(arena, LexPosition::new(1, 10)).unwrap();
+        assert_eq!(
+            arena[type_].get_payload_type(),
+            AstNodePayloadType::TypeReference
+        );
+        let x = index.find_local(arena, LexPosition::new(1, 6)).unwrap();
+        assert_eq!(names.get_value(arena[x].get_name()), "x");
+        assert_eq!(
+            index.find(arena, LexPosition::new(1, 6)),
+            Some(AstNodeId::Local(x))
+        );
+
+        // references resolve to the local they refer to
+        let a = index.find_local(arena, LexPosition::new(2, 27)).unwrap();
+        assert_eq!(index.find_local(arena, LexPosition::new(2, 17)), Some(a));
+
+        // a position between statements is only in the root block
+        let stat = index.find_stat(arena, LexPosition::new(1, 5)).unwrap();
+        assert_eq!(
+            arena[stat].get_payload_type(),
+            AstNodePayloadType::StatLocal
+        );
+        assert!(index.find(arena, LexPosition::new(10, 0)).is_none());
+    }
+
+    #[test]
+    fn test_ancestors() {
+        let result = Parser::parse("local value = foo.bar(1, baz)", ParseOptions::default());
+        let arena = result.get_arena();
+        let index = index_of(&result);
+
+        let baz = index.find(arena, LexPosition::new(0, 26)).unwrap();
+        let kinds: Vec<AstNodePayloadType> = index
+            .get_ancestors(baz)
+            .iter()
+            .map(|node| payload_type(arena, *node))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AstNodePayloadType::ExprCall,
+                AstNodePayloadType::StatLocal,
+                AstNodePayloadType::StatBlock
+            ]
+        );
+        assert_eq!(index.get_parent(baz), Some(index.get_ancestors(baz)[0]));
+
+        let stat = index.get_enclosing_stat(baz).unwrap();
+        assert_eq!(
+            arena[stat].get_payload_type(),
+            AstNodePayloadType::StatLocal
+        );
+
+        // the root block has no ancestors
+        let root = AstNodeId::Stat(index.get_root());
+        assert!(index.get_ancestors(root).is_empty());
+        assert!(index.get_enclosing_stat(root).is_none());
+    }
+
+    #[test]
+    fn test_find_empty_nodes() {
+        // the missing expression at the end of the source is an empty error node
+        let result = Parser::parse("local a = 1\nlocal b = ", ParseOptions::default());
+        let arena = result.get_arena();
+        let index = index_of(&result);
+
+        let error = index.find_expr(arena, LexPosition::new(1, 10)).unwrap();
+        assert_eq!(
+            arena[error].get_payload_type(),
+            AstNodePayloadType::ExprError
+        );
+
+        // the missing annotation between ':' and '=' is an empty error node inside of the
+        // statement, it's preferred over the local ending at it
+        let result = Parser::parse("local x:= 1", ParseOptions::default());
+        let arena = result.get_arena();
+        let index = index_of(&result);
+
+        let error = index.find(arena, LexPosition::new(0, 8)).unwrap();
+        assert_eq!(payload_type(arena, error), AstNodePayloadType::TypeError);
+        let location = arena.get_location(error);
+        assert_eq!(location.get_begin(), location.get_end());
+        assert!(matches!(index.get_parent(error), Some(AstNodeId::Local(_))));
+
+        // the error node of a missing operand covers the token found instead
+        let result = Parser::parse("local a = (1 + ) * 2", ParseOptions::default());
+        let arena = result.get_arena();
+        let index = index_of(&result);
+
+        let error = index.find_expr(arena, LexPosition::new(0, 15)).unwrap();
+        assert_eq!(
+            arena[error].get_payload_type(),
+            AstNodePayloadType::ExprError
+        );
+        let stat = index.get_enclosing_stat(AstNodeId::Expr(error)).unwrap();
+        assert_eq!(
+            arena[stat].get_payload_type(),
+            AstNodePayloadType::StatLocal
+        );
+    }
+
+    #[test]
+    fn test_enclosing_function() {
+        let src = "local function f(a) return a + 1 end\nfunction a.b() return 2 end\nreturn 3";
+        let result = Parser::parse(src, ParseOptions::default());
+        let arena = result.get_arena();
+        let names = result.get_names();
+        let index = index_of(&result);
+
+        let function_name = |line: u32, column: u32| {
+            let node = index.find(arena, LexPosition::new(line, column)).unwrap();
+            index
+                .get_enclosing_function(arena, node)
+                .map(|(function, body)| {
+                    (
+                        payload_type(arena, function),
+                        String::from(names.get_value(body.get_debug_name())),
+                    )
+                })
+        };
+
+        assert_eq!(
+            function_name(0, 31),
+            Some((AstNodePayloadType::StatLocalFunction, String::from("f")))
+        );
+
+        // a global function stored in a table field is named after the field
+        assert_eq!(
+            function_name(1, 22),
+            Some((AstNodePayloadType::StatFunction, String::from("b")))
+        );
+
+        assert_eq!(function_name(2, 7), None);
+    }
+}
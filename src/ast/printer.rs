@@ -0,0 +1,1265 @@
+use super::{
+    AstArena, AstArgumentName, AstExprId, AstName, AstStatId, AstTypeId, AstTypeList,
+    AstTypeOrPack, AstTypePackId, BinaryOperator, Expr, ExprFunction, LexType, LocalId, NameTable,
+    Stat, TableIndexer, TableKind, Type, TypeFunction, TypePack, UnaryOperator, NAME_ERROR,
+};
+
+// written for names that failed to parse and for assignment targets that are error expressions
+const NAME_PLACEHOLDER: &str = "__error";
+
+// turns a tree back into Luau source, like Luau's Transpiler, parsing the output of a tree
+// without errors gives a tree of the same shape, a tree with errors prints as source that parses
+// without errors: error expressions and types are printed as `nil` / `any`, names that failed to
+// parse as a placeholder name and error statements as the statements they recovered
+pub fn print_chunk(arena: &AstArena, names: &NameTable, block: AstStatId) -> String {
+    let mut printer = Printer::new(arena, names);
+    printer.print_statements(block);
+
+    printer.output
+}
+
+pub fn print_stat(arena: &AstArena, names: &NameTable, stat: AstStatId) -> String {
+    let mut printer = Printer::new(arena, names);
+    printer.print_stat(stat);
+
+    printer.output
+}
+
+pub fn print_expr(arena: &AstArena, names: &NameTable, expr: AstExprId) -> String {
+    let mut printer = Printer::new(arena, names);
+    printer.print_expr(expr, Context::TOP);
+
+    printer.output
+}
+
+pub fn print_type(arena: &AstArena, names: &NameTable, type_: AstTypeId) -> String {
+    let mut printer = Printer::new(arena, names);
+    printer.print_type(type_, false);
+
+    printer.output
+}
+
+pub fn print_type_pack(arena: &AstArena, names: &NameTable, type_pack: AstTypePackId) -> String {
+    let mut printer = Printer::new(arena, names);
+    printer.print_type_pack(type_pack);
+
+    printer.output
+}
+
+// where an expression is printed, decides whether it needs parentheses
+#[derive(Clone, Copy)]
+struct Context {
+    // binary operators binding at most this tight would be parsed as part of the outer expression
+    limit: usize,
+    // the binary operator printed right after the expression
+    follow: Option<BinaryOperator>,
+}
+
+impl Context {
+    const TOP: Context = Context {
+        limit: 0,
+        follow: None,
+    };
+
+    fn get_follow_priority(&self) -> usize {
+        self.follow.map_or(0, |operator| operator.left_priority())
+    }
+}
+
+struct Printer<'a> {
+    arena: &'a AstArena,
+    names: &'a NameTable,
+    output: String,
+    indent: usize,
+}
+
+impl<'a> Printer<'a> {
+    fn new(arena: &'a AstArena, names: &'a NameTable) -> Self {
+        Printer {
+            arena,
+            names,
+            output: String::new(),
+            indent: 0,
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn get_name_value(&self, name: AstName) -> &'a str {
+        let value = self.names.get_value(name);
+
+        if value == NAME_ERROR {
+            NAME_PLACEHOLDER
+        } else {
+            value
+        }
+    }
+
+    fn write_name(&mut self, name: AstName) {
+        let value = self.get_name_value(name);
+        self.write(value);
+    }
+
+    fn write_local(&mut self, local: LocalId, with_annotation: bool) {
+        let local = &self.arena[local];
+        self.write_name(local.get_name());
+
+        if with_annotation {
+            if let Some(annotation) = local.get_annotation() {
+                self.write(": ");
+                self.print_type(annotation, false);
+            }
+        }
+    }
+
+    // a property name, names that aren't identifiers are written as a string key
+    fn write_prop_name(&mut self, name: AstName) {
+        let value = self.get_name_value(name);
+        let is_identifier = value
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+            && value
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+            && matches!(self.names.get_entry(name).get_type(), LexType::Name(_));
+
+        if is_identifier {
+            self.write_name(name);
+        } else {
+            self.write("[");
+            self.write_string(value.as_bytes(), '"');
+            self.write("]");
+        }
+    }
+
+    fn write_string(&mut self, value: &[u8], quote: char) {
+        self.output.push(quote);
+        self.write_string_content(value, quote);
+        self.output.push(quote);
+    }
+
+    // control characters are written as decimal escapes, strings that aren't valid utf-8 are
+    // written byte by byte
+    fn write_string_content(&mut self, value: &[u8], quote: char) {
+        match std::str::from_utf8(value) {
+            Ok(value) => {
+                for ch in value.chars() {
+                    self.write_char(ch, quote);
+                }
+            }
+            Err(_) => {
+                for byte in value {
+                    if byte.is_ascii() {
+                        self.write_char(char::from(*byte), quote);
+                    } else {
+                        self.write(&format!("\\{:03}", byte));
+                    }
+                }
+            }
+        }
+    }
+
+    fn write_char(&mut self, ch: char, quote: char) {
+        match ch {
+            '\\' => self.write("\\\\"),
+            '\n' => self.write("\\n"),
+            '\r' => self.write("\\r"),
+            '\t' => self.write("\\t"),
+            // braces start an expression in interpolated strings
+            '{' if quote == '`' => self.write("\\{"),
+            ch if ch == quote => {
+                self.output.push('\\');
+                self.output.push(ch);
+            }
+            ch if ch.is_control() => {
+                let mut buf = [0u8; 4];
+                for byte in ch.encode_utf8(&mut buf).bytes() {
+                    self.write(&format!("\\{:03}", byte));
+                }
+            }
+            ch => self.output.push(ch),
+        }
+    }
+
+    fn write_number(&mut self, value: f64) {
+        let text = if value.is_nan() {
+            String::from("(0 / 0)")
+        } else if value.is_infinite() {
+            String::from(if value > 0.0 { "1e500" } else { "-1e500" })
+        } else if value != 0.0 && (value.abs() >= 1e16 || value.abs() < 1e-5) {
+            format!("{:e}", value)
+        } else {
+            format!("{}", value)
+        };
+
+        self.write(&text);
+    }
+
+    fn new_line(&mut self) {
+        self.output.push('\n');
+
+        for _ in 0..self.indent {
+            self.write("    ");
+        }
+    }
+
+    // the statements of a block without `do` and `end`, each on its own line
+    fn print_statements(&mut self, block: AstStatId) {
+        let body = match self.arena[block].get_payload() {
+            Stat::Block(body) => body,
+            _ => {
+                self.print_stat(block);
+                return;
+            }
+        };
+
+        let body: Vec<AstStatId> = body
+            .iter()
+            .copied()
+            .filter(|stat| !self.is_empty_error(*stat))
+            .collect();
+
+        let mut previous_end: Option<usize> = None;
+        for (index, stat) in body.iter().enumerate() {
+            if index > 0 {
+                self.new_line();
+            }
+
+            let start = self.output.len();
+            self.print_stat(*stat);
+
+            // `(` on a new line would be read as the arguments of a call ending the previous
+            // statement
+            if let Some(end) = previous_end {
+                if self.output[start..].starts_with('(') && !self.output[..end].ends_with(';') {
+                    self.output.insert(end, ';');
+                }
+            }
+            previous_end = Some(self.output.len());
+        }
+    }
+
+    fn print_body(&mut self, block: AstStatId) {
+        self.indent += 1;
+
+        let is_empty = matches!(
+            self.arena[block].get_payload(),
+            Stat::Block(body) if body.iter().all(|stat| self.is_empty_error(*stat))
+        );
+        if !is_empty {
+            self.new_line();
+            self.print_statements(block);
+        }
+
+        self.indent -= 1;
+        self.new_line();
+    }
+
+    // an error statement that recovered no statements prints nothing and takes no line
+    fn is_empty_error(&self, stat: AstStatId) -> bool {
+        let stat = &self.arena[stat];
+
+        match stat.get_payload() {
+            Stat::Error(error) => {
+                !stat.has_semicolon()
+                    && error
+                        .get_statements()
+                        .iter()
+                        .all(|stat| self.is_empty_error(*stat))
+            }
+            _ => false,
+        }
+    }
+
+    // an assignment target, `nil` can't be assigned to so error expressions are written as a name
+    fn print_var(&mut self, expr: AstExprId) {
+        if let Expr::Error(_) = self.arena[expr].get_payload() {
+            self.write(NAME_PLACEHOLDER);
+        } else {
+            self.print_expr(expr, Context::TOP);
+        }
+    }
+
+    fn print_list(&mut self, exprs: &[AstExprId]) {
+        for (index, expr) in exprs.iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+
+            self.print_expr(*expr, Context::TOP);
+        }
+    }
+
+    fn print_stat(&mut self, id: AstStatId) {
+        let arena = self.arena;
+        let stat = &arena[id];
+
+        match stat.get_payload() {
+            Stat::Assign(assign) => {
+                for (index, var) in assign.get_vars().iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+
+                    self.print_var(*var);
+                }
+                self.write(" = ");
+                self.print_list(assign.get_values());
+            }
+            Stat::Block(_) => {
+                self.write("do");
+                self.print_body(id);
+                self.write("end");
+            }
+            Stat::CompoundAssign(assign) => {
+                self.print_var(assign.get_var());
+                self.write(" ");
+                self.write(binary_operator_text(assign.get_operator()));
+                self.write("= ");
+                self.print_expr(assign.get_value(), Context::TOP);
+            }
+            Stat::DeclareClass(class) => {
+                self.write("declare class ");
+                self.write_name(class.get_name());
+
+                if let Some(super_name) = class.get_super_name() {
+                    self.write(" extends ");
+                    self.write_name(super_name);
+                }
+
+                self.indent += 1;
+                for prop in class.get_props() {
+                    self.new_line();
+
+                    match arena[prop.get_type()].get_payload() {
+                        Type::Function(function) if prop.is_method() => {
+                            self.write("function ");
+                            self.write_name(prop.get_name());
+                            self.print_generics(
+                                function.get_generics(),
+                                function.get_generic_packs(),
+                            );
+                            self.write("(self");
+                            if !function.get_arg_types().get_types().is_empty()
+                                || function.get_arg_types().get_tail_type().is_some()
+                            {
+                                self.write(", ");
+                            }
+                            self.print_params(function.get_arg_types(), function.get_arg_names());
+                            self.write(")");
+                            self.print_return_annotation(function.get_return_types());
+                        }
+                        _ => {
+                            self.write_prop_name(prop.get_name());
+                            self.write(": ");
+                            self.print_type(prop.get_type(), false);
+                        }
+                    }
+                }
+
+                if let Some(indexer) = class.get_indexer() {
+                    self.new_line();
+                    self.print_indexer(indexer);
+                }
+
+                self.indent -= 1;
+                self.new_line();
+                self.write("end");
+            }
+            Stat::DeclareFunction(function) => {
+                self.write("declare function ");
+                self.write_name(function.get_name());
+                self.print_generics(function.get_generics(), function.get_generic_packs());
+                self.write("(");
+                self.print_params(function.get_params(), function.get_param_names());
+                self.write(")");
+                self.print_return_annotation(function.get_return_types());
+            }
+            Stat::DeclareGlobal(global) => {
+                self.write("declare ");
+                self.write_name(global.get_name());
+                self.write(": ");
+                self.print_type(global.get_type(), false);
+            }
+            Stat::Expr(expr) => self.print_expr(expr.get_expr(), Context::TOP),
+            Stat::ForIn(for_in) => {
+                self.write("for ");
+                for (index, var) in for_in.get_vars().iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+
+                    self.write_local(*var, true);
+                }
+                self.write(" in ");
+                self.print_list(for_in.get_values());
+                self.write(" do");
+                self.print_body(for_in.get_body());
+                self.write("end");
+            }
+            Stat::Function(function) => {
+                self.write("function ");
+                self.print_expr(function.get_name(), Context::TOP);
+                self.print_function(function.get_function());
+            }
+            Stat::Local(local) => {
+                self.write("local ");
+                for (index, var) in local.get_vars().iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+
+                    self.write_local(*var, true);
+                }
+
+                if !local.get_values().is_empty() {
+                    self.write(" = ");
+                    self.print_list(local.get_values());
+                }
+            }
+            Stat::LocalFunction(function) => {
+                self.write("local function ");
+                self.write_local(function.get_name(), false);
+                self.print_function(function.get_function());
+            }
+            Stat::Break => self.write("break"),
+            Stat::Continue => self.write("continue"),
+            Stat::For(for_) => {
+                self.write("for ");
+                self.write_local(for_.get_var(), true);
+                self.write(" = ");
+                self.print_expr(for_.get_from(), Context::TOP);
+                self.write(", ");
+                self.print_expr(for_.get_to(), Context::TOP);
+
+                if let Some(step) = for_.get_step() {
+                    self.write(", ");
+                    self.print_expr(step, Context::TOP);
+                }
+
+                self.write(" do");
+                self.print_body(for_.get_body());
+                self.write("end");
+            }
+            Stat::If(_) => {
+                self.write("if ");
+                self.print_if(id);
+            }
+            Stat::Repeat(repeat) => {
+                self.write("repeat");
+                self.print_body(repeat.get_body());
+                self.write("until ");
+                self.print_expr(repeat.get_condition(), Context::TOP);
+            }
+            Stat::Return(return_) => {
+                self.write("return");
+
+                if !return_.get_list().is_empty() {
+                    self.write(" ");
+                    self.print_list(return_.get_list());
+                }
+            }
+            Stat::While(while_) => {
+                self.write("while ");
+                self.print_expr(while_.get_condition(), Context::TOP);
+                self.write(" do");
+                self.print_body(while_.get_body());
+                self.write("end");
+            }
+            Stat::TypeAlias(alias) => {
+                if alias.is_exported() {
+                    self.write("export ");
+                }
+
+                self.write("type ");
+                self.write_name(alias.get_name());
+
+                if !alias.get_generics().is_empty() || !alias.get_generic_packs().is_empty() {
+                    self.write("<");
+
+                    let mut first = true;
+                    for generic in alias.get_generics() {
+                        if !first {
+                            self.write(", ");
+                        }
+                        first = false;
+
+                        self.write_name(generic.get_name());
+                        if let Some(default_value) = generic.get_default_value() {
+                            self.write(" = ");
+                            self.print_type(default_value, false);
+                        }
+                    }
+
+                    for generic in alias.get_generic_packs() {
+                        if !first {
+                            self.write(", ");
+                        }
+                        first = false;
+
+                        self.write_name(generic.get_name());
+                        self.write("...");
+                        if let Some(default_value) = generic.get_default_value() {
+                            self.write(" = ");
+                            self.print_type_pack(default_value);
+                        }
+                    }
+
+                    self.write(">");
+                }
+
+                self.write(" = ");
+                self.print_type(alias.get_type(), false);
+            }
+            // the statements that were recovered are kept, the rest of the error is dropped
+            Stat::Error(error) => {
+                let statements: Vec<AstStatId> = error
+                    .get_statements()
+                    .iter()
+                    .copied()
+                    .filter(|stat| !self.is_empty_error(*stat))
+                    .collect();
+                for (index, stat) in statements.iter().enumerate() {
+                    if index > 0 {
+                        self.new_line();
+                    }
+
+                    self.print_stat(*stat);
+                }
+            }
+        }
+
+        if stat.has_semicolon() {
+            self.write(";");
+        }
+    }
+
+    // the condition and branches of an if statement, `if` or `elseif` is already written
+    fn print_if(&mut self, id: AstStatId) {
+        let arena = self.arena;
+        let if_ = match arena[id].get_payload() {
+            Stat::If(if_) => if_,
+            _ => return,
+        };
+
+        self.print_expr(if_.get_condition(), Context::TOP);
+        self.write(" then");
+        self.print_body(if_.get_then_body());
+
+        match if_.get_else_body() {
+            Some(else_body) if matches!(arena[else_body].get_payload(), Stat::If(_)) => {
+                self.write("elseif ");
+                self.print_if(else_body);
+            }
+            Some(else_body) => {
+                self.write("else");
+                self.print_body(else_body);
+                self.write("end");
+            }
+            None => self.write("end"),
+        }
+    }
+
+    // the signature and body of a function, from the generics to `end`
+    fn print_function(&mut self, function: &ExprFunction) {
+        self.print_generics(function.get_generics(), function.get_generic_packs());
+
+        self.write("(");
+        for (index, arg) in function.get_args().iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+
+            self.write_local(*arg, true);
+        }
+
+        if function.is_vararg() {
+            if !function.get_args().is_empty() {
+                self.write(", ");
+            }
+
+            self.write("...");
+            if let Some(annotation) = function.get_vararg_annotation() {
+                self.write(": ");
+                self.print_vararg_annotation(annotation);
+            }
+        }
+        self.write(")");
+
+        if let Some(return_types) = function.get_return_annotation() {
+            self.write(": ");
+            self.print_return_types(return_types);
+        }
+
+        self.print_body(function.get_body());
+        self.write("end");
+    }
+
+    fn print_generics(&mut self, generics: &[AstName], generic_packs: &[AstName]) {
+        if generics.is_empty() && generic_packs.is_empty() {
+            return;
+        }
+
+        self.write("<");
+        for (index, generic) in generics.iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+
+            self.write_name(*generic);
+        }
+
+        for (index, generic) in generic_packs.iter().enumerate() {
+            if index > 0 || !generics.is_empty() {
+                self.write(", ");
+            }
+
+            self.write_name(*generic);
+            self.write("...");
+        }
+        self.write(">");
+    }
+
+    // the parameters of a declared or function type, the tail is written as `...: T`
+    fn print_params(&mut self, params: &AstTypeList, names: &[Option<AstArgumentName>]) {
+        for (index, type_) in params.get_types().iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+
+            if let Some(Some(name)) = names.get(index) {
+                self.write_name(name.get_name());
+                self.write(": ");
+            }
+
+            self.print_type(*type_, false);
+        }
+
+        if let Some(tail_type) = params.get_tail_type() {
+            if !params.get_types().is_empty() {
+                self.write(", ");
+            }
+
+            self.write("...: ");
+            self.print_vararg_annotation(tail_type);
+        }
+    }
+
+    fn print_vararg_annotation(&mut self, annotation: AstTypePackId) {
+        match self.arena[annotation].get_payload() {
+            TypePack::Variadic(type_) => self.print_type(*type_, false),
+            _ => self.print_type_pack(annotation),
+        }
+    }
+
+    // an empty return list is left out, it is what a missing annotation is parsed as
+    fn print_return_annotation(&mut self, return_types: &AstTypeList) {
+        if !return_types.get_types().is_empty() || return_types.get_tail_type().is_some() {
+            self.write(": ");
+            self.print_return_types(return_types);
+        }
+    }
+
+    // a single type or a single type pack is written without parentheses
+    fn print_return_types(&mut self, return_types: &AstTypeList) {
+        match (return_types.get_types(), return_types.get_tail_type()) {
+            ([type_], None) => self.print_type(*type_, false),
+            ([], Some(tail_type)) => self.print_type_pack(tail_type),
+            _ => self.print_type_list(return_types),
+        }
+    }
+
+    fn print_type_list(&mut self, types: &AstTypeList) {
+        self.write("(");
+        for (index, type_) in types.get_types().iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+
+            self.print_type(*type_, false);
+        }
+
+        if let Some(tail_type) = types.get_tail_type() {
+            if !types.get_types().is_empty() {
+                self.write(", ");
+            }
+
+            self.print_type_pack(tail_type);
+        }
+        self.write(")");
+    }
+
+    fn print_indexer(&mut self, indexer: &TableIndexer) {
+        self.write("[");
+        self.print_type(indexer.get_index_type(), false);
+        self.write("]: ");
+        self.print_type(indexer.get_result_type(), false);
+    }
+
+    fn print_expr(&mut self, id: AstExprId, context: Context) {
+        if self.needs_parentheses(id, context) {
+            self.write("(");
+            self.print_expr_payload(id, Context::TOP);
+            self.write(")");
+        } else {
+            self.print_expr_payload(id, context);
+        }
+    }
+
+    fn needs_parentheses(&self, id: AstExprId, context: Context) -> bool {
+        let follow = context.get_follow_priority();
+
+        match self.arena[id].get_payload() {
+            Expr::Binary(binary) => {
+                let operator = binary.get_operator();
+
+                operator.left_priority() <= context.limit || follow > operator.right_priority()
+            }
+            // the operand of a unary operator takes the operators binding tighter than it
+            Expr::Unary(unary) => follow > unary.get_operator().priority(),
+            Expr::ConstantNumber(value) if value.is_sign_negative() => {
+                follow > UnaryOperator::Minus.priority()
+            }
+            // the else branch takes everything that follows
+            Expr::IfElse(_) => context.follow.is_some(),
+            // `<` would start the parameters of the asserted type
+            Expr::TypeAssertion(_) => context.follow == Some(BinaryOperator::Less),
+            _ => false,
+        }
+    }
+
+    // calls and indexing need a prefix expression on their left
+    fn print_prefix_expr(&mut self, id: AstExprId) {
+        let is_prefix = matches!(
+            self.arena[id].get_payload(),
+            Expr::Group(_)
+                | Expr::Local(_)
+                | Expr::Global(_)
+                | Expr::Call(_)
+                | Expr::IndexName(_)
+                | Expr::IndexExpr(_)
+        );
+
+        if is_prefix {
+            self.print_expr_payload(id, Context::TOP);
+        } else {
+            self.write("(");
+            self.print_expr_payload(id, Context::TOP);
+            self.write(")");
+        }
+    }
+
+    fn print_expr_payload(&mut self, id: AstExprId, context: Context) {
+        let arena = self.arena;
+
+        match arena[id].get_payload() {
+            Expr::Group(expr) => {
+                self.write("(");
+                self.print_expr(*expr, Context::TOP);
+                self.write(")");
+            }
+            Expr::ConstantNil => self.write("nil"),
+            Expr::ConstantBool(value) => self.write(if *value { "true" } else { "false" }),
+            Expr::ConstantNumber(value) => self.write_number(*value),
+            Expr::ConstantString(value) => self.write_string(value, '"'),
+            Expr::Local(local) => self.write_local(local.get_local(), false),
+            Expr::Global(name) => self.write_name(*name),
+            Expr::Varargs => self.write("..."),
+            Expr::Call(call) => {
+                self.print_prefix_expr(call.get_func());
+                self.write("(");
+                self.print_list(call.get_args());
+                self.write(")");
+            }
+            Expr::IndexName(index) => {
+                self.print_prefix_expr(index.get_expr());
+                self.output.push(index.get_op());
+                self.write_name(index.get_index());
+            }
+            Expr::IndexExpr(index) => {
+                self.print_prefix_expr(index.get_expr());
+                self.write("[");
+                self.print_expr(index.get_index(), Context::TOP);
+                self.write("]");
+            }
+            Expr::Binary(binary) => {
+                let operator = binary.get_operator();
+
+                self.print_expr(
+                    binary.get_left(),
+                    Context {
+                        limit: context.limit,
+                        follow: Some(operator),
+                    },
+                );
+                self.write(" ");
+                self.write(binary_operator_text(operator));
+                self.write(" ");
+                self.print_expr(
+                    binary.get_right(),
+                    Context {
+                        limit: operator.right_priority(),
+                        follow: context.follow,
+                    },
+                );
+            }
+            Expr::Function(function) => {
+                self.write("function");
+                self.print_function(function);
+            }
+            Expr::IfElse(_) => {
+                self.write("if ");
+                self.print_if_else(id);
+            }
+            Expr::InterpString(interp) => {
+                self.output.push('`');
+                for (index, value) in interp.get_strings().iter().enumerate() {
+                    self.write_string_content(value, '`');
+
+                    if let Some(expr) = interp.get_expressions().get(index) {
+                        self.write("{");
+
+                        // `{{` isn't allowed, a table is separated from the brace
+                        let start = self.output.len();
+                        self.print_expr(*expr, Context::TOP);
+                        if self.output[start..].starts_with('{') {
+                            self.output.insert(start, ' ');
+                            self.write(" ");
+                        }
+
+                        self.write("}");
+                    }
+                }
+                self.output.push('`');
+            }
+            Expr::Table(items) => {
+                if items.is_empty() {
+                    self.write("{}");
+                    return;
+                }
+
+                self.write("{ ");
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+
+                    match (item.get_kind(), item.get_key()) {
+                        (TableKind::Record, Some(key)) => {
+                            if let Expr::ConstantString(name) = arena[key].get_payload() {
+                                self.write(&String::from_utf8_lossy(name));
+                            }
+                            self.write(" = ");
+                        }
+                        (TableKind::General, Some(key)) => {
+                            self.write("[");
+                            self.print_expr(key, Context::TOP);
+                            self.write("] = ");
+                        }
+                        _ => {}
+                    }
+
+                    self.print_expr(item.get_value(), Context::TOP);
+                }
+                self.write(" }");
+            }
+            Expr::TypeAssertion(assertion) => {
+                // the asserted expression is a simple expression
+                let expr = assertion.get_expr();
+                let is_simple = !matches!(
+                    arena[expr].get_payload(),
+                    Expr::Binary(_) | Expr::Unary(_) | Expr::IfElse(_) | Expr::TypeAssertion(_)
+                ) && !matches!(arena[expr].get_payload(), Expr::ConstantNumber(value) if value.is_sign_negative());
+
+                if is_simple {
+                    self.print_expr(expr, Context::TOP);
+                } else {
+                    self.write("(");
+                    self.print_expr(expr, Context::TOP);
+                    self.write(")");
+                }
+
+                self.write(" :: ");
+                self.print_type(assertion.get_annotation(), false);
+            }
+            Expr::Unary(unary) => {
+                self.write(match unary.get_operator() {
+                    UnaryOperator::Not => "not ",
+                    UnaryOperator::Minus => "-",
+                    UnaryOperator::Len => "#",
+                });
+
+                let start = self.output.len();
+                self.print_expr(
+                    unary.get_expr(),
+                    Context {
+                        limit: unary.get_operator().priority(),
+                        follow: context.follow,
+                    },
+                );
+
+                // `--` would start a comment
+                if self.output[start..].starts_with('-') {
+                    self.output.insert(start, ' ');
+                }
+            }
+            Expr::Error(_) => self.write("nil"),
+        }
+    }
+
+    // the condition and branches of an if expression, `if` or `elseif` is already written
+    fn print_if_else(&mut self, id: AstExprId) {
+        let arena = self.arena;
+        let if_else = match arena[id].get_payload() {
+            Expr::IfElse(if_else) => if_else,
+            _ => return,
+        };
+
+        self.print_expr(if_else.get_condition(), Context::TOP);
+        self.write(" then ");
+        match if_else.get_true_expr() {
+            Some(expr) => self.print_expr(expr, Context::TOP),
+            None => self.write("nil"),
+        }
+
+        match if_else.get_false_expr() {
+            Some(expr) if matches!(arena[expr].get_payload(), Expr::IfElse(_)) => {
+                self.write(" elseif ");
+                self.print_if_else(expr);
+            }
+            Some(expr) => {
+                self.write(" else ");
+                self.print_expr(expr, Context::TOP);
+            }
+            None => self.write(" else nil"),
+        }
+    }
+
+    // members of unions and intersections are parenthesized when they are unions, intersections
+    // or functions themselves
+    fn print_type(&mut self, id: AstTypeId, is_member: bool) {
+        let arena = self.arena;
+        let payload = arena[id].get_payload();
+
+        if is_member
+            && matches!(
+                payload,
+                Type::Union(_) | Type::Intersection(_) | Type::Function(_)
+            )
+        {
+            self.write("(");
+            self.print_type(id, false);
+            self.write(")");
+            return;
+        }
+
+        match payload {
+            Type::Error(_) => self.write("any"),
+            Type::Function(function) => self.print_function_type(function),
+            Type::Intersection(types) => self.print_type_members(types, " & "),
+            Type::Reference(reference) => {
+                if let Some(prefix) = reference.get_prefix() {
+                    self.write_name(prefix);
+                    self.write(".");
+                }
+                self.write_name(reference.get_name());
+
+                if let Some(parameters) = reference.get_parameters() {
+                    self.write("<");
+                    for (index, parameter) in parameters.iter().enumerate() {
+                        if index > 0 {
+                            self.write(", ");
+                        }
+
+                        match *parameter {
+                            AstTypeOrPack::Type(type_) => self.print_type(type_, false),
+                            AstTypeOrPack::TypePack(type_pack) => self.print_type_pack(type_pack),
+                        }
+                    }
+                    self.write(">");
+                }
+            }
+            Type::SingletonBool(value) => self.write(if *value { "true" } else { "false" }),
+            Type::SingletonString(value) => self.write_string(value, '"'),
+            Type::Table(table) => {
+                if table.get_props().is_empty() && table.get_indexer().is_none() {
+                    self.write("{}");
+                    return;
+                }
+
+                self.write("{ ");
+                for (index, prop) in table.get_props().iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+
+                    self.write_prop_name(prop.get_name());
+                    self.write(": ");
+                    self.print_type(prop.get_type(), false);
+                }
+
+                if let Some(indexer) = table.get_indexer() {
+                    if !table.get_props().is_empty() {
+                        self.write(", ");
+                    }
+
+                    self.print_indexer(indexer);
+                }
+                self.write(" }");
+            }
+            Type::Typeof(expr) => {
+                self.write("typeof(");
+                self.print_expr(*expr, Context::TOP);
+                self.write(")");
+            }
+            Type::Union(types) => self.print_type_members(types, " | "),
+        }
+    }
+
+    fn print_type_members(&mut self, types: &[AstTypeId], separator: &str) {
+        for (index, type_) in types.iter().enumerate() {
+            if index > 0 {
+                self.write(separator);
+            }
+
+            self.print_type(*type_, true);
+        }
+    }
+
+    fn print_function_type(&mut self, function: &TypeFunction) {
+        self.print_generics(function.get_generics(), function.get_generic_packs());
+
+        self.write("(");
+        let arg_types = function.get_arg_types();
+        for (index, type_) in arg_types.get_types().iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+
+            if let Some(Some(name)) = function.get_arg_names().get(index) {
+                self.write_name(name.get_name());
+                self.write(": ");
+            }
+
+            self.print_type(*type_, false);
+        }
+
+        if let Some(tail_type) = arg_types.get_tail_type() {
+            if !arg_types.get_types().is_empty() {
+                self.write(", ");
+            }
+
+            self.print_type_pack(tail_type);
+        }
+        self.write(") -> ");
+
+        self.print_return_types(function.get_return_types());
+    }
+
+    fn print_type_pack(&mut self, id: AstTypePackId) {
+        match self.arena[id].get_payload() {
+            TypePack::Explicit(types) => self.print_type_list(types),
+            TypePack::Generic(name) => {
+                self.write_name(*name);
+                self.write("...");
+            }
+            TypePack::Variadic(type_) => {
+                self.write("...");
+                self.print_type(*type_, false);
+            }
+        }
+    }
+}
+
+fn binary_operator_text(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Add => "+",
+        BinaryOperator::Sub => "-",
+        BinaryOperator::Mul => "*",
+        BinaryOperator::Div => "/",
+        BinaryOperator::FloorDiv => "//",
+        BinaryOperator::Mod => "%",
+        BinaryOperator::Pow => "^",
+        BinaryOperator::Concat => "..",
+        BinaryOperator::NotEqual => "~=",
+        BinaryOperator::Equal => "==",
+        BinaryOperator::Less => "<",
+        BinaryOperator::LessEqual => "<=",
+        BinaryOperator::Greater => ">",
+        BinaryOperator::GreaterEqual => ">=",
+        BinaryOperator::And => "and",
+        BinaryOperator::Or => "or",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::{block_body, return_list, tree_shape};
+    use super::super::{AstNodeId, ParseOptions, ParseResult, Parser};
+    use super::*;
+
+    fn print_result(result: &ParseResult) -> String {
+        print_chunk(result.get_arena(), result.get_names(), result.get_root_id())
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let sources = [
+            "local x: number = 1\nlocal a, b = 1, 2;\nx += 1; x -= 2; x ..= \"s\"; x //= 3",
+            "local t = { 1, 2, a = 3, [4] = 5, [\"k\"] = f(), {} }",
+            "local s = 'quote\" \\\\ \\n \\0 \\t \\255 é', [[long\nstring]]",
+            "local i = `hello {name}, {1 + 2} \\{ \\` { {1} }`",
+            "local v = -x ^ 2, (-x) ^ 2, not a == b, a .. b .. c, (a .. b) .. c, a - (b - c)",
+            "local v = a - b - c, 2 ^ 3 ^ 4, (2 ^ 3) ^ 4, - -x, #t, a and b or c, -(a + b)",
+            "local n = 1e300, 0.1, 123, 0x10, 1e-7, 1e999, 2 ^ 53",
+            "local y = if a then b elseif c then d else e\nlocal z = (if a then b else c) + 1",
+            "local w = x :: number, (x :: any) :: string, (a + b) :: number, (x :: T) < y",
+            "f(x)(y).z:m(1)[2] = 1\n(\"s\"):len()\nlocal a = b;\n(f)()",
+            "function t.a.b:c<T>(x: T, ...: number): (T, ...string)\n  return x, self\nend",
+            "local function g<T...>(...: T...): T... return ... end\nlocal function h(): () end",
+            "local r = function(a, b, ...) return a end, function(): number | string end",
+            "for i = 1, 10, 2 do break end\nfor k: string, v in pairs(t) do continue end",
+            "while true do end\nrepeat local q = 1 until q\ndo local x end",
+            "if a then elseif b then f() else end\nif a then else if b then end end",
+            "type A<T = number, U... = ...string> = (T, U...) -> ()",
+            "export type B = { a: number, [string]: boolean, [\"not a name\"]: nil, [\"end\"]: string }",
+            "type C = (number) -> string | (() -> number)?\ntype D = ((number) -> string) | nil",
+            "type E = A<number, ...string> & B & { number }\ntype F = typeof(x.y) | \"str\" | true",
+            "type G = Foo<(number, string), (), T...>\ntype H = <T, U...>(a: T, U...) -> ...U",
+            "type I = (m.T<number>?) & {}\ntype J = () -> (number, string)\ntype K = (a | b) & c",
+            "declare function foo<T>(a: T, ...: number): string\ndeclare g: number",
+            "declare class Foo extends Bar\n x: number\n [\"y z\"]: string\n function m(self, a: number): string\n [string]: any\nend",
+        ];
+
+        let mut options = ParseOptions::default();
+        options.set_allow_declaration_syntax(true);
+
+        for src in sources.iter() {
+            let result = Parser::parse(src, options);
+            assert!(result.get_errors().is_empty(), "{}", src);
+            let printed = print_result(&result);
+
+            let reparsed = Parser::parse(&printed, options);
+            let errors: Vec<&str> = reparsed
+                .get_errors()
+                .iter()
+                .map(|error| error.get_message())
+                .collect();
+            assert!(errors.is_empty(), "{}\n{:?}", printed, errors);
+
+            assert_eq!(
+                tree_shape(
+                    reparsed.get_arena(),
+                    AstNodeId::Stat(reparsed.get_root_id())
+                ),
+                tree_shape(result.get_arena(), AstNodeId::Stat(result.get_root_id())),
+                "{}",
+                printed
+            );
+            assert_eq!(print_result(&reparsed), printed);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_errors() {
+        let sources = [
+            "local function() end",
+            "local = 1",
+            "function a.() end",
+            "for = 1, 2 do end",
+            "local x: = 1",
+            "export type = number",
+            ") local x = 1",
+            "local a = 1 +\nlocal b = (((c +",
+            "foo(1, 2\nbar()",
+            "local function f(a, b c) return a end\nf()",
+            "local a = (1 + 2\nx = 1",
+            "a.b\nreturn",
+            "while true do\nlocal a = 1",
+            "x = \na. = 1",
+            "1 = 2\na, 1 = 2, 3\nf() += 1",
+            "local t = { a = , [] = 1 }",
+            "if then elseif else end",
+            "type T = { : number }\nlocal v: T< = 1",
+            "return return",
+        ];
+
+        for src in sources.iter() {
+            let result = Parser::parse(src, ParseOptions::default());
+            assert!(!result.get_errors().is_empty(), "{}", src);
+            let printed = print_result(&result);
+
+            let reparsed = Parser::parse(&printed, ParseOptions::default());
+            let errors: Vec<&str> = reparsed
+                .get_errors()
+                .iter()
+                .map(|error| error.get_message())
+                .collect();
+            assert!(errors.is_empty(), "{}\n{}\n{:?}", src, printed, errors);
+            assert_eq!(print_result(&reparsed), printed);
+        }
+
+        let print = |src: &str| print_result(&Parser::parse(src, ParseOptions::default()));
+        assert_eq!(
+            print("local function() end"),
+            "local function __error()\nend"
+        );
+        assert_eq!(print("1, a = 2"), "__error, a = 2");
+    }
+
+    #[test]
+    fn test_format() {
+        let print = |src: &str| print_result(&Parser::parse(src, ParseOptions::default()));
+
+        assert_eq!(
+            print("return a+b*c,(a+b)*c,-x^2,a..b..c"),
+            "return a + b * c, (a + b) * c, -x ^ 2, a .. b .. c"
+        );
+        assert_eq!(
+            print("local function f(a:number,...:string):string if a then return 'x' end end"),
+            "local function f(a: number, ...: string): string\n    if a then\n        return \"x\"\n    end\nend"
+        );
+        assert_eq!(
+            print("type T<U> ={[string]:U,n:number}"),
+            "type T<U> = { n: number, [string]: U }"
+        );
+    }
+
+    #[test]
+    fn test_precedence() {
+        let mut result = Parser::parse(
+            "return a + b, c * d, e ^ f, -g, h - i",
+            ParseOptions::default(),
+        );
+        let root = result.get_root_id();
+        let list = return_list(result.get_arena(), block_body(result.get_arena(), root)[0]);
+        let operands = |arena: &AstArena, id: AstExprId| match arena[id].get_payload() {
+            Expr::Binary(binary) => (binary.get_left(), binary.get_right()),
+            Expr::Unary(unary) => (unary.get_expr(), unary.get_expr()),
+            _ => panic!("failed"),
+        };
+
+        // operands are replaced by nodes that bind weaker than their parent, the unary operator
+        // shares its operand with the one replacing e
+        let arena = result.get_arena_mut();
+        let add = arena[list[0]].get_payload().clone();
+        let minus = arena[list[3]].get_payload().clone();
+        let (c, d) = operands(arena, list[1]);
+        let (e, _) = operands(arena, list[2]);
+        let (g, _) = operands(arena, list[3]);
+        let (_, i) = operands(arena, list[4]);
+        *arena[c].get_payload_mut() = add.clone();
+        *arena[d].get_payload_mut() = Expr::ConstantNumber(-1.0);
+        *arena[e].get_payload_mut() = minus;
+        *arena[g].get_payload_mut() = Expr::ConstantNumber(-2.0);
+        *arena[i].get_payload_mut() = add;
+
+        assert_eq!(
+            print_result(&result),
+            "return a + b, (a + b) * -1, (- -2) ^ f, - -2, h - (a + b)"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use super::{get_children, AstArena, AstExprId, AstNodeId, AstStatId, Expr, NameTable, Stat};
+
+// helpers shared by the test modules of the tree, parsing and printing
+
+pub fn expr_shape(arena: &AstArena, expr: AstExprId, names: &NameTable) -> String {
+    match arena[expr].get_payload() {
+        Expr::Binary(binary) => format!(
+            "({:?} {} {})",
+            binary.get_operator(),
+            expr_shape(arena, binary.get_left(), names),
+            expr_shape(arena, binary.get_right(), names)
+        ),
+        Expr::Unary(unary) => {
+            format!(
+                "({:?} {})",
+                unary.get_operator(),
+                expr_shape(arena, unary.get_expr(), names)
+            )
+        }
+        Expr::TypeAssertion(assertion) => {
+            format!("(:: {})", expr_shape(arena, assertion.get_expr(), names))
+        }
+        Expr::Group(expr) => format!("[{}]", expr_shape(arena, *expr, names)),
+        Expr::ConstantNumber(value) => format!("{}", value),
+        Expr::Global(name) => String::from(names.get_value(*name)),
+        _ => String::from("?"),
+    }
+}
+
+pub fn block_body(arena: &AstArena, stat: AstStatId) -> Vec<AstStatId> {
+    if let Stat::Block(body) = arena[stat].get_payload() {
+        body.clone()
+    } else {
+        panic!("failed");
+    }
+}
+
+pub fn return_list(arena: &AstArena, stat: AstStatId) -> Vec<AstExprId> {
+    if let Stat::Return(return_) = arena[stat].get_payload() {
+        return_.get_list().to_vec()
+    } else {
+        panic!("failed");
+    }
+}
+
+// the kinds of a node and its descendants, children are listed in parentheses
+pub fn tree_shape(arena: &AstArena, node: AstNodeId) -> String {
+    let kind = match node {
+        AstNodeId::Expr(id) => format!("{:?}", arena[id].get_payload_type()),
+        AstNodeId::Stat(id) => format!("{:?}", arena[id].get_payload_type()),
+        AstNodeId::Type(id) => format!("{:?}", arena[id].get_payload_type()),
+        AstNodeId::TypePack(id) => format!("{:?}", arena[id].get_payload_type()),
+        AstNodeId::Local(_) => String::from("Local"),
+    };
+
+    let children: Vec<String> = get_children(arena, node)
+        .into_iter()
+        .map(|child| tree_shape(arena, child))
+        .collect();
+    if children.is_empty() {
+        kind
+    } else {
+        format!("{}({})", kind, children.join(" "))
+    }
+}
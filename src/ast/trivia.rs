@@ -0,0 +1,41 @@
+use super::LexLocation;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriviaKind {
+    // spaces, tabs and carriage returns that don't end a line
+    Whitespace,
+    // a single line break, either \n or \r\n
+    Newline,
+    Comment,
+    BlockComment,
+}
+
+// source text between lexemes, only collected by a lossless lexer
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trivia {
+    kind: TriviaKind,
+    location: LexLocation,
+    text: String,
+}
+
+impl Trivia {
+    pub fn new(kind: TriviaKind, location: LexLocation, text: &str) -> Self {
+        Trivia {
+            kind,
+            location,
+            text: String::from(text),
+        }
+    }
+
+    pub fn get_kind(&self) -> TriviaKind {
+        self.kind
+    }
+
+    pub fn get_location(&self) -> LexLocation {
+        self.location
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
@@ -1,5 +1,4 @@
-mod ast;
-
+pub mod ast;
 
 #[cfg(test)]
 mod tests {